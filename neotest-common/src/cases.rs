@@ -0,0 +1,24 @@
+use std::any::type_name;
+use std::str::FromStr;
+
+/// Parses the `value` of a `column` from a file of test-cases, panicking with
+/// the `location` of the case in the file if it is not a valid `T`.
+///
+/// # Arguments
+///
+/// * `value` - the value to parse
+/// * `column` - the name of the column of the value
+/// * `location` - the file and line of the case, e.g. `tests/data/parse.csv:3`
+#[doc(hidden)]
+pub fn __parse_case<T>(value: &str, column: &str, location: &str) -> T
+where
+  T: FromStr,
+{
+  match value.parse() {
+    Ok(value) => value,
+    Err(_) => panic!(
+      "{location}: '{value}' is not a valid `{}` for column '{column}'",
+      type_name::<T>()
+    ),
+  }
+}
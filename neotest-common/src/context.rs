@@ -3,9 +3,11 @@
 ///
 /// This enables subsections of tests to each be executed independently.
 #[doc(hidden)]
+#[derive(Clone)]
 pub struct __Context {
   section_path: &'static [usize],
   current: usize,
+  expects_panic: bool,
 }
 
 #[allow(dead_code)]
@@ -20,9 +22,25 @@ impl __Context {
     Self {
       section_path,
       current: 0,
+      expects_panic: false,
     }
   }
 
+  /// Marks the test-case of this [`__Context`] as expected to panic, as with
+  /// an input that is given `#[should_panic]`.
+  #[doc(hidden)]
+  pub fn expecting_panic(self) -> Self {
+    Self {
+      expects_panic: true,
+      ..self
+    }
+  }
+
+  /// Tests whether the test-case of this [`__Context`] is expected to panic
+  pub fn expects_panic(&self) -> bool {
+    self.expects_panic
+  }
+
   /// Tests whether a context is allowed to execute a subtest
   ///
   /// # Developer Note
@@ -40,6 +58,7 @@ impl __Context {
     Self {
       section_path: self.pop_prefix(),
       current: 0,
+      expects_panic: self.expects_panic,
     }
   }
 
@@ -0,0 +1,345 @@
+use std::any::type_name;
+use std::panic;
+use std::thread;
+
+use crate::{AsyncFixture, Fixture, Result, TeardownError, TestOutcome, TestResult};
+
+/// Determines the outcome of a test from its `result`.
+///
+/// A test-case that is expected to panic passes only by panicking, and so its
+/// fixtures are torn down as passing when it does.
+///
+/// # Arguments
+///
+/// * `result` - the result of the test, as returned by [`panic::catch_unwind`]
+/// * `expects_panic` - whether the test-case is expected to panic
+#[doc(hidden)]
+pub fn __outcome(result: &thread::Result<TestResult>, expects_panic: bool) -> TestOutcome {
+  match (result, expects_panic) {
+    (Ok(Ok(())), false) => TestOutcome::Passed,
+    (Ok(_), _) => TestOutcome::Failed,
+    (Err(_), false) => TestOutcome::Panicked,
+    (Err(_), true) => TestOutcome::Passed,
+  }
+}
+
+/// The undocumented [`__FixtureGuard`] object is an internal neotest utility
+/// that holds a test-scoped fixture while the remaining fixtures of its test
+/// are prepared.
+///
+/// If the test is abandoned before it runs -- such as when a later fixture
+/// fails to be prepared -- the fixture is torn down when the guard is dropped,
+/// with a failed outcome. Guards are dropped in the reverse order that they
+/// were created, and so fixtures are torn down in the reverse order that they
+/// were prepared.
+#[doc(hidden)]
+pub struct __FixtureGuard<T, F>
+where
+  F: FnOnce(T, &TestOutcome) -> Result<()>,
+{
+  fixture: Option<(T, F)>,
+  test: &'static str,
+}
+
+impl<T, F> __FixtureGuard<T, F>
+where
+  F: FnOnce(T, &TestOutcome) -> Result<()>,
+{
+  /// Constructs a new [`__FixtureGuard`] that tears down the `fixture` with
+  /// `tear_down` unless the test that uses it is run.
+  ///
+  /// # Arguments
+  ///
+  /// * `fixture` - the prepared fixture
+  /// * `tear_down` - the function that tears down the fixture
+  /// * `test` - the name of the test that uses the fixture
+  #[doc(hidden)]
+  pub fn new(fixture: T, tear_down: F, test: &'static str) -> Self {
+    Self {
+      fixture: Some((fixture, tear_down)),
+      test,
+    }
+  }
+
+  /// Releases the fixture to be used by its test, which is then responsible
+  /// for tearing it down.
+  #[doc(hidden)]
+  pub fn into_inner(mut self) -> T {
+    self.fixture.take().unwrap().0
+  }
+}
+
+impl<T, F> Drop for __FixtureGuard<T, F>
+where
+  F: FnOnce(T, &TestOutcome) -> Result<()>,
+{
+  fn drop(&mut self) {
+    if let Some((fixture, tear_down)) = self.fixture.take() {
+      if let Err(e) = tear_down(fixture, &TestOutcome::Failed) {
+        eprintln!("{}", TeardownError::new(type_name::<T>(), self.test, e));
+      }
+    }
+  }
+}
+
+/// Asynchronously tears down the `fixture` of the test named `test`, which was
+/// abandoned before it ran -- such as when a later fixture failed to be
+/// prepared.
+///
+/// This is the counterpart of [`__FixtureGuard`] for fixtures of `async fn`
+/// tests, whose teardown cannot be awaited when a guard is dropped. Teardown
+/// errors are written to stderr, so that the setup error is still what gets
+/// reported.
+///
+/// # Arguments
+///
+/// * `fixture` - the fixture to tear down
+/// * `test` - the name of the test that used the fixture
+#[doc(hidden)]
+pub async fn __abandon_async<T>(fixture: T, test: &'static str)
+where
+  T: AsyncFixture,
+{
+  if let Err(e) = fixture.tear_down(&TestOutcome::Failed).await {
+    eprintln!("{}", TeardownError::new(type_name::<T>(), test, e));
+  }
+}
+
+/// Tears down the `fixture` used by the test named `test`, given the `outcome`
+/// and current `result` of running the test.
+///
+/// Teardown errors replace the result of the test only if the test itself
+/// succeeded; otherwise they are written to stderr so that the original
+/// failure is still what gets reported.
+///
+/// # Arguments
+///
+/// * `fixture` - the fixture to tear down
+/// * `test` - the name of the test that used the fixture
+/// * `outcome` - the outcome of the test
+/// * `result` - the result of the test, as returned by [`panic::catch_unwind`]
+#[doc(hidden)]
+pub fn __tear_down<T>(
+  fixture: T,
+  test: &'static str,
+  outcome: &TestOutcome,
+  result: thread::Result<TestResult>,
+) -> thread::Result<TestResult>
+where
+  T: Fixture,
+{
+  __tear_down_with(fixture, T::tear_down, test, outcome, result)
+}
+
+/// Tears down the `fixture` used by the test named `test` with the specified
+/// `tear_down` function, given the `outcome` and current `result` of running
+/// the test.
+///
+/// This behaves the same as [`__tear_down`], but supports fixtures that are
+/// torn down by other means than [`Fixture::tear_down`].
+///
+/// # Arguments
+///
+/// * `fixture` - the fixture to tear down
+/// * `tear_down` - the function that tears down the fixture
+/// * `test` - the name of the test that used the fixture
+/// * `outcome` - the outcome of the test
+/// * `result` - the result of the test, as returned by [`panic::catch_unwind`]
+#[doc(hidden)]
+pub fn __tear_down_with<T, F>(
+  fixture: T,
+  tear_down: F,
+  test: &'static str,
+  outcome: &TestOutcome,
+  result: thread::Result<TestResult>,
+) -> thread::Result<TestResult>
+where
+  F: FnOnce(T, &TestOutcome) -> Result<()>,
+{
+  finish_tear_down::<T>(tear_down(fixture, outcome), test, result)
+}
+
+/// Asynchronously tears down the `fixture` used by the test named `test`,
+/// given the `outcome` and current `result` of running the test.
+///
+/// This behaves the same as [`__tear_down`], but for fixtures of `async fn`
+/// tests.
+///
+/// # Arguments
+///
+/// * `fixture` - the fixture to tear down
+/// * `test` - the name of the test that used the fixture
+/// * `outcome` - the outcome of the test
+/// * `result` - the result of the test, as returned by [`__catch_unwind`]
+///
+/// [`__catch_unwind`]: crate::__internal::__catch_unwind
+#[doc(hidden)]
+pub async fn __tear_down_async<T>(
+  fixture: T,
+  test: &'static str,
+  outcome: &TestOutcome,
+  result: thread::Result<TestResult>,
+) -> thread::Result<TestResult>
+where
+  T: AsyncFixture,
+{
+  finish_tear_down::<T>(fixture.tear_down(outcome).await, test, result)
+}
+
+/// Combines the result of tearing down a fixture of type `T` with the current
+/// `result` of the test.
+///
+/// # Arguments
+///
+/// * `teardown` - the result of tearing down the fixture
+/// * `test` - the name of the test that used the fixture
+/// * `result` - the result of the test
+fn finish_tear_down<T>(
+  teardown: Result<()>,
+  test: &'static str,
+  result: thread::Result<TestResult>,
+) -> thread::Result<TestResult> {
+  let teardown = match teardown {
+    Ok(()) => return result,
+    Err(e) => TeardownError::new(type_name::<T>(), test, e),
+  };
+  match result {
+    Ok(Ok(())) => Ok(Err(teardown.into())),
+    result => {
+      eprintln!("{teardown}");
+      result
+    }
+  }
+}
+
+/// Concludes a test by returning its `result`, or by resuming the panic of the
+/// test if it panicked.
+///
+/// # Arguments
+///
+/// * `result` - the result of the test, as returned by [`panic::catch_unwind`]
+#[doc(hidden)]
+pub fn __conclude(result: thread::Result<TestResult>) -> TestResult {
+  match result {
+    Ok(result) => result,
+    Err(payload) => panic::resume_unwind(payload),
+  }
+}
+
+/// Concludes a test-case that is expected to panic, which is run by a test
+/// that must return `()`.
+///
+/// A test-case that fails without panicking has its error written to stderr,
+/// so that the test still fails for not having panicked -- along with the
+/// reason that it failed.
+///
+/// # Arguments
+///
+/// * `result` - the result of the test-case
+#[doc(hidden)]
+pub fn __expect_panic(result: TestResult) {
+  if let Err(e) = result {
+    eprintln!("Error: {e:?}");
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use std::cell::Cell;
+
+  /// Runs `test` and tears down a fixture that records the outcome it was torn
+  /// down with, and that fails to be torn down if `fails` is set.
+  fn run_with_tear_down<F>(
+    test: F,
+    fails: bool,
+  ) -> (Option<TestOutcome>, thread::Result<TestResult>)
+  where
+    F: FnOnce() -> TestResult + panic::UnwindSafe,
+  {
+    let torn_down: Cell<Option<TestOutcome>> = Cell::new(None);
+    let result = panic::catch_unwind(test);
+    let outcome = __outcome(&result, false);
+    let result = __tear_down_with(
+      (),
+      |(), outcome: &TestOutcome| {
+        torn_down.set(Some(*outcome));
+        match fails {
+          true => Err("leaked temp file".into()),
+          false => Ok(()),
+        }
+      },
+      "test_something",
+      &outcome,
+      result,
+    );
+    (torn_down.get(), result)
+  }
+
+  #[test]
+  fn test_tear_down_after_pass() {
+    let (outcome, result) = run_with_tear_down(|| Ok(()), false);
+
+    assert_eq!(outcome, Some(TestOutcome::Passed));
+    assert!(matches!(result, Ok(Ok(()))));
+  }
+
+  #[test]
+  fn test_tear_down_after_error() {
+    let (outcome, result) = run_with_tear_down(|| Err("failed".into()), false);
+
+    assert_eq!(outcome, Some(TestOutcome::Failed));
+    let error = result.unwrap().unwrap_err();
+    assert_eq!(error.to_string(), "failed");
+  }
+
+  #[test]
+  fn test_tear_down_after_panic() {
+    let (outcome, result) = run_with_tear_down(|| panic!("panicked"), false);
+
+    assert_eq!(outcome, Some(TestOutcome::Panicked));
+    let payload = result.unwrap_err();
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"panicked"));
+  }
+
+  #[test]
+  fn test_outcome_of_expected_panic() {
+    let panicked = panic::catch_unwind(|| -> TestResult { panic!("panicked") });
+    let passed = panic::catch_unwind(|| -> TestResult { Ok(()) });
+
+    assert_eq!(__outcome(&panicked, true), TestOutcome::Passed);
+    assert_eq!(__outcome(&passed, true), TestOutcome::Failed);
+  }
+
+  #[test]
+  fn test_tear_down_error_fails_passing_test() {
+    let (outcome, result) = run_with_tear_down(|| Ok(()), true);
+
+    assert_eq!(outcome, Some(TestOutcome::Passed));
+    let error = result.unwrap().unwrap_err();
+    let teardown = error.downcast_ref::<TeardownError>().unwrap();
+    assert_eq!(teardown.fixture(), "()");
+    assert_eq!(teardown.test(), "test_something");
+    assert_eq!(
+      error.to_string(),
+      "failed to tear down fixture `()` for test `test_something`: leaked temp file"
+    );
+  }
+
+  #[test]
+  fn test_tear_down_error_keeps_test_error() {
+    let (outcome, result) = run_with_tear_down(|| Err("failed".into()), true);
+
+    assert_eq!(outcome, Some(TestOutcome::Failed));
+    let error = result.unwrap().unwrap_err();
+    assert_eq!(error.to_string(), "failed");
+  }
+
+  #[test]
+  fn test_tear_down_error_keeps_panic() {
+    let (outcome, result) = run_with_tear_down(|| panic!("panicked"), true);
+
+    assert_eq!(outcome, Some(TestOutcome::Panicked));
+    assert!(result.is_err());
+  }
+}
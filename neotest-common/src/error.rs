@@ -0,0 +1,428 @@
+use std::time::Duration;
+
+use crate::Error;
+
+/// An error returned from a test when its [`Fixture`] could not be prepared.
+///
+/// This distinguishes failures in setting up the environment of a test from
+/// failures of the test itself, and names both the fixture and the test that
+/// was abandoned.
+///
+/// # Examples
+///
+/// Basic use:
+///
+/// ```rust
+/// # use neotest_common::SetupError;
+/// let error = SetupError::new("TestFixture", "test_something", "no database".into());
+///
+/// assert_eq!(
+///     error.to_string(),
+///     "failed to set up fixture `TestFixture` for test `test_something`: no database",
+/// );
+/// ```
+///
+/// [`Fixture`]: crate::Fixture
+pub struct SetupError {
+  fixture: &'static str,
+  test: &'static str,
+  source: Error,
+}
+
+impl SetupError {
+  /// Constructs a new [`SetupError`]
+  ///
+  /// # Arguments
+  ///
+  /// * `fixture` - the name of the fixture type that failed to be prepared
+  /// * `test` - the name of the test that required the fixture
+  /// * `source` - the error returned from preparing the fixture
+  pub fn new(fixture: &'static str, test: &'static str, source: Error) -> Self {
+    Self {
+      fixture,
+      test,
+      source,
+    }
+  }
+
+  /// Returns the name of the fixture type that failed to be prepared
+  pub fn fixture(&self) -> &'static str {
+    self.fixture
+  }
+
+  /// Returns the name of the test that required the fixture
+  pub fn test(&self) -> &'static str {
+    self.test
+  }
+}
+
+impl std::fmt::Display for SetupError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(
+      f,
+      "failed to set up fixture `{}` for test `{}`: {}",
+      self.fixture, self.test, self.source
+    )
+  }
+}
+
+/// Test failures are reported through [`Debug`], so this is formatted the same
+/// as [`Display`] to keep test output readable.
+///
+/// [`Display`]: std::fmt::Display
+impl std::fmt::Debug for SetupError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    std::fmt::Display::fmt(self, f)
+  }
+}
+
+impl std::error::Error for SetupError {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    Some(self.source.as_ref())
+  }
+}
+
+/// An error returned from a test when its [`Fixture`] could not be torn down.
+///
+/// This is only returned when the test itself succeeded; failures of the test
+/// take precedence over failures to tear down its fixture.
+///
+/// # Examples
+///
+/// Basic use:
+///
+/// ```rust
+/// # use neotest_common::TeardownError;
+/// let error = TeardownError::new("TestFixture", "test_something", "leaked temp file".into());
+///
+/// assert_eq!(
+///     error.to_string(),
+///     "failed to tear down fixture `TestFixture` for test `test_something`: leaked temp file",
+/// );
+/// ```
+///
+/// [`Fixture`]: crate::Fixture
+pub struct TeardownError {
+  fixture: &'static str,
+  test: &'static str,
+  source: Error,
+}
+
+impl TeardownError {
+  /// Constructs a new [`TeardownError`]
+  ///
+  /// # Arguments
+  ///
+  /// * `fixture` - the name of the fixture type that failed to be torn down
+  /// * `test` - the name of the test that used the fixture
+  /// * `source` - the error returned from tearing down the fixture
+  pub fn new(fixture: &'static str, test: &'static str, source: Error) -> Self {
+    Self {
+      fixture,
+      test,
+      source,
+    }
+  }
+
+  /// Returns the name of the fixture type that failed to be torn down
+  pub fn fixture(&self) -> &'static str {
+    self.fixture
+  }
+
+  /// Returns the name of the test that used the fixture
+  pub fn test(&self) -> &'static str {
+    self.test
+  }
+}
+
+impl std::fmt::Display for TeardownError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(
+      f,
+      "failed to tear down fixture `{}` for test `{}`: {}",
+      self.fixture, self.test, self.source
+    )
+  }
+}
+
+/// Formatted the same as [`Display`], for the same reasons as [`SetupError`].
+///
+/// [`Display`]: std::fmt::Display
+impl std::fmt::Debug for TeardownError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    std::fmt::Display::fmt(self, f)
+  }
+}
+
+impl std::error::Error for TeardownError {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    Some(self.source.as_ref())
+  }
+}
+
+/// An error returned from a test when it did not complete within its timeout.
+///
+/// The test names the full path of the test-case that was abandoned, including
+/// its parameter input and subtest, e.g. `test_something::input_1::subtest`.
+///
+/// # Examples
+///
+/// Basic use:
+///
+/// ```rust
+/// # use neotest_common::TimeoutError;
+/// # use std::time::Duration;
+/// let error = TimeoutError::new("test_something::input_1", Duration::from_millis(250));
+///
+/// assert_eq!(
+///     error.to_string(),
+///     "test `test_something::input_1` timed out after 250ms",
+/// );
+/// ```
+pub struct TimeoutError {
+  test: &'static str,
+  timeout: Duration,
+}
+
+impl TimeoutError {
+  /// Constructs a new [`TimeoutError`]
+  ///
+  /// # Arguments
+  ///
+  /// * `test` - the path of the test-case that timed out
+  /// * `timeout` - the timeout that the test exceeded
+  pub fn new(test: &'static str, timeout: Duration) -> Self {
+    Self { test, timeout }
+  }
+
+  /// Returns the path of the test-case that timed out
+  pub fn test(&self) -> &'static str {
+    self.test
+  }
+
+  /// Returns the timeout that the test exceeded
+  pub fn timeout(&self) -> Duration {
+    self.timeout
+  }
+}
+
+impl std::fmt::Display for TimeoutError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "test `{}` timed out after {:?}", self.test, self.timeout)
+  }
+}
+
+/// Formatted the same as [`Display`], for the same reasons as [`SetupError`].
+///
+/// [`Display`]: std::fmt::Display
+impl std::fmt::Debug for TimeoutError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    std::fmt::Display::fmt(self, f)
+  }
+}
+
+impl std::error::Error for TimeoutError {}
+
+/// An error returned from a test whose parameters are generated at runtime,
+/// when any of its generated cases failed.
+///
+/// Every failing case is named by its index and the `Debug` value of its
+/// generated parameters, along with the reason it failed.
+///
+/// # Examples
+///
+/// Basic use:
+///
+/// ```rust
+/// # use neotest_common::GeneratedCasesError;
+/// let mut error = GeneratedCasesError::new(3);
+/// error.push(1, "a = 4".into(), "assertion failed: a < 4".into());
+///
+/// assert_eq!(
+///     error.to_string(),
+///     "1 of 3 generated cases failed:\n  case 1 (a = 4): assertion failed: a < 4",
+/// );
+/// ```
+pub struct GeneratedCasesError {
+  total: usize,
+  failures: Vec<(usize, String, String)>,
+}
+
+impl GeneratedCasesError {
+  /// Constructs a new [`GeneratedCasesError`] without any failing cases
+  ///
+  /// # Arguments
+  ///
+  /// * `total` - the number of cases that were run
+  pub fn new(total: usize) -> Self {
+    Self {
+      total,
+      failures: Vec::new(),
+    }
+  }
+
+  /// Adds a failing case to this error
+  ///
+  /// # Arguments
+  ///
+  /// * `index` - the index of the case that failed
+  /// * `case` - the description of the generated parameters of the case
+  /// * `reason` - the reason that the case failed
+  pub fn push(&mut self, index: usize, case: String, reason: String) {
+    self.failures.push((index, case, reason));
+  }
+
+  /// Returns the number of cases that were run
+  pub fn total(&self) -> usize {
+    self.total
+  }
+
+  /// Returns the index of each case that failed
+  pub fn failed_cases(&self) -> impl Iterator<Item = usize> + '_ {
+    self.failures.iter().map(|(index, _, _)| *index)
+  }
+}
+
+impl std::fmt::Display for GeneratedCasesError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(
+      f,
+      "{} of {} generated cases failed:",
+      self.failures.len(),
+      self.total
+    )?;
+    for (index, case, reason) in self.failures.iter() {
+      write!(f, "\n  case {index} ({case}): {reason}")?;
+    }
+    Ok(())
+  }
+}
+
+/// Formatted the same as [`Display`], for the same reasons as [`SetupError`].
+///
+/// [`Display`]: std::fmt::Display
+impl std::fmt::Debug for GeneratedCasesError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    std::fmt::Display::fmt(self, f)
+  }
+}
+
+impl std::error::Error for GeneratedCasesError {}
+
+/// An error returned from a property test when any of its cases failed.
+///
+/// The failing case is shrunk to the simplest values that still fail, which
+/// are named by the `Debug` value of each parameter, along with the reason
+/// that they failed. The seed that generated the cases is also reported, which
+/// replays them when given by the `NEOTEST_SEED` environment variable.
+///
+/// # Examples
+///
+/// Basic use:
+///
+/// ```rust
+/// # use neotest_common::PropertyError;
+/// let error = PropertyError::new(0x2a, 13, 4, "a = 0".into(), "assertion failed: a > 0".into());
+///
+/// assert_eq!(
+///     error.to_string(),
+///     "property failed on case 13, and was shrunk 4 times to (a = 0): assertion failed: a > 0\n  \
+///      replay with NEOTEST_SEED=0x2a",
+/// );
+/// ```
+pub struct PropertyError {
+  seed: u64,
+  case: u32,
+  shrinks: usize,
+  values: String,
+  reason: String,
+}
+
+impl PropertyError {
+  /// Constructs a new [`PropertyError`]
+  ///
+  /// # Arguments
+  ///
+  /// * `seed` - the seed that generated the cases
+  /// * `case` - the index of the first case that failed
+  /// * `shrinks` - the number of times that the failing values were shrunk
+  /// * `values` - the description of the simplest failing values
+  /// * `reason` - the reason that the simplest values failed
+  pub fn new(seed: u64, case: u32, shrinks: usize, values: String, reason: String) -> Self {
+    Self {
+      seed,
+      case,
+      shrinks,
+      values,
+      reason,
+    }
+  }
+
+  /// Returns the seed that generated the cases
+  pub fn seed(&self) -> u64 {
+    self.seed
+  }
+
+  /// Returns the index of the first case that failed
+  pub fn case(&self) -> u32 {
+    self.case
+  }
+
+  /// Returns the description of the simplest failing values
+  pub fn values(&self) -> &str {
+    &self.values
+  }
+}
+
+impl std::fmt::Display for PropertyError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(
+      f,
+      "property failed on case {}, and was shrunk {} times to ({}): {}\n  replay with NEOTEST_SEED={:#x}",
+      self.case, self.shrinks, self.values, self.reason, self.seed
+    )
+  }
+}
+
+/// Formatted the same as [`Display`], for the same reasons as [`SetupError`].
+///
+/// [`Display`]: std::fmt::Display
+impl std::fmt::Debug for PropertyError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    std::fmt::Display::fmt(self, f)
+  }
+}
+
+impl std::error::Error for PropertyError {}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_setup_error_display() {
+    let error = SetupError::new("Database", "test_query", "connection refused".into());
+
+    assert_eq!(error.fixture(), "Database");
+    assert_eq!(error.test(), "test_query");
+    assert_eq!(
+      error.to_string(),
+      "failed to set up fixture `Database` for test `test_query`: connection refused"
+    );
+  }
+
+  #[test]
+  fn test_setup_error_debug_is_display() {
+    let error = SetupError::new("Database", "test_query", "connection refused".into());
+
+    assert_eq!(format!("{error:?}"), error.to_string());
+  }
+
+  #[test]
+  fn test_setup_error_source() {
+    let error = SetupError::new("Database", "test_query", "connection refused".into());
+
+    let source = std::error::Error::source(&error).unwrap();
+    assert_eq!(source.to_string(), "connection refused");
+  }
+}
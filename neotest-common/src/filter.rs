@@ -0,0 +1,37 @@
+use std::cell::Cell;
+
+use crate::TestResult;
+
+thread_local! {
+  /// Whether the generated case being run on this thread was filtered, or
+  /// [`None`] if no generated case is being run.
+  static FILTERED: Cell<Option<bool>> = const { Cell::new(None) };
+}
+
+/// Concludes a test-case that is excluded by the `exclude` input of its test,
+/// without running it.
+///
+/// The test-case is reported as filtered by the runner of the generated cases
+/// of its test. On its own, the test-case is reported as passing -- since the
+/// test harness cannot skip a test that has started -- and so notes that it
+/// was filtered in its output instead.
+#[doc(hidden)]
+pub fn __filter_case() -> TestResult {
+  FILTERED.with(|v| match v.get() {
+    Some(_) => v.set(Some(true)),
+    None => println!("filtered by `exclude`"),
+  });
+  Ok(())
+}
+
+/// Starts recording whether the next test-case run on this thread is
+/// filtered, rather than reporting it.
+pub(crate) fn record_filtered() {
+  FILTERED.with(|v| v.set(Some(false)));
+}
+
+/// Stops recording filtered test-cases, and returns whether the test-case run
+/// since [`record_filtered`] was filtered.
+pub(crate) fn take_filtered() -> bool {
+  FILTERED.with(|v| v.take()) == Some(true)
+}
@@ -1,4 +1,6 @@
-use crate::Result;
+use std::future::{self, Future};
+
+use crate::{Result, TestOutcome};
 
 /// A trait for defining fixture functionality.
 ///
@@ -28,7 +30,48 @@ use crate::Result;
 /// }
 /// ```
 ///
+/// With teardown logic:
+///
+/// ```rust
+/// # use neotest_common::{Fixture, Result, TestOutcome};
+/// struct LoggingFixture {
+///     log: Vec<String>,
+/// }
+///
+/// impl Fixture for LoggingFixture {
+///     fn prepare() -> Result<Self> {
+///         Ok(Self{ log: Vec::new() })
+///     }
+///
+///     fn tear_down(self, outcome: &TestOutcome) -> Result<()> {
+///         // Only flush the logs when something went wrong
+///         if outcome.is_failure() {
+///             for line in self.log.iter() {
+///                 eprintln!("{line}");
+///             }
+///         }
+///         Ok(())
+///     }
+/// }
+/// ```
+///
+/// # Injection
+///
+/// Any argument of a `#[neotest]` test that is not bound to a `parameter`
+/// input is prepared as a fixture, in any position and by its own type. Tests
+/// may therefore take any number of fixtures, each either by reference or by
+/// value.
+///
+/// Fixtures named with `fixture(scope = suite)` are instead prepared once, and
+/// shared by all tests in the test binary. These must also be [`Send`] and
+/// [`Sync`], and are never torn down.
+///
 /// [`derive(Fixture)`]: crate::Fixture
+#[diagnostic::on_unimplemented(
+  message = "`{Self}` is not a test fixture",
+  label = "test parameter not bound to an input, and so injected as a fixture",
+  note = "bind this with `parameter = <name> as [...]`, or implement `Fixture` for `{Self}`"
+)]
 pub trait Fixture
 where
   Self: Sized,
@@ -36,12 +79,141 @@ where
   /// Prepares this fixture so that it can be used for testing.
   ///
   /// Preparation is able to fail, as indicated by returning a [`Result`].
-  /// In such a case, the test will be abandoned and treated as an error.
+  /// In such a case, the test will be abandoned and treated as an error --
+  /// which is reported as a [`SetupError`] naming both the fixture and test.
+  ///
+  /// [`SetupError`]: crate::SetupError
+  fn prepare() -> Result<Self>;
+
+  /// Tears down this fixture after the test has been executed.
+  ///
+  /// This is called with the `outcome` of the test, regardless of whether the
+  /// test passed, returned an error, or panicked. By default, this does
+  /// nothing.
+  ///
+  /// Teardown is able to fail, as indicated by returning a [`Result`]. In such
+  /// a case, the test will be treated as an error -- which is reported as a
+  /// [`TeardownError`] separately from any failure of the test itself.
   ///
   /// # Notes
   ///
-  /// Unlike traditional unit-testing frameworks of old, there is no
-  /// equivalent `tear_down()` function; rather, the implemented Fixture should
-  /// also implement `Drop` if teardown logic is desired.
-  fn prepare() -> Result<Self>;
+  /// Teardown is only possible when the test borrows the fixture (e.g. takes
+  /// `&Fixture` or `&mut Fixture`). Tests that take ownership of the fixture
+  /// are free to consume it, and so `Drop` should be implemented instead if
+  /// teardown logic is desired for these.
+  ///
+  /// [`TeardownError`]: crate::TeardownError
+  fn tear_down(self, _outcome: &TestOutcome) -> Result<()> {
+    Ok(())
+  }
+}
+
+/// A trait for defining fixtures that are prepared from the parameters of a
+/// test.
+///
+/// Each multiplexed test-case prepares its own fixture from the parameter
+/// values of that case, which are selected by naming them after the fixture
+/// in the `fixture` argument of the test, e.g. `fixture = Database(backend)`.
+/// A single named parameter is provided as `P` directly, while multiple named
+/// parameters are provided as a tuple in the order they are named. Parameter
+/// values are cloned before they are provided, since the test receives them as
+/// well.
+///
+/// # Examples
+///
+/// Basic use:
+///
+/// ```rust
+/// # use neotest_common::{ParameterizedFixture, Result};
+/// #[derive(Clone, Copy)]
+/// enum Backend {
+///     Memory,
+///     Disk,
+/// }
+///
+/// struct Database {
+///     backend: Backend,
+/// }
+///
+/// impl ParameterizedFixture<Backend> for Database {
+///     fn prepare_with(backend: Backend) -> Result<Self> {
+///         Ok(Self { backend })
+///     }
+/// }
+/// ```
+pub trait ParameterizedFixture<P>
+where
+  Self: Sized,
+{
+  /// Prepares this fixture from the parameters of the test, so that it can be
+  /// used for testing.
+  ///
+  /// Preparation failures are handled the same as for [`Fixture::prepare`].
+  ///
+  /// # Arguments
+  ///
+  /// * `params` - the parameters of the test-case
+  fn prepare_with(params: P) -> Result<Self>;
+
+  /// Tears down this fixture after the test has been executed.
+  ///
+  /// This is handled the same as for [`Fixture::tear_down`], and by default
+  /// does nothing.
+  fn tear_down(self, _outcome: &TestOutcome) -> Result<()> {
+    Ok(())
+  }
+}
+
+/// A trait for defining fixtures that are prepared and torn down
+/// asynchronously, for use by `async fn` tests.
+///
+/// Every [`Fixture`] is also an [`AsyncFixture`] that completes immediately,
+/// so `async fn` tests may use either kind of fixture. Implementations may be
+/// written with `async fn`, and are run on the executor of the test.
+///
+/// # Examples
+///
+/// Basic use:
+///
+/// ```rust
+/// # use neotest_common::{AsyncFixture, Result};
+/// struct Connection {
+///     /* ... */
+/// }
+///
+/// impl AsyncFixture for Connection {
+///     async fn prepare() -> Result<Self> {
+///         Ok(Self { /* ... */ })
+///     }
+/// }
+/// ```
+pub trait AsyncFixture
+where
+  Self: Sized,
+{
+  /// Prepares this fixture so that it can be used for testing.
+  ///
+  /// Preparation failures are handled the same as for [`Fixture::prepare`].
+  fn prepare() -> impl Future<Output = Result<Self>>;
+
+  /// Tears down this fixture after the test has been executed.
+  ///
+  /// This is handled the same as for [`Fixture::tear_down`], and by default
+  /// does nothing.
+  fn tear_down(self, _outcome: &TestOutcome) -> impl Future<Output = Result<()>> {
+    future::ready(Ok(()))
+  }
+}
+
+impl<T> AsyncFixture for T
+where
+  T: Fixture,
+{
+  fn prepare() -> impl Future<Output = Result<Self>> {
+    future::ready(<T as Fixture>::prepare())
+  }
+
+  fn tear_down(self, outcome: &TestOutcome) -> impl Future<Output = Result<()>> {
+    future::ready(<T as Fixture>::tear_down(self, outcome))
+  }
 }
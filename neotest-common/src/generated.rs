@@ -0,0 +1,91 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::filter::{record_filtered, take_filtered};
+use crate::{GeneratedCasesError, TestResult};
+
+/// The undocumented [`__GeneratedCases`] object is an internal neotest utility
+/// that runs each case of a test whose parameters are generated at runtime.
+///
+/// Every case is run, even after an earlier case fails, and the outcome of
+/// each case is written to stdout -- including cases that are filtered by the
+/// `exclude` input of the test. All failing cases are then reported together
+/// by [`Self::finish`].
+#[doc(hidden)]
+pub struct __GeneratedCases {
+  count: usize,
+  failures: Vec<(usize, String, String)>,
+}
+
+#[allow(clippy::new_without_default)]
+impl __GeneratedCases {
+  #[doc(hidden)]
+  pub fn new() -> Self {
+    Self {
+      count: 0,
+      failures: Vec::new(),
+    }
+  }
+
+  /// Runs the next case, recording it as a failure if it either returns an
+  /// error or panics.
+  ///
+  /// # Arguments
+  ///
+  /// * `case` - the description of the generated parameters of the case
+  /// * `f` - the function that runs the case
+  #[doc(hidden)]
+  pub fn run<F>(&mut self, case: String, f: F)
+  where
+    F: FnOnce() -> TestResult,
+  {
+    let index = self.count;
+    self.count += 1;
+
+    record_filtered();
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    let filtered = take_filtered();
+    let reason = match result {
+      Ok(Ok(())) if filtered => {
+        println!("case {index} ({case}) ... filtered");
+        return;
+      }
+      Ok(Ok(())) => {
+        println!("case {index} ({case}) ... ok");
+        return;
+      }
+      Ok(Err(error)) => error.to_string(),
+      Err(payload) => panic_message(payload.as_ref()),
+    };
+    println!("case {index} ({case}) ... FAILED");
+    self.failures.push((index, case, reason));
+  }
+
+  /// Concludes the test, failing it if any of its cases failed.
+  #[doc(hidden)]
+  pub fn finish(self) -> TestResult {
+    if self.failures.is_empty() {
+      return Ok(());
+    }
+    let mut error = GeneratedCasesError::new(self.count);
+    for (index, case, reason) in self.failures {
+      error.push(index, case, reason);
+    }
+    Err(Box::new(error))
+  }
+}
+
+/// Returns the message of a panic from its `payload`.
+///
+/// # Arguments
+///
+/// * `payload` - the payload of the panic
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+  if let Some(message) = payload.downcast_ref::<&str>() {
+    format!("panicked: {message}")
+  } else if let Some(message) = payload.downcast_ref::<String>() {
+    format!("panicked: {message}")
+  } else {
+    "panicked".to_string()
+  }
+}
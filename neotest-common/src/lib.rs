@@ -4,18 +4,45 @@
 //!
 //! * The [`Result`] and [`TestResult`] types for returning errors from tests,
 //! * The [`Error`] type for generic representation of any failures,
-//! * The [`Fixture`] trait, required for fixture-based logic, and
+//! * The [`SetupError`] and [`TeardownError`] types for reporting fixtures that
+//!   failed to prepare or tear down, the [`TimeoutError`] type for reporting
+//!   tests that did not complete in time, the [`GeneratedCasesError`] type
+//!   for reporting the failing cases of runtime-generated parameters, and the
+//!   [`PropertyError`] type for reporting the failing cases of property tests,
+//! * The [`Fixture`], [`ParameterizedFixture`], and [`AsyncFixture`] traits,
+//!   required for fixture-based logic,
+//! * The [`prop`] module, which provides the strategies of property tests,
+//! * The [`TestOutcome`] type, which tells fixtures how a test concluded, and
 //! * Various pieces of internal utilities needed to make this framework operate.
 //!
 //! This crate should never be depended on directly. The base `neotest` crate
 //! should be used instead.
+mod cases;
 mod context;
+mod dispatch;
+mod error;
+mod filter;
 mod fixture;
+mod generated;
+mod module;
+mod outcome;
+mod parameter_set;
+pub mod prop;
+mod property;
 mod result;
+mod runtime;
+mod shared;
+mod timeout;
+
+#[doc(inline)]
+pub use error::*;
 
 #[doc(inline)]
 pub use fixture::*;
 
+#[doc(inline)]
+pub use outcome::*;
+
 #[doc(inline)]
 pub use result::*;
 
@@ -27,6 +54,26 @@ pub use result::*;
 /// implementation-freedom).
 #[doc(hidden)]
 pub mod __internal {
+  #[doc(hidden, inline)]
+  pub use super::cases::*;
   #[doc(hidden, inline)]
   pub use super::context::*;
+  #[doc(hidden, inline)]
+  pub use super::dispatch::*;
+  #[doc(hidden, inline)]
+  pub use super::filter::*;
+  #[doc(hidden, inline)]
+  pub use super::generated::*;
+  #[doc(hidden, inline)]
+  pub use super::module::*;
+  #[doc(hidden, inline)]
+  pub use super::parameter_set::*;
+  #[doc(hidden, inline)]
+  pub use super::property::*;
+  #[doc(hidden, inline)]
+  pub use super::runtime::*;
+  #[doc(hidden, inline)]
+  pub use super::shared::*;
+  #[doc(hidden, inline)]
+  pub use super::timeout::*;
 }
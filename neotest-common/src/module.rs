@@ -0,0 +1,392 @@
+use std::any::type_name;
+use std::ops::Deref;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+
+use crate::{Fixture, Result, TeardownError, TestOutcome, TestResult};
+
+/// A fixture that is shared by all tests within a `#[neotest_suite]` module.
+///
+/// The fixture is prepared when it is first used, and is torn down once it has
+/// been used the expected number of times -- which is the number of times the
+/// tests of the module invoke it when they are all run. Uses by `#[ignore]`d
+/// tests are only expected when the test harness is given `--ignored` or
+/// `--include-ignored`. A failure to prepare the fixture is cached, and
+/// reported to every test that requires it.
+#[doc(hidden)]
+pub struct __ModuleFixture<T> {
+  uses: usize,
+  ignored_uses: usize,
+  state: Mutex<ModuleState<T>>,
+}
+
+struct ModuleState<T> {
+  remaining: Option<usize>,
+  fixture: Option<std::result::Result<Arc<T>, String>>,
+  outcome: TestOutcome,
+}
+
+impl<T> ModuleState<T> {
+  fn remaining(&mut self) -> &mut usize {
+    self.remaining.as_mut().unwrap()
+  }
+}
+
+impl<T> __ModuleFixture<T>
+where
+  T: Fixture,
+{
+  /// Constructs a new [`__ModuleFixture`] that is expected to be used `uses`
+  /// times by tests that are run by default, and `ignored_uses` times by tests
+  /// that are `#[ignore]`d, before it is torn down.
+  ///
+  /// # Arguments
+  ///
+  /// * `uses` - the number of times the fixture is used by tests run by default
+  /// * `ignored_uses` - the number of times the fixture is used by ignored tests
+  pub const fn new(uses: usize, ignored_uses: usize) -> Self {
+    Self {
+      uses,
+      ignored_uses,
+      state: Mutex::new(ModuleState {
+        remaining: None,
+        fixture: None,
+        outcome: TestOutcome::Passed,
+      }),
+    }
+  }
+
+  fn lock(&self) -> MutexGuard<'_, ModuleState<T>> {
+    // A poisoned state is still consistent, since panics are caught below
+    let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+    if state.remaining.is_none() {
+      state.remaining = Some(expected_uses(
+        self.uses,
+        self.ignored_uses,
+        std::env::args(),
+      ));
+    }
+    state
+  }
+
+  /// Releases a use of the fixture, tearing it down if this was the last use.
+  ///
+  /// # Arguments
+  ///
+  /// * `fixture` - the fixture that was used
+  /// * `outcome` - the outcome of the test that used the fixture
+  fn release(&self, fixture: Arc<T>, outcome: TestOutcome) -> Result<()> {
+    drop(fixture);
+    self.release_use(outcome)
+  }
+
+  /// Releases a use of the fixture that may not have used it at all, tearing
+  /// it down if this was the last use.
+  ///
+  /// # Arguments
+  ///
+  /// * `outcome` - the outcome of the test that used the fixture
+  fn release_use(&self, outcome: TestOutcome) -> Result<()> {
+    let mut state = self.lock();
+    if outcome.is_failure() {
+      state.outcome = outcome;
+    }
+    let remaining = state.remaining();
+    *remaining = remaining.saturating_sub(1);
+    if *remaining != 0 {
+      return Ok(());
+    }
+    let outcome = std::mem::replace(&mut state.outcome, TestOutcome::Passed);
+    match state.fixture.take() {
+      Some(Ok(fixture)) => match Arc::try_unwrap(fixture) {
+        Ok(fixture) => fixture.tear_down(&outcome),
+        Err(_) => Err("fixture is still in use after its last expected use".into()),
+      },
+      _ => Ok(()),
+    }
+  }
+}
+
+/// Returns the number of times that a module fixture is expected to be used
+/// by the tests that the test harness was given `args` to run.
+///
+/// # Arguments
+///
+/// * `uses` - the number of times the fixture is used by tests run by default
+/// * `ignored_uses` - the number of times the fixture is used by ignored tests
+/// * `args` - the command-line arguments of the test harness
+fn expected_uses<I>(uses: usize, ignored_uses: usize, args: I) -> usize
+where
+  I: IntoIterator,
+  I::Item: AsRef<str>,
+{
+  let mut result = uses;
+  for arg in args {
+    match arg.as_ref() {
+      "--include-ignored" => return uses + ignored_uses,
+      "--ignored" => result = ignored_uses,
+      _ => {}
+    }
+  }
+  result
+}
+
+/// A use of a [`__ModuleFixture`] by a single test.
+///
+/// If the guard is dropped without being released through [`__release`] --
+/// such as when another fixture of the test failed to be prepared -- the use
+/// is released as a failure.
+#[doc(hidden)]
+pub struct __ModuleFixtureGuard<T>
+where
+  T: Fixture + 'static,
+{
+  owner: &'static __ModuleFixture<T>,
+  fixture: Option<Arc<T>>,
+}
+
+impl<T> Deref for __ModuleFixtureGuard<T>
+where
+  T: Fixture + 'static,
+{
+  type Target = T;
+
+  fn deref(&self) -> &T {
+    self.fixture.as_ref().unwrap()
+  }
+}
+
+impl<T> Drop for __ModuleFixtureGuard<T>
+where
+  T: Fixture + 'static,
+{
+  fn drop(&mut self) {
+    if let Some(fixture) = self.fixture.take() {
+      if let Err(e) = self.owner.release(fixture, TestOutcome::Failed) {
+        eprintln!("failed to tear down fixture `{}`: {e}", type_name::<T>());
+      }
+    }
+  }
+}
+
+/// Returns a use of the module fixture `owner`, preparing it on first use.
+///
+/// # Arguments
+///
+/// * `owner` - the module fixture to use
+#[doc(hidden)]
+pub fn __module_fixture<T>(owner: &'static __ModuleFixture<T>) -> Result<__ModuleFixtureGuard<T>>
+where
+  T: Fixture + 'static,
+{
+  let mut state = owner.lock();
+  // A use beyond those expected -- such as by a test that was run again --
+  // prepares the fixture anew, which must then also be torn down
+  if state.fixture.is_none() && *state.remaining() == 0 {
+    *state.remaining() = 1;
+  }
+  let fixture =
+    state
+      .fixture
+      .get_or_insert_with(|| match panic::catch_unwind(AssertUnwindSafe(T::prepare)) {
+        Ok(result) => result.map(Arc::new).map_err(|e| e.to_string()),
+        Err(_) => Err("fixture panicked while being prepared".to_string()),
+      });
+  match fixture {
+    Ok(fixture) => Ok(__ModuleFixtureGuard {
+      owner,
+      fixture: Some(fixture.clone()),
+    }),
+    Err(e) => Err(e.clone().into()),
+  }
+}
+
+/// Releases the use of a module fixture by the test named `test`, given the
+/// `outcome` and current `result` of running the test.
+///
+/// Teardown errors are handled the same as for [`__tear_down`].
+///
+/// # Arguments
+///
+/// * `guard` - the use of the module fixture
+/// * `test` - the name of the test that used the fixture
+/// * `outcome` - the outcome of the test
+/// * `result` - the result of the test, as returned by [`panic::catch_unwind`]
+///
+/// [`__tear_down`]: crate::__internal::__tear_down
+#[doc(hidden)]
+pub fn __release<T>(
+  mut guard: __ModuleFixtureGuard<T>,
+  test: &'static str,
+  outcome: &TestOutcome,
+  result: thread::Result<TestResult>,
+) -> thread::Result<TestResult>
+where
+  T: Fixture + 'static,
+{
+  let fixture = match guard.fixture.take() {
+    Some(fixture) => fixture,
+    None => return result,
+  };
+  let teardown = match guard.owner.release(fixture, *outcome) {
+    Ok(()) => return result,
+    Err(e) => TeardownError::new(type_name::<T>(), test, e),
+  };
+  match result {
+    Ok(Ok(())) => Ok(Err(teardown.into())),
+    result => {
+      eprintln!("{teardown}");
+      result
+    }
+  }
+}
+
+/// Reserves an additional use of a module fixture, for a test-case that uses
+/// the fixture more than once -- such as once for every case of a generated
+/// parameter.
+///
+/// # Arguments
+///
+/// * `owner` - the module fixture that will be used
+#[doc(hidden)]
+pub fn __retain_module_fixture<T>(owner: &'static __ModuleFixture<T>)
+where
+  T: Fixture + 'static,
+{
+  *owner.lock().remaining() += 1;
+}
+
+/// Releases the use of a module fixture by a test-case of the test named
+/// `test` that did not use it -- such as a test-case that was filtered, or
+/// one whose uses were each reserved by [`__retain_module_fixture`].
+///
+/// The fixture is torn down if this was its last use, where teardown errors
+/// are reported as errors of the test-case.
+///
+/// # Arguments
+///
+/// * `owner` - the module fixture that would have been used
+/// * `test` - the name of the test that would have used the fixture
+#[doc(hidden)]
+pub fn __skip_module_fixture<T>(
+  owner: &'static __ModuleFixture<T>,
+  test: &'static str,
+) -> TestResult
+where
+  T: Fixture + 'static,
+{
+  owner
+    .release_use(TestOutcome::Passed)
+    .map_err(|e| TeardownError::new(type_name::<T>(), test, e).into())
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use std::sync::atomic::{AtomicUsize, Ordering};
+
+  /// Defines a fixture type named `$name` that counts its preparations in
+  /// `$prepared`, and its teardowns by a passing test in `$passed`.
+  macro_rules! counted_fixture {
+    ($name:ident, $prepared:ident, $passed:ident) => {
+      static $prepared: AtomicUsize = AtomicUsize::new(0);
+      static $passed: AtomicUsize = AtomicUsize::new(0);
+
+      struct $name;
+
+      impl Fixture for $name {
+        fn prepare() -> Result<Self> {
+          $prepared.fetch_add(1, Ordering::SeqCst);
+          Ok($name)
+        }
+
+        fn tear_down(self, outcome: &TestOutcome) -> Result<()> {
+          if *outcome == TestOutcome::Passed {
+            $passed.fetch_add(1, Ordering::SeqCst);
+          }
+          Ok(())
+        }
+      }
+    };
+  }
+
+  /// Uses the fixture `owner` in a test that produces `outcome`.
+  fn use_fixture<T>(owner: &'static __ModuleFixture<T>, outcome: TestOutcome) -> TestResult
+  where
+    T: Fixture + 'static,
+  {
+    let guard = __module_fixture(owner)?;
+    match __release(guard, "test_something", &outcome, Ok(Ok(()))) {
+      Ok(result) => result,
+      Err(_) => unreachable!(),
+    }
+  }
+
+  #[test]
+  fn test_expected_uses() {
+    assert_eq!(expected_uses(3, 2, ["test"]), 3);
+    assert_eq!(expected_uses(3, 2, ["test", "--ignored"]), 2);
+    assert_eq!(expected_uses(3, 2, ["test", "--include-ignored"]), 5);
+    assert_eq!(expected_uses(3, 2, ["--include-ignored", "--ignored"]), 5);
+  }
+
+  #[test]
+  fn test_tear_down_after_last_use() {
+    counted_fixture!(Database, PREPARED, PASSED);
+    static FIXTURE: __ModuleFixture<Database> = __ModuleFixture::new(2, 0);
+
+    use_fixture(&FIXTURE, TestOutcome::Passed).unwrap();
+    assert_eq!(PASSED.load(Ordering::SeqCst), 0);
+    use_fixture(&FIXTURE, TestOutcome::Passed).unwrap();
+
+    assert_eq!(PREPARED.load(Ordering::SeqCst), 1);
+    assert_eq!(PASSED.load(Ordering::SeqCst), 1);
+  }
+
+  #[test]
+  fn test_tear_down_after_unexpected_use() {
+    counted_fixture!(Database, PREPARED, PASSED);
+    static FIXTURE: __ModuleFixture<Database> = __ModuleFixture::new(1, 0);
+
+    use_fixture(&FIXTURE, TestOutcome::Passed).unwrap();
+    use_fixture(&FIXTURE, TestOutcome::Passed).unwrap();
+
+    // Every preparation of the fixture is torn down
+    assert_eq!(PREPARED.load(Ordering::SeqCst), 2);
+    assert_eq!(PASSED.load(Ordering::SeqCst), 2);
+  }
+
+  #[test]
+  fn test_tear_down_after_expected_panic() {
+    counted_fixture!(Database, PREPARED, PASSED);
+    static FIXTURE: __ModuleFixture<Database> = __ModuleFixture::new(1, 0);
+
+    let result = panic::catch_unwind(|| -> TestResult { panic!("panicked") });
+    let outcome = crate::__internal::__outcome(&result, true);
+    use_fixture(&FIXTURE, outcome).unwrap();
+
+    assert_eq!(PREPARED.load(Ordering::SeqCst), 1);
+    assert_eq!(PASSED.load(Ordering::SeqCst), 1);
+  }
+
+  #[test]
+  fn test_tear_down_while_in_use() {
+    counted_fixture!(Database, PREPARED, PASSED);
+    static FIXTURE: __ModuleFixture<Database> = __ModuleFixture::new(1, 0);
+
+    let guard = __module_fixture(&FIXTURE).unwrap();
+    let leaked = guard.fixture.clone();
+    let result = __release(guard, "test_something", &TestOutcome::Passed, Ok(Ok(())));
+
+    let error = result.unwrap().unwrap_err();
+    let teardown = error.downcast_ref::<TeardownError>().unwrap();
+    assert_eq!(teardown.test(), "test_something");
+    assert!(error
+      .to_string()
+      .ends_with("fixture is still in use after its last expected use"));
+    assert_eq!(PREPARED.load(Ordering::SeqCst), 1);
+    assert_eq!(PASSED.load(Ordering::SeqCst), 0);
+    drop(leaked);
+  }
+}
@@ -0,0 +1,27 @@
+/// The outcome of running a test, which is provided to [`Fixture::tear_down`]
+/// so that teardown logic can react to failures.
+///
+/// [`Fixture::tear_down`]: crate::Fixture::tear_down
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestOutcome {
+  /// The test completed successfully
+  Passed,
+
+  /// The test returned an error
+  Failed,
+
+  /// The test panicked, such as from a failed assertion
+  Panicked,
+}
+
+impl TestOutcome {
+  /// Returns `true` if the test completed successfully
+  pub fn is_success(&self) -> bool {
+    *self == TestOutcome::Passed
+  }
+
+  /// Returns `true` if the test either returned an error or panicked
+  pub fn is_failure(&self) -> bool {
+    !self.is_success()
+  }
+}
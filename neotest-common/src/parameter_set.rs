@@ -0,0 +1,28 @@
+/// The marker that a `parameter_set!` defines alongside its macro, so that a
+/// lone path that does not name a set can be reported as such.
+#[doc(hidden)]
+pub struct __ParameterSet;
+
+/// A trait that is only implemented by [`__ParameterSet`], which explains how
+/// to use a strategy named by a lone path when it is not implemented.
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+  message = "`{Self}` is not a parameter set",
+  label = "a lone path after `as` names a parameter set",
+  note = "a strategy held by a constant, static, or function must be written in parentheses, e.g. `parameter = value as (STRATEGY)`"
+)]
+pub trait __IsParameterSet {}
+
+impl __IsParameterSet for __ParameterSet {}
+
+/// Checks that the lone path of a `parameter` input names a parameter set.
+///
+/// # Arguments
+///
+/// * `_set` - the item named by the path
+#[doc(hidden)]
+pub fn __parameter_set<T>(_set: &T)
+where
+  T: __IsParameterSet,
+{
+}
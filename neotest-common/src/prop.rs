@@ -0,0 +1,60 @@
+//! This module provides property-based testing, where a test is run against
+//! many values that are generated by a [`Strategy`].
+//!
+//! Strategies are given as the inputs of the parameters of a test, in place
+//! of a list of inputs:
+//!
+//! ```ignore
+//! use neotest::prop::{any, vec};
+//!
+//! #[neotest(
+//!   parameter = value as any::<u32>(),
+//!   parameter = bytes as vec(any::<u8>(), 0..64),
+//!   cases = 256,
+//! )]
+//! fn test_property(value: u32, bytes: Vec<u8>) { /* ... */ }
+//! ```
+//!
+//! Each test-case is run `cases` times -- or [`DEFAULT_CASES`] times, if not
+//! specified -- against values generated from a seeded [`TestRng`]. When a
+//! case fails, its values are shrunk to the simplest values that still fail,
+//! which are reported along with the seed of the generator. Setting the
+//! `NEOTEST_SEED` environment variable to this seed replays the same values.
+//!
+//! Strategies are formed from:
+//!
+//! * [`any`], which generates any value of an [`Arbitrary`] type,
+//! * [`vec`](fn@vec), which generates vectors of values from another strategy,
+//! * [`just`], which always generates the same value,
+//! * ranges of integers, such as `0..10u32` or `1..=6u8`, and
+//! * tuples of other strategies.
+//!
+//! A strategy that is named by a lone path, such as a constant, must be
+//! written in parentheses -- e.g. `parameter = value as (SMALL)` -- since a
+//! lone path names a parameter set instead.
+//!
+//! Structs and enums implement [`Arbitrary`] with `#[derive(Arbitrary)]`,
+//! which generates and shrinks each of their fields.
+mod arbitrary;
+mod collection;
+mod integer;
+mod rng;
+mod strategy;
+
+#[doc(inline)]
+pub use arbitrary::*;
+#[doc(inline)]
+pub use collection::*;
+#[doc(inline)]
+pub use rng::*;
+#[doc(inline)]
+pub use strategy::*;
+
+/// The number of cases that a property test is run with, unless its `cases`
+/// input specifies otherwise.
+pub const DEFAULT_CASES: u32 = 256;
+
+/// The number of nested values of recursive enums that derive [`Arbitrary`],
+/// after which only their variants that are not recursive are generated --
+/// unless their `#[arbitrary(max_depth = ...)]` attribute specifies otherwise.
+pub const DEFAULT_MAX_DEPTH: usize = 4;
@@ -0,0 +1,299 @@
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use super::collection::shrink_elements;
+use super::integer::{self, Integer};
+use super::{Strategy, TestRng};
+
+/// The maximum number of elements of a collection generated by [`any`].
+const MAX_LEN: usize = 32;
+
+/// A type whose values can be generated for a property test, without naming a
+/// [`Strategy`] for them.
+///
+/// This is implemented for the primitive types, for [`String`], and for
+/// vectors, options, boxes, and tuples of other arbitrary types. Values of an
+/// arbitrary type are generated by the strategy [`any`].
+///
+/// # Examples
+///
+/// Basic use:
+///
+/// ```rust
+/// # use neotest_common::prop::{Arbitrary, TestRng};
+/// #[derive(Clone, Debug)]
+/// struct Even(u32);
+///
+/// impl Arbitrary for Even {
+///   fn arbitrary(rng: &mut TestRng) -> Self {
+///     Even(u32::arbitrary(rng) & !1)
+///   }
+///
+///   fn shrink(&self) -> Vec<Self> {
+///     self.0.shrink().into_iter().map(|v| Even(v & !1)).collect()
+///   }
+/// }
+/// ```
+pub trait Arbitrary: Clone + Debug + Sized {
+  /// Generates a new value from the random number generator
+  ///
+  /// # Arguments
+  ///
+  /// * `rng` - the generator to generate the value from
+  fn arbitrary(rng: &mut TestRng) -> Self;
+
+  /// Returns the values that are simpler than this one, in the order that
+  /// they should be tried -- starting with the simplest.
+  fn shrink(&self) -> Vec<Self> {
+    Vec::new()
+  }
+}
+
+/// A [`Strategy`] that generates any value of an [`Arbitrary`] type.
+pub struct Any<T>(PhantomData<fn() -> T>);
+
+impl<T> Clone for Any<T> {
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<T> Copy for Any<T> {}
+
+impl<T> Debug for Any<T> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "any::<{}>()", std::any::type_name::<T>())
+  }
+}
+
+impl<T> Strategy for Any<T>
+where
+  T: Arbitrary,
+{
+  type Value = T;
+
+  fn generate(&self, rng: &mut TestRng) -> T {
+    T::arbitrary(rng)
+  }
+
+  fn shrink(&self, value: &T) -> Vec<T> {
+    value.shrink()
+  }
+}
+
+/// Creates a [`Strategy`] that generates any value of the [`Arbitrary`] type
+/// `T`.
+///
+/// # Examples
+///
+/// Basic use:
+///
+/// ```rust
+/// # use neotest_common::prop::{any, Strategy, TestRng};
+/// let mut rng = TestRng::from_seed(42);
+///
+/// let value: (u32, bool) = any::<(u32, bool)>().generate(&mut rng);
+/// ```
+pub fn any<T>() -> Any<T>
+where
+  T: Arbitrary,
+{
+  Any(PhantomData)
+}
+
+macro_rules! impl_integer_arbitrary {
+  ($($ty:ty),+) => {
+    $(
+      impl Arbitrary for $ty {
+        fn arbitrary(rng: &mut TestRng) -> Self {
+          integer::generate_biased(rng, <$ty>::MIN, <$ty>::MAX)
+        }
+
+        fn shrink(&self) -> Vec<Self> {
+          integer::shrink_towards(*self, <$ty as Integer>::ZERO)
+        }
+      }
+    )+
+  };
+}
+
+impl_integer_arbitrary!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+macro_rules! impl_float_arbitrary {
+  ($($ty:ident),+) => {
+    $(
+      impl Arbitrary for $ty {
+        fn arbitrary(rng: &mut TestRng) -> Self {
+          if rng.one_in(8) {
+            let edges = [0.0, 1.0, -1.0, $ty::MIN_POSITIVE, $ty::EPSILON, $ty::MAX, $ty::MIN];
+            return edges[rng.below(edges.len() as u128) as usize];
+          }
+          let exponent = rng.below(64) as i32 - 32;
+          let magnitude = rng.next_f64() * 2f64.powi(exponent);
+          let value = if rng.one_in(2) { -magnitude } else { magnitude };
+          value as $ty
+        }
+
+        fn shrink(&self) -> Vec<Self> {
+          let value = *self;
+          if value == 0.0 || !value.is_finite() {
+            return Vec::new();
+          }
+          let mut result = vec![0.0];
+          if value < 0.0 {
+            result.push(-value);
+          }
+          if value.trunc() != value {
+            result.push(value.trunc());
+          }
+          // Halving stops once the value is too small to be simpler
+          if value.abs() >= 1.0 {
+            result.push(value / 2.0);
+          }
+          result
+        }
+      }
+    )+
+  };
+}
+
+impl_float_arbitrary!(f32, f64);
+
+impl Arbitrary for bool {
+  fn arbitrary(rng: &mut TestRng) -> Self {
+    rng.one_in(2)
+  }
+
+  fn shrink(&self) -> Vec<Self> {
+    match self {
+      true => vec![false],
+      false => Vec::new(),
+    }
+  }
+}
+
+impl Arbitrary for char {
+  fn arbitrary(rng: &mut TestRng) -> Self {
+    if rng.one_in(4) {
+      loop {
+        if let Some(c) = char::from_u32(rng.below(u128::from(u32::from(char::MAX)) + 1) as u32) {
+          return c;
+        }
+      }
+    }
+    // Printable ASCII characters are generated most of the time
+    char::from(b' ' + rng.below(95) as u8)
+  }
+
+  fn shrink(&self) -> Vec<Self> {
+    match self {
+      'a' => Vec::new(),
+      _ => vec!['a'],
+    }
+  }
+}
+
+impl Arbitrary for () {
+  fn arbitrary(_: &mut TestRng) -> Self {}
+}
+
+impl Arbitrary for String {
+  fn arbitrary(rng: &mut TestRng) -> Self {
+    let chars: Vec<char> = Arbitrary::arbitrary(rng);
+    chars.into_iter().collect()
+  }
+
+  fn shrink(&self) -> Vec<Self> {
+    let chars: Vec<char> = self.chars().collect();
+    shrink_elements(&chars, 0, Arbitrary::shrink)
+      .into_iter()
+      .map(|v| v.into_iter().collect())
+      .collect()
+  }
+}
+
+impl<T> Arbitrary for Vec<T>
+where
+  T: Arbitrary,
+{
+  fn arbitrary(rng: &mut TestRng) -> Self {
+    let len = rng.below(MAX_LEN as u128 + 1) as usize;
+    (0..len).map(|_| T::arbitrary(rng)).collect()
+  }
+
+  fn shrink(&self) -> Vec<Self> {
+    shrink_elements(self, 0, Arbitrary::shrink)
+  }
+}
+
+impl<T> Arbitrary for Option<T>
+where
+  T: Arbitrary,
+{
+  fn arbitrary(rng: &mut TestRng) -> Self {
+    match rng.one_in(4) {
+      true => None,
+      false => Some(T::arbitrary(rng)),
+    }
+  }
+
+  fn shrink(&self) -> Vec<Self> {
+    match self {
+      Some(value) => std::iter::once(None)
+        .chain(value.shrink().into_iter().map(Some))
+        .collect(),
+      None => Vec::new(),
+    }
+  }
+}
+
+impl<T> Arbitrary for Box<T>
+where
+  T: Arbitrary,
+{
+  fn arbitrary(rng: &mut TestRng) -> Self {
+    Box::new(T::arbitrary(rng))
+  }
+
+  fn shrink(&self) -> Vec<Self> {
+    (**self).shrink().into_iter().map(Box::new).collect()
+  }
+}
+
+macro_rules! impl_tuple_arbitrary {
+  ($(($($name:ident: $index:tt),+)),+) => {
+    $(
+      impl<$($name),+> Arbitrary for ($($name,)+)
+      where
+        $($name: Arbitrary),+
+      {
+        fn arbitrary(rng: &mut TestRng) -> Self {
+          ($($name::arbitrary(rng),)+)
+        }
+
+        fn shrink(&self) -> Vec<Self> {
+          let mut result: Vec<Self> = Vec::new();
+          $(
+            for element in self.$index.shrink() {
+              let mut value = self.clone();
+              value.$index = element;
+              result.push(value);
+            }
+          )+
+          result
+        }
+      }
+    )+
+  };
+}
+
+impl_tuple_arbitrary! {
+  (A: 0),
+  (A: 0, B: 1),
+  (A: 0, B: 1, C: 2),
+  (A: 0, B: 1, C: 2, D: 3),
+  (A: 0, B: 1, C: 2, D: 3, E: 4),
+  (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5),
+  (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6),
+  (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7)
+}
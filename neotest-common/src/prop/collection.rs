@@ -0,0 +1,165 @@
+use std::ops::{Range, RangeInclusive};
+
+use super::{Strategy, TestRng};
+
+/// The range of the number of elements of a generated collection.
+///
+/// This is formed from either an exact size, such as `8`, or from a range of
+/// sizes, such as `0..64` or `1..=4`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SizeRange {
+  min: usize,
+  max: usize,
+}
+
+impl SizeRange {
+  /// Constructs a new [`SizeRange`] of sizes within `[min, max]`
+  ///
+  /// # Arguments
+  ///
+  /// * `min` - the inclusive minimum size
+  /// * `max` - the inclusive maximum size
+  pub fn new(min: usize, max: usize) -> Self {
+    assert!(
+      min <= max,
+      "cannot generate sizes from the empty range {min}..={max}"
+    );
+    Self { min, max }
+  }
+
+  /// Returns the inclusive minimum size
+  pub fn min(&self) -> usize {
+    self.min
+  }
+
+  /// Returns the inclusive maximum size
+  pub fn max(&self) -> usize {
+    self.max
+  }
+
+  /// Generates a size within this range
+  ///
+  /// # Arguments
+  ///
+  /// * `rng` - the generator to generate the size from
+  pub fn generate(&self, rng: &mut TestRng) -> usize {
+    self.min + rng.below((self.max - self.min) as u128 + 1) as usize
+  }
+}
+
+impl From<usize> for SizeRange {
+  fn from(size: usize) -> Self {
+    Self::new(size, size)
+  }
+}
+
+impl From<Range<usize>> for SizeRange {
+  fn from(range: Range<usize>) -> Self {
+    assert!(
+      range.start < range.end,
+      "cannot generate sizes from the empty range {range:?}"
+    );
+    Self::new(range.start, range.end - 1)
+  }
+}
+
+impl From<RangeInclusive<usize>> for SizeRange {
+  fn from(range: RangeInclusive<usize>) -> Self {
+    Self::new(*range.start(), *range.end())
+  }
+}
+
+/// A [`Strategy`] that generates vectors of values from another strategy.
+///
+/// Vectors are shrunk by first removing their elements, and then by shrinking
+/// each element that remains.
+#[derive(Clone, Debug)]
+pub struct VecStrategy<S> {
+  element: S,
+  size: SizeRange,
+}
+
+impl<S> Strategy for VecStrategy<S>
+where
+  S: Strategy,
+{
+  type Value = Vec<S::Value>;
+
+  fn generate(&self, rng: &mut TestRng) -> Self::Value {
+    let len = self.size.generate(rng);
+    (0..len).map(|_| self.element.generate(rng)).collect()
+  }
+
+  fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+    shrink_elements(value, self.size.min, |v| self.element.shrink(v))
+  }
+}
+
+/// Creates a [`Strategy`] that generates vectors whose number of elements is
+/// within `size`, and whose elements are generated by `element`.
+///
+/// # Arguments
+///
+/// * `element` - the strategy of each element
+/// * `size` - the number of elements, such as `8` or `0..64`
+///
+/// # Examples
+///
+/// Basic use:
+///
+/// ```rust
+/// # use neotest_common::prop::{any, vec, Strategy, TestRng};
+/// let strategy = vec(any::<u8>(), 0..64);
+/// let mut rng = TestRng::from_seed(42);
+///
+/// assert!(strategy.generate(&mut rng).len() < 64);
+/// ```
+pub fn vec<S>(element: S, size: impl Into<SizeRange>) -> VecStrategy<S>
+where
+  S: Strategy,
+{
+  VecStrategy {
+    element,
+    size: size.into(),
+  }
+}
+
+/// Returns the sequences that are simpler than `values`, which have either
+/// fewer elements, or a simpler element.
+///
+/// Elements are removed in chunks that halve in size -- down to the removal of
+/// each single element -- without removing more than `min` allows.
+///
+/// # Arguments
+///
+/// * `values` - the sequence to shrink
+/// * `min` - the minimum number of elements of the sequence
+/// * `shrink` - the function that shrinks a single element
+pub(crate) fn shrink_elements<T, F>(values: &[T], min: usize, shrink: F) -> Vec<Vec<T>>
+where
+  T: Clone,
+  F: Fn(&T) -> Vec<T>,
+{
+  let mut result: Vec<Vec<T>> = Vec::new();
+  let len = values.len();
+  if len > min {
+    result.push(values[..min].to_vec());
+    let mut chunk = (len - min).div_ceil(2);
+    while chunk > 0 {
+      for start in (0..=len - chunk).step_by(chunk) {
+        let mut shrunk = values[..start].to_vec();
+        shrunk.extend_from_slice(&values[start + chunk..]);
+        result.push(shrunk);
+      }
+      chunk /= 2;
+    }
+  }
+  for (i, value) in values.iter().enumerate() {
+    for element in shrink(value) {
+      let mut shrunk = values.to_vec();
+      shrunk[i] = element;
+      result.push(shrunk);
+    }
+  }
+  result
+}
@@ -0,0 +1,127 @@
+use std::fmt::Debug;
+
+use super::TestRng;
+
+/// The primitive integer types, which are generated and shrunk through their
+/// key -- a `u128` that has the same order as the integer itself.
+///
+/// This allows every integer type to share a single implementation, rather
+/// than one for each of their signed and unsigned representations.
+pub(crate) trait Integer: Copy + Debug + 'static {
+  const ZERO: Self;
+  const ONE: Self;
+
+  /// Returns the key of this integer
+  fn to_key(self) -> u128;
+
+  /// Returns the integer of the specified `key`
+  ///
+  /// # Arguments
+  ///
+  /// * `key` - the key of the integer
+  fn from_key(key: u128) -> Self;
+}
+
+macro_rules! impl_integer {
+  (unsigned: $($ty:ty),+; signed: $($signed:ty),+) => {
+    $(
+      impl Integer for $ty {
+        const ZERO: Self = 0;
+        const ONE: Self = 1;
+
+        fn to_key(self) -> u128 {
+          self as u128
+        }
+
+        fn from_key(key: u128) -> Self {
+          key as $ty
+        }
+      }
+    )+
+    $(
+      impl Integer for $signed {
+        const ZERO: Self = 0;
+        const ONE: Self = 1;
+
+        // Flipping the sign bit orders negative values before positive ones
+        fn to_key(self) -> u128 {
+          (self as i128 as u128) ^ (1 << 127)
+        }
+
+        fn from_key(key: u128) -> Self {
+          (key ^ (1 << 127)) as i128 as $signed
+        }
+      }
+    )+
+  };
+}
+
+impl_integer! {
+  unsigned: u8, u16, u32, u64, u128, usize;
+  signed: i8, i16, i32, i64, i128, isize
+}
+
+/// Generates a uniformly distributed integer within `[min, max]`
+///
+/// # Arguments
+///
+/// * `rng` - the generator to generate from
+/// * `min` - the inclusive lower bound
+/// * `max` - the inclusive upper bound
+pub(crate) fn generate_between<T: Integer>(rng: &mut TestRng, min: T, max: T) -> T {
+  let (min, max) = (min.to_key(), max.to_key());
+  let key = match (max - min).checked_add(1) {
+    Some(span) => min + rng.below(span),
+    None => rng.next_u128(),
+  };
+  T::from_key(key)
+}
+
+/// Generates an integer within `[min, max]` that is biased towards the values
+/// that most often uncover bugs -- such as either bound, zero, and values of
+/// small magnitudes.
+///
+/// # Arguments
+///
+/// * `rng` - the generator to generate from
+/// * `min` - the inclusive lower bound
+/// * `max` - the inclusive upper bound
+pub(crate) fn generate_biased<T: Integer>(rng: &mut TestRng, min: T, max: T) -> T {
+  let (min_key, max_key) = (min.to_key(), max.to_key());
+  let zero = T::ZERO.to_key().clamp(min_key, max_key);
+  match rng.below(8) {
+    0 => {
+      let edges = [min, max, T::ZERO, T::ONE];
+      let edge = edges[rng.below(edges.len() as u128) as usize];
+      T::from_key(edge.to_key().clamp(min_key, max_key))
+    }
+    1..=3 => {
+      let low = T::from_key(zero.saturating_sub(256).max(min_key));
+      let high = T::from_key(zero.saturating_add(256).min(max_key));
+      generate_between(rng, low, high)
+    }
+    _ => generate_between(rng, min, max),
+  }
+}
+
+/// Returns the simpler integers to try in place of `value`, which approach
+/// `target` -- from `target` itself to the integer adjacent to `value`.
+///
+/// # Arguments
+///
+/// * `value` - the integer to shrink
+/// * `target` - the simplest integer
+pub(crate) fn shrink_towards<T: Integer>(value: T, target: T) -> Vec<T> {
+  let (value, target) = (value.to_key(), target.to_key());
+  let mut result: Vec<T> = Vec::new();
+  let mut delta = value.abs_diff(target);
+  while delta != 0 {
+    let key = match value > target {
+      true => value - delta,
+      false => value + delta,
+    };
+    result.push(T::from_key(key));
+    delta /= 2;
+  }
+  result
+}
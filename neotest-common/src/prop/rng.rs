@@ -0,0 +1,182 @@
+/// The seeded pseudo-random number generator that property tests generate
+/// their values from.
+///
+/// This is an implementation of `xoshiro256**`, whose state is expanded from a
+/// single 64-bit seed with `SplitMix64`. The same seed always produces the same
+/// sequence of values, on every platform -- which is what allows a failing
+/// property test to be replayed from its seed.
+///
+/// # Examples
+///
+/// Basic use:
+///
+/// ```rust
+/// # use neotest_common::prop::TestRng;
+/// let mut a = TestRng::from_seed(42);
+/// let mut b = TestRng::from_seed(42);
+///
+/// assert_eq!(a.next_u64(), b.next_u64());
+/// assert!(a.below(10) < 10);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TestRng {
+  state: [u64; 4],
+  depth: usize,
+}
+
+impl TestRng {
+  /// Constructs a new [`TestRng`] from the specified `seed`
+  ///
+  /// # Arguments
+  ///
+  /// * `seed` - the seed of the generator
+  pub fn from_seed(seed: u64) -> Self {
+    let mut seed = seed;
+    let mut split_mix = || {
+      seed = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+      let mut z = seed;
+      z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+      z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+      z ^ (z >> 31)
+    };
+    Self {
+      state: [split_mix(), split_mix(), split_mix(), split_mix()],
+      depth: 0,
+    }
+  }
+
+  /// Returns the next 64 random bits
+  pub fn next_u64(&mut self) -> u64 {
+    let [s0, s1, s2, s3] = &mut self.state;
+    let result = s1.wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+    let t = *s1 << 17;
+    *s2 ^= *s0;
+    *s3 ^= *s1;
+    *s1 ^= *s2;
+    *s0 ^= *s3;
+    *s2 ^= t;
+    *s3 = s3.rotate_left(45);
+    result
+  }
+
+  /// Returns the next 128 random bits
+  pub fn next_u128(&mut self) -> u128 {
+    (u128::from(self.next_u64()) << 64) | u128::from(self.next_u64())
+  }
+
+  /// Returns a uniformly distributed value in the range `[0, 1)`
+  pub fn next_f64(&mut self) -> f64 {
+    (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+  }
+
+  /// Returns a uniformly distributed value in the range `[0, bound)`, or `0`
+  /// if `bound` is `0`.
+  ///
+  /// # Arguments
+  ///
+  /// * `bound` - the exclusive upper bound of the value
+  pub fn below(&mut self, bound: u128) -> u128 {
+    if bound <= 1 {
+      return 0;
+    }
+    // Values are drawn from the smallest power of two that holds the bound,
+    // and rejected until they are within it -- so that each is equally likely
+    let mask = u128::MAX >> (bound - 1).leading_zeros();
+    loop {
+      let value = match mask <= u128::from(u64::MAX) {
+        true => u128::from(self.next_u64()),
+        false => self.next_u128(),
+      } & mask;
+      if value < bound {
+        return value;
+      }
+    }
+  }
+
+  /// Returns `true` with a probability of one in `n`
+  ///
+  /// # Arguments
+  ///
+  /// * `n` - the inverse of the probability
+  pub fn one_in(&mut self, n: u32) -> bool {
+    self.below(u128::from(n)) == 0
+  }
+
+  /// Returns the index of a weight chosen at random, where each index is
+  /// chosen in proportion to its weight.
+  ///
+  /// # Arguments
+  ///
+  /// * `weights` - the weight of each index
+  ///
+  /// # Panics
+  ///
+  /// This panics if every weight is zero.
+  ///
+  /// # Examples
+  ///
+  /// Basic use:
+  ///
+  /// ```rust
+  /// # use neotest_common::prop::TestRng;
+  /// let mut rng = TestRng::from_seed(42);
+  ///
+  /// assert_ne!(rng.weighted_index(&[1, 0, 3]), 1);
+  /// ```
+  pub fn weighted_index(&mut self, weights: &[u32]) -> usize {
+    let total: u128 = weights.iter().map(|v| u128::from(*v)).sum();
+    assert!(
+      total > 0,
+      "cannot choose an index when every weight is zero"
+    );
+    let mut choice = self.below(total);
+    for (i, weight) in weights.iter().enumerate() {
+      match choice.checked_sub(u128::from(*weight)) {
+        Some(rest) => choice = rest,
+        None => return i,
+      }
+    }
+    unreachable!()
+  }
+
+  /// Returns the number of nested [`recurse`] calls that are generating values
+  /// with this generator.
+  ///
+  /// Recursive types use this to stop generating values that contain
+  /// themselves, once they are nested too deeply.
+  ///
+  /// [`recurse`]: Self::recurse
+  pub fn depth(&self) -> usize {
+    self.depth
+  }
+
+  /// Runs `f` with the [`depth`] of this generator increased by one, for the
+  /// generation of a value that may be nested within values of its own type.
+  ///
+  /// # Arguments
+  ///
+  /// * `f` - the function that generates the nested value
+  ///
+  /// # Examples
+  ///
+  /// Basic use:
+  ///
+  /// ```rust
+  /// # use neotest_common::prop::TestRng;
+  /// let mut rng = TestRng::from_seed(42);
+  ///
+  /// assert_eq!(rng.recurse(|rng| rng.recurse(|rng| rng.depth())), 2);
+  /// assert_eq!(rng.depth(), 0);
+  /// ```
+  ///
+  /// [`depth`]: Self::depth
+  pub fn recurse<T, F>(&mut self, f: F) -> T
+  where
+    F: FnOnce(&mut Self) -> T,
+  {
+    self.depth += 1;
+    let result = f(self);
+    self.depth -= 1;
+    result
+  }
+}
@@ -0,0 +1,193 @@
+use std::fmt::Debug;
+use std::ops::{Range, RangeInclusive};
+
+use super::integer::{self, Integer};
+use super::TestRng;
+
+/// A source of values for a property test, which both generates values and
+/// shrinks a failing value into simpler ones.
+///
+/// Strategies are given as the inputs of parameters, and the test is run
+/// against every value that they generate:
+///
+/// ```ignore
+/// #[neotest(parameter = value as any::<u32>())]
+/// fn test_property(value: u32) { /* ... */ }
+/// ```
+///
+/// When the test fails, the failing value is replaced by the first value of
+/// [`Self::shrink`] that still fails -- repeatedly, until no simpler value
+/// fails. Strategies that return no simpler values are never shrunk.
+///
+/// # Examples
+///
+/// Basic use:
+///
+/// ```rust
+/// # use neotest_common::prop::{Strategy, TestRng};
+/// let strategy = 10u32..20;
+/// let mut rng = TestRng::from_seed(42);
+///
+/// let value = strategy.generate(&mut rng);
+/// assert!((10..20).contains(&value));
+/// assert_eq!(strategy.shrink(&15), vec![10, 13, 14]);
+/// ```
+pub trait Strategy {
+  /// The type of the generated values
+  type Value: Clone + Debug;
+
+  /// Generates a new value from the random number generator
+  ///
+  /// # Arguments
+  ///
+  /// * `rng` - the generator to generate the value from
+  fn generate(&self, rng: &mut TestRng) -> Self::Value;
+
+  /// Returns the values that are simpler than `value`, in the order that they
+  /// should be tried -- starting with the simplest.
+  ///
+  /// # Arguments
+  ///
+  /// * `value` - the value to shrink
+  fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+    let _ = value;
+    Vec::new()
+  }
+}
+
+impl<S> Strategy for &S
+where
+  S: Strategy + ?Sized,
+{
+  type Value = S::Value;
+
+  fn generate(&self, rng: &mut TestRng) -> Self::Value {
+    (**self).generate(rng)
+  }
+
+  fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+    (**self).shrink(value)
+  }
+}
+
+impl<S> Strategy for Box<S>
+where
+  S: Strategy + ?Sized,
+{
+  type Value = S::Value;
+
+  fn generate(&self, rng: &mut TestRng) -> Self::Value {
+    (**self).generate(rng)
+  }
+
+  fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+    (**self).shrink(value)
+  }
+}
+
+/// A [`Strategy`] that always generates the same value.
+#[derive(Clone, Copy, Debug)]
+pub struct Just<T>(pub T);
+
+impl<T> Strategy for Just<T>
+where
+  T: Clone + Debug,
+{
+  type Value = T;
+
+  fn generate(&self, _: &mut TestRng) -> T {
+    self.0.clone()
+  }
+}
+
+/// Creates a [`Strategy`] that always generates `value`
+///
+/// # Arguments
+///
+/// * `value` - the value to generate
+pub fn just<T>(value: T) -> Just<T>
+where
+  T: Clone + Debug,
+{
+  Just(value)
+}
+
+/// Ranges of integers generate values uniformly within the range, and shrink
+/// towards the value in the range that is closest to zero.
+macro_rules! impl_range_strategy {
+  ($($ty:ty),+) => {
+    $(
+      impl Strategy for Range<$ty> {
+        type Value = $ty;
+
+        fn generate(&self, rng: &mut TestRng) -> $ty {
+          assert!(self.start < self.end, "cannot generate from the empty range {self:?}");
+          integer::generate_between(rng, self.start, self.end - 1)
+        }
+
+        fn shrink(&self, value: &$ty) -> Vec<$ty> {
+          let target = <$ty as Integer>::ZERO.clamp(self.start, self.end - 1);
+          integer::shrink_towards(*value, target)
+        }
+      }
+
+      impl Strategy for RangeInclusive<$ty> {
+        type Value = $ty;
+
+        fn generate(&self, rng: &mut TestRng) -> $ty {
+          assert!(!self.is_empty(), "cannot generate from the empty range {self:?}");
+          integer::generate_between(rng, *self.start(), *self.end())
+        }
+
+        fn shrink(&self, value: &$ty) -> Vec<$ty> {
+          let target = <$ty as Integer>::ZERO.clamp(*self.start(), *self.end());
+          integer::shrink_towards(*value, target)
+        }
+      }
+    )+
+  };
+}
+
+impl_range_strategy!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Tuples of strategies generate a tuple of their values, and shrink one
+/// element at a time.
+macro_rules! impl_tuple_strategy {
+  ($(($($name:ident: $index:tt),+)),+) => {
+    $(
+      impl<$($name),+> Strategy for ($($name,)+)
+      where
+        $($name: Strategy),+
+      {
+        type Value = ($($name::Value,)+);
+
+        fn generate(&self, rng: &mut TestRng) -> Self::Value {
+          ($(self.$index.generate(rng),)+)
+        }
+
+        fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+          let mut result: Vec<Self::Value> = Vec::new();
+          $(
+            for element in self.$index.shrink(&value.$index) {
+              let mut value = value.clone();
+              value.$index = element;
+              result.push(value);
+            }
+          )+
+          result
+        }
+      }
+    )+
+  };
+}
+
+impl_tuple_strategy! {
+  (A: 0),
+  (A: 0, B: 1),
+  (A: 0, B: 1, C: 2),
+  (A: 0, B: 1, C: 2, D: 3),
+  (A: 0, B: 1, C: 2, D: 3, E: 4),
+  (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5),
+  (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6),
+  (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7)
+}
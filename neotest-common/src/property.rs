@@ -0,0 +1,191 @@
+use std::cell::Cell;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+use std::time::SystemTime;
+
+use crate::filter::{record_filtered, take_filtered};
+use crate::generated::panic_message;
+use crate::prop::{Strategy, TestRng};
+use crate::{PropertyError, Result, TestResult};
+
+/// The maximum number of simpler values that are tried while shrinking the
+/// values of a failing case.
+const MAX_SHRINK_ATTEMPTS: usize = 4096;
+
+thread_local! {
+  /// Whether panics on this thread are silenced, which they are while the
+  /// values of a failing case are shrunk.
+  static SILENCED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// The undocumented [`__Property`] object is an internal neotest utility that
+/// runs a test-case against the values generated by the strategies of its
+/// parameters.
+///
+/// The first case that fails is shrunk to the simplest values that still fail,
+/// which are reported along with the seed of the generator. The seed is read
+/// from the `NEOTEST_SEED` environment variable if it is set, so that failures
+/// can be replayed.
+#[doc(hidden)]
+pub struct __Property {
+  cases: u32,
+}
+
+impl __Property {
+  /// Constructs a new [`__Property`] that runs the specified number of cases
+  ///
+  /// # Arguments
+  ///
+  /// * `cases` - the number of cases to run
+  #[doc(hidden)]
+  pub fn new(cases: u32) -> Self {
+    Self { cases }
+  }
+
+  /// Runs `f` against the values generated by `strategy`, failing with the
+  /// simplest values that fail if any case fails.
+  ///
+  /// Cases that are filtered by the `exclude` input of the test are skipped,
+  /// but still count towards the number of cases.
+  ///
+  /// # Arguments
+  ///
+  /// * `strategy` - the strategy of the values of the parameters
+  /// * `describe` - the function that describes the values of a case
+  /// * `f` - the function that runs a case
+  #[doc(hidden)]
+  pub fn run<S, D, F>(&self, strategy: S, describe: D, mut f: F) -> TestResult
+  where
+    S: Strategy,
+    D: Fn(&S::Value) -> String,
+    F: FnMut(S::Value) -> TestResult,
+  {
+    let seed = seed()?;
+    let mut rng = TestRng::from_seed(seed);
+    for case in 0..self.cases {
+      let value = strategy.generate(&mut rng);
+      let reason = match run_case(&mut f, value.clone()) {
+        Some(reason) => reason,
+        None => continue,
+      };
+      let (value, reason, shrinks) = silenced(|| shrink(&strategy, &mut f, value, reason));
+      let error = PropertyError::new(seed, case, shrinks, describe(&value), reason);
+      return Err(Box::new(error));
+    }
+    Ok(())
+  }
+}
+
+/// Runs a single case, returning the reason that it failed -- or [`None`] if
+/// it either passed or was filtered.
+///
+/// # Arguments
+///
+/// * `f` - the function that runs a case
+/// * `value` - the values of the case
+fn run_case<T, F>(f: &mut F, value: T) -> Option<String>
+where
+  F: FnMut(T) -> TestResult,
+{
+  record_filtered();
+  let result = panic::catch_unwind(AssertUnwindSafe(|| f(value)));
+  let filtered = take_filtered();
+  match result {
+    Ok(Ok(())) => None,
+    _ if filtered => None,
+    Ok(Err(error)) => Some(error.to_string()),
+    Err(payload) => Some(panic_message(payload.as_ref())),
+  }
+}
+
+/// Shrinks the failing `value` by repeatedly replacing it with the first
+/// simpler value that still fails, returning the simplest failing value along
+/// with the reason it failed and the number of times it was shrunk.
+///
+/// # Arguments
+///
+/// * `strategy` - the strategy that generated the value
+/// * `f` - the function that runs a case
+/// * `value` - the failing value
+/// * `reason` - the reason that the value failed
+fn shrink<S, F>(
+  strategy: &S,
+  f: &mut F,
+  value: S::Value,
+  reason: String,
+) -> (S::Value, String, usize)
+where
+  S: Strategy,
+  F: FnMut(S::Value) -> TestResult,
+{
+  let (mut value, mut reason) = (value, reason);
+  let mut shrinks = 0;
+  let mut attempts = 0;
+  'shrink: while attempts < MAX_SHRINK_ATTEMPTS {
+    for candidate in strategy.shrink(&value) {
+      attempts += 1;
+      if let Some(failure) = run_case(f, candidate.clone()) {
+        value = candidate;
+        reason = failure;
+        shrinks += 1;
+        continue 'shrink;
+      }
+      if attempts >= MAX_SHRINK_ATTEMPTS {
+        break;
+      }
+    }
+    break;
+  }
+  (value, reason, shrinks)
+}
+
+/// Runs `f` without reporting the panics of this thread, which would otherwise
+/// be reported for every value that fails while shrinking.
+///
+/// # Arguments
+///
+/// * `f` - the function to run
+fn silenced<T, F>(f: F) -> T
+where
+  F: FnOnce() -> T,
+{
+  static HOOK: Once = Once::new();
+  HOOK.call_once(|| {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+      if !SILENCED.with(Cell::get) {
+        hook(info)
+      }
+    }));
+  });
+
+  SILENCED.with(|v| v.set(true));
+  let result = f();
+  SILENCED.with(|v| v.set(false));
+  result
+}
+
+/// Returns the seed of the generator, which is read from the `NEOTEST_SEED`
+/// environment variable if it is set, or is otherwise random.
+fn seed() -> Result<u64> {
+  let seed = match std::env::var("NEOTEST_SEED") {
+    Ok(seed) => seed,
+    Err(_) => {
+      let mut hasher = RandomState::new().build_hasher();
+      if let Ok(time) = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+        hasher.write_u128(time.as_nanos());
+      }
+      return Ok(hasher.finish());
+    }
+  };
+  let trimmed = seed.trim();
+  let parsed = match trimmed.strip_prefix("0x") {
+    Some(hex) => u64::from_str_radix(hex, 16),
+    None => trimmed.parse(),
+  };
+  parsed.map_err(|_| {
+    format!("invalid NEOTEST_SEED '{seed}'; expected an integer such as 42 or 0x2a").into()
+  })
+}
@@ -0,0 +1,78 @@
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::{pin, Pin};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+/// Runs the `future` to completion on the current thread, parking the thread
+/// whenever the future is waiting to be woken.
+///
+/// This is the default executor of `async fn` tests, which is sufficient for
+/// any future that does not depend on a specific runtime.
+///
+/// # Arguments
+///
+/// * `future` - the future to run
+#[doc(hidden)]
+pub fn __block_on<F>(future: F) -> F::Output
+where
+  F: Future,
+{
+  let mut future = pin!(future);
+  let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+  let mut context = Context::from_waker(&waker);
+  loop {
+    match future.as_mut().poll(&mut context) {
+      Poll::Ready(output) => return output,
+      Poll::Pending => thread::park(),
+    }
+  }
+}
+
+/// Wraps the `future` so that any panic while polling it is caught, the same
+/// as [`panic::catch_unwind`] does for functions.
+///
+/// # Arguments
+///
+/// * `future` - the future to run
+#[doc(hidden)]
+pub fn __catch_unwind<F>(future: F) -> __CatchUnwind<F>
+where
+  F: Future,
+{
+  __CatchUnwind(Box::pin(future))
+}
+
+/// The future returned by [`__catch_unwind`].
+#[doc(hidden)]
+pub struct __CatchUnwind<F>(Pin<Box<F>>);
+
+impl<F> Future for __CatchUnwind<F>
+where
+  F: Future,
+{
+  type Output = thread::Result<F::Output>;
+
+  fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    let future = self.0.as_mut();
+    match panic::catch_unwind(AssertUnwindSafe(|| future.poll(cx))) {
+      Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+      Ok(Poll::Pending) => Poll::Pending,
+      Err(e) => Poll::Ready(Err(e)),
+    }
+  }
+}
+
+/// A waker that unparks the thread blocking on a future.
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+  fn wake(self: Arc<Self>) {
+    self.0.unpark();
+  }
+
+  fn wake_by_ref(self: &Arc<Self>) {
+    self.0.unpark();
+  }
+}
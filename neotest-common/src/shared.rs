@@ -0,0 +1,56 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Mutex, OnceLock};
+
+use crate::{Fixture, Result};
+
+/// The result of preparing a shared fixture.
+///
+/// Errors are stored by their message, since [`Error`] is neither [`Send`] nor
+/// [`Sync`] and so cannot be shared between tests.
+///
+/// [`Error`]: crate::Error
+type Shared<T> = std::result::Result<T, String>;
+
+/// The registry of all shared fixtures, keyed by the fixture type.
+///
+/// Each entry is a leaked `OnceLock<Shared<T>>`, so that each fixture is
+/// prepared at most once for the lifetime of the test binary.
+static REGISTRY: OnceLock<Mutex<HashMap<TypeId, &'static (dyn Any + Send + Sync)>>> =
+  OnceLock::new();
+
+/// Returns the shared instance of the fixture `T`, preparing it on first use.
+///
+/// Preparation is only ever attempted once. If it fails or panics, the failure
+/// is cached and reported to every test that requires the fixture.
+#[doc(hidden)]
+pub fn __shared<T>() -> Result<&'static T>
+where
+  T: Fixture + Send + Sync + 'static,
+{
+  let cell = shared_cell::<T>();
+  let shared = cell.get_or_init(|| match panic::catch_unwind(AssertUnwindSafe(T::prepare)) {
+    Ok(result) => result.map_err(|e| e.to_string()),
+    Err(_) => Err("fixture panicked while being prepared".to_string()),
+  });
+  shared.as_ref().map_err(|e| e.clone().into())
+}
+
+/// Returns the cell that holds the shared instance of `T`, creating it if it
+/// does not yet exist.
+fn shared_cell<T>() -> &'static OnceLock<Shared<T>>
+where
+  T: Send + Sync + 'static,
+{
+  let registry = REGISTRY.get_or_init(Default::default);
+  // A poisoned registry is still consistent, since entries are only inserted
+  let mut registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+  let cell = *registry.entry(TypeId::of::<T>()).or_insert_with(|| {
+    let cell: &'static OnceLock<Shared<T>> = Box::leak(Box::default());
+    cell
+  });
+  cell
+    .downcast_ref()
+    .expect("shared fixture registered with the wrong type")
+}
@@ -0,0 +1,147 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::OnceLock;
+use std::thread;
+use std::time::Duration;
+
+use crate::{TestResult, TimeoutError};
+
+/// The environment variable that sets the default timeout of every test that
+/// does not specify its own, e.g. `NEOTEST_TIMEOUT=30s`.
+const TIMEOUT_ENV: &str = "NEOTEST_TIMEOUT";
+
+/// Runs the test-case `f`, failing with a [`TimeoutError`] if it does not
+/// complete within the `timeout` -- or within the default timeout from the
+/// `NEOTEST_TIMEOUT` environment variable, if no timeout is specified.
+///
+/// Tests with a timeout are run on a separate worker thread, while the calling
+/// thread acts as the watchdog. A test that times out cannot be stopped, and
+/// so its worker thread is abandoned until the test binary exits.
+///
+/// # Arguments
+///
+/// * `test` - the path of the test-case being run
+/// * `timeout` - the timeout of the test-case, if one was specified
+/// * `f` - the function that runs the test-case
+#[doc(hidden)]
+pub fn __run_with_timeout<F>(test: &'static str, timeout: Option<Duration>, f: F) -> TestResult
+where
+  F: FnOnce() -> TestResult + Send + 'static,
+{
+  let timeout = match timeout.or_else(default_timeout) {
+    Some(timeout) => timeout,
+    None => return f(),
+  };
+
+  // Test errors are not `Send`, so they are sent back as their message.
+  let (sender, receiver) = mpsc::channel();
+  let mut worker = thread::Builder::new();
+  if let Some(name) = thread::current().name() {
+    worker = worker.name(name.to_string());
+  }
+  worker.spawn(move || {
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    let _ = sender.send(result.map(|v| v.map_err(|e| format!("{e:?}"))));
+  })?;
+
+  match receiver.recv_timeout(timeout) {
+    Ok(Ok(result)) => result.map_err(|e| WorkerError(e).into()),
+    Ok(Err(panic)) => panic::resume_unwind(panic),
+    Err(RecvTimeoutError::Timeout) => Err(TimeoutError::new(test, timeout).into()),
+    Err(RecvTimeoutError::Disconnected) => {
+      Err(format!("test `{test}` exited without a result").into())
+    }
+  }
+}
+
+/// Parses a duration in the form `<number><unit>`, where `<unit>` is one of
+/// `ms`, `s`, `m`, or `h` -- e.g. `250ms`, `5s`, or `1.5m`.
+///
+/// Returns [`None`] if the duration is not in this form.
+///
+/// # Arguments
+///
+/// * `s` - the string to parse
+#[doc(hidden)]
+pub fn __parse_duration(s: &str) -> Option<Duration> {
+  let s = s.trim();
+  let split = s
+    .find(|c: char| !c.is_ascii_digit() && c != '.')
+    .unwrap_or(s.len());
+  let (value, unit) = s.split_at(split);
+  let scale = match unit {
+    "ms" => 0.001,
+    "s" => 1.0,
+    "m" => 60.0,
+    "h" => 3600.0,
+    _ => return None,
+  };
+  let value: f64 = value.parse().ok()?;
+  Duration::try_from_secs_f64(value * scale).ok()
+}
+
+/// Returns the default timeout from the `NEOTEST_TIMEOUT` environment variable,
+/// which is read once per test binary.
+fn default_timeout() -> Option<Duration> {
+  static DEFAULT: OnceLock<Option<Duration>> = OnceLock::new();
+  *DEFAULT.get_or_init(|| {
+    let value = std::env::var(TIMEOUT_ENV).ok()?;
+    match __parse_duration(&value) {
+      Some(timeout) if !timeout.is_zero() => Some(timeout),
+      _ => panic!("invalid {TIMEOUT_ENV} value '{value}'; expected a duration such as `5s`"),
+    }
+  })
+}
+
+/// The error of a test that was run on a worker thread, which is reported with
+/// the same message as the original error.
+struct WorkerError(String);
+
+impl std::fmt::Display for WorkerError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(&self.0)
+  }
+}
+
+impl std::fmt::Debug for WorkerError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(&self.0)
+  }
+}
+
+impl std::error::Error for WorkerError {}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_parse_duration_units() {
+    assert_eq!(__parse_duration("250ms"), Some(Duration::from_millis(250)));
+    assert_eq!(__parse_duration("5s"), Some(Duration::from_secs(5)));
+    assert_eq!(__parse_duration("2m"), Some(Duration::from_secs(120)));
+    assert_eq!(__parse_duration("1h"), Some(Duration::from_secs(3600)));
+  }
+
+  #[test]
+  fn test_parse_duration_fractions() {
+    assert_eq!(__parse_duration("1.5m"), Some(Duration::from_secs(90)));
+    assert_eq!(__parse_duration("0.5s"), Some(Duration::from_millis(500)));
+    assert_eq!(
+      __parse_duration(" 2.5s "),
+      Some(Duration::from_millis(2500))
+    );
+  }
+
+  #[test]
+  fn test_parse_duration_zero() {
+    assert_eq!(__parse_duration("0s"), Some(Duration::ZERO));
+  }
+
+  #[test]
+  fn test_parse_duration_invalid() {
+    for s in ["", "5", "s", "5d", "5 s", "-5s", "1.2.3s", "5sec", "ms5"] {
+      assert_eq!(__parse_duration(s), None, "'{s}' should not parse");
+    }
+  }
+}
@@ -0,0 +1,36 @@
+//! This crate depends on `neotest` and `neotest_common`, but not on
+//! `neotest_macros`, so that the code generated by the macros is checked to
+//! only name paths that are reachable by any downstream crate.
+
+pub mod sets {
+  use neotest::parameter_set;
+
+  parameter_set!(pub(crate) EDGE_U32 = [zero: 0, one: 1, max: u32::MAX]);
+}
+
+#[cfg(test)]
+mod test {
+  use crate::sets::EDGE_U32;
+  use neotest::{neotest, neotest_suite, parameter_set};
+
+  parameter_set!(SMALL = [1, 2, 3]);
+
+  #[neotest(parameter = value as EDGE_U32)]
+  fn test_parameter_set(value: u32) {
+    assert!(value == 0 || value == 1 || value == u32::MAX);
+  }
+
+  #[neotest(parameter = value as SMALL)]
+  fn test_local_parameter_set(value: u32) {
+    assert!((1..=3).contains(&value));
+  }
+
+  #[neotest_suite(parameter = value as SMALL)]
+  mod suite {
+    #[neotest(parameter = edge as EDGE_U32)]
+    fn test_suite_parameter_set(value: u32, edge: u32) {
+      assert!((1..=3).contains(&value));
+      assert!(edge == 0 || edge == 1 || edge == u32::MAX);
+    }
+  }
+}
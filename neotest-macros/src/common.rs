@@ -9,6 +9,16 @@ pub mod attribute {
     parse_quote!(#[allow(dead_code)])
   }
 
+  /// A helper function for creating the attribute `#[allow(non_snake_case)]`
+  pub fn allow_non_snake_case() -> Attribute {
+    parse_quote!(#[allow(non_snake_case)])
+  }
+
+  /// A helper function for creating the attribute `#[allow(unused_imports)]`
+  pub fn allow_unused_imports() -> Attribute {
+    parse_quote!(#[allow(unused_imports)])
+  }
+
   /// A helper function for creating the attribute `#[test]`
   pub fn test() -> Attribute {
     parse_quote!(#[test])
@@ -52,6 +62,44 @@ pub mod ident {
     format_ident!("__context")
   }
 
+  /// Creates the ident of the function that runs an `async fn` test on the
+  /// executor specified for it.
+  pub fn block_on() -> Ident {
+    format_ident!("__neotest_block_on")
+  }
+
+  /// Creates the ident of the runner of the cases of a test with generated
+  /// parameters.
+  pub fn generated_cases() -> Ident {
+    format_ident!("__neotest_cases")
+  }
+
+  /// Creates the ident that holds whether a test-case is excluded by the
+  /// `exclude` input of its test.
+  pub fn excluded() -> Ident {
+    format_ident!("__neotest_excluded")
+  }
+
+  /// Creates the ident that holds each generated input of a parameter, while
+  /// its test-cases are being run.
+  ///
+  /// # Arguments
+  ///
+  /// * `base` - the name of the generated parameter
+  pub fn new_generated_parameter(base: &Ident) -> Ident {
+    format_ident!("__neotest_param_{base}")
+  }
+
+  /// Creates the ident that holds all generated inputs of a parameter, when
+  /// the inputs must be combined with those of other generated parameters.
+  ///
+  /// # Arguments
+  ///
+  /// * `base` - the name of the generated parameter
+  pub fn new_generated_inputs(base: &Ident) -> Ident {
+    format_ident!("__neotest_inputs_{base}")
+  }
+
   /// Creates an ident used for performing the actual test itself
   ///
   /// # Arguments
@@ -70,30 +118,59 @@ pub mod ident {
     format_ident!("{base}_dispatcher")
   }
 
-  /// Creates an ident for test input dispatch functions
+  /// Creates an ident used for a single test-case, which invokes the
+  /// dispatcher with one concrete set of generic arguments and parameters.
   ///
-  /// The name is produced by concatenating the indices into a string identifier.
-  /// The indices each represent which parameter is expanded from the inputs.
+  /// # Arguments
+  ///
+  /// * `base` - the base name of the test (what is specified by the user)
+  /// * `index` - the index of the test-case
+  pub fn new_test_case(base: &Ident, index: usize) -> Ident {
+    format_ident!("__neotest_{base}_case_{index}")
+  }
+
+  /// Creates an ident for the static that holds a module-scoped fixture
   ///
   /// # Arguments
   ///
-  /// * `indices` - the indices that represent the input values
-  /// * `span` - the span for where this input comes from
-  pub fn new_test_input(indices: &[usize], span: Span) -> Ident {
-    use std::fmt::Write;
-    assert!(!indices.is_empty());
+  /// * `ty` - the type of the fixture
+  pub fn new_module_fixture(ty: &syn::Type) -> Ident {
+    use quote::ToTokens;
 
-    let mut out = String::from("input");
+    let name = sanitize(&ty.to_token_stream().to_string());
+    format_ident!("__NEOTEST_MODULE_FIXTURE_{name}")
+  }
 
-    // If we have more than one input, make this plural.
-    if indices.len() > 1 {
-      let _ = write!(&mut out, "s");
+  /// Converts the specified string into something resembling an identifier, by
+  /// replacing each run of non-identifier characters with a single underscore.
+  ///
+  /// # Arguments
+  ///
+  /// * `s` - the string to sanitize
+  pub fn sanitize(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+      if c.is_ascii_alphanumeric() {
+        out.push(c);
+      } else if !out.is_empty() && !out.ends_with('_') {
+        out.push('_');
+      }
     }
+    out.trim_end_matches('_').to_string()
+  }
 
-    for n in indices {
-      let _ = write!(&mut out, "_{}", n);
-    }
+  /// Creates an ident for generic test input functions
+  ///
+  /// The name is produced by joining the names of each generic input into a
+  /// single identifier, e.g. `u32` or `u32_String`.
+  ///
+  /// # Arguments
+  ///
+  /// * `names` - the names of each generic input value
+  /// * `span` - the span for where this input comes from
+  pub fn new_test_generic_input(names: &[String], span: Span) -> Ident {
+    assert!(!names.is_empty());
 
-    Ident::new(&out, span)
+    Ident::new(&names.join("_"), span)
   }
 }
@@ -0,0 +1,602 @@
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote, ToTokens};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::token::{Comma, Eq};
+use syn::{
+  parse_macro_input, parse_quote, Attribute, Data, DataEnum, DeriveInput, Expr, Fields,
+  GenericArgument, Generics, PathArguments, Type, Variant,
+};
+
+use crate::derive_fixture::contains_any_ident;
+
+pub fn derive(input: TokenStream) -> TokenStream {
+  let input: DeriveInput = parse_macro_input!(input);
+
+  match derive_impl(input) {
+    Ok(output) => output.into(),
+    Err(error) => error.to_compile_error().into(),
+  }
+}
+
+fn derive_impl(input: DeriveInput) -> syn::Result<TokenStream2> {
+  let DeriveInput {
+    attrs,
+    ident,
+    mut generics,
+    data,
+    ..
+  } = input;
+
+  let args = ArbitraryArg::parse_all(&attrs, &["max_depth"], "a type")?;
+  let max_depth = args.into_iter().find_map(|(_, arg)| match arg {
+    ArbitraryArg::MaxDepth(depth) => Some(depth),
+    _ => None,
+  });
+  let self_ty = SelfType::new(&ident, &generics);
+
+  let (arbitrary, shrink, fields) = match &data {
+    Data::Struct(data) => {
+      if let Some(depth) = &max_depth {
+        return Err(syn::Error::new(
+          depth.span(),
+          "argument 'max_depth' can only be specified for enums",
+        ));
+      }
+      let fields = ArbitraryFields::new(&data.fields)?;
+      let path: syn::Path = parse_quote!(Self);
+      let arbitrary = fields.construct(&path, fields.generators());
+      let shrink = fields.shrink(&path, &self_ty);
+      (arbitrary, shrink, vec![fields])
+    }
+    Data::Enum(data) => {
+      let variants = ArbitraryVariants::new(&ident, data)?;
+      let arbitrary = variants.arbitrary(max_depth);
+      let shrink = variants.shrink(&self_ty);
+      let fields = variants.variants.into_iter().map(|v| v.fields).collect();
+      (arbitrary, shrink, fields)
+    }
+    Data::Union(data) => {
+      return Err(syn::Error::new(
+        data.union_token.span,
+        "Arbitrary cannot be derived for unions",
+      ))
+    }
+  };
+  add_bounds(&mut generics, &fields);
+  let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+  let rng = rng_ident();
+
+  Ok(quote! {
+    impl #impl_generics ::neotest_common::prop::Arbitrary for #ident #ty_generics #where_clause {
+      fn arbitrary(#rng: &mut ::neotest_common::prop::TestRng) -> Self {
+        #arbitrary
+      }
+
+      fn shrink(&self) -> ::std::vec::Vec<Self> {
+        #shrink
+      }
+    }
+  })
+}
+
+/// Adds an [`Arbitrary`] bound to every generic type parameter that is used
+/// by a field that is generated by its type, rather than by a strategy.
+///
+/// [`Arbitrary`]: neotest_common::prop::Arbitrary
+///
+/// # Arguments
+///
+/// * `generics` - the generics of the derived type to add bounds to
+/// * `fields` - the fields of the derived type
+fn add_bounds(generics: &mut Generics, fields: &[ArbitraryFields]) {
+  let params: Vec<syn::Ident> = generics.type_params().map(|v| v.ident.clone()).collect();
+  for param in params {
+    let is_used = fields
+      .iter()
+      .flat_map(|v| v.fields.iter())
+      .filter(|v| v.strategy.is_none())
+      .any(|v| contains_any_ident(v.ty.to_token_stream(), std::slice::from_ref(&param)));
+    if is_used {
+      let predicates = &mut generics.make_where_clause().predicates;
+      predicates.push(parse_quote!(#param: ::neotest_common::prop::Arbitrary));
+    }
+  }
+}
+
+/// Creates the ident of the generator within the derived functions.
+fn rng_ident() -> syn::Ident {
+  format_ident!("__neotest_rng")
+}
+
+/// Creates the ident of the shrunk values within the derived `shrink`.
+fn result_ident() -> syn::Ident {
+  format_ident!("__neotest_result")
+}
+
+/// A single argument of an `#[arbitrary(...)]` attribute, which is one of:
+///
+/// ```ignore
+/// #[arbitrary(strategy = <expr>)]
+/// #[arbitrary(weight = <number>)]
+/// #[arbitrary(recursive)]
+/// #[arbitrary(max_depth = <number>)]
+/// ```
+enum ArbitraryArg {
+  /// Generates and shrinks a field with the strategy of the expression
+  Strategy(Box<Expr>),
+  /// Weighs how often a variant is generated, relative to the others
+  Weight(syn::LitInt),
+  /// Marks a variant as containing its own type, when it does so indirectly
+  Recursive,
+  /// Limits the number of nested values of a recursive enum
+  MaxDepth(syn::LitInt),
+}
+
+impl ArbitraryArg {
+  /// Parses every argument of the `#[arbitrary(...)]` attributes, along with
+  /// the ident that names each of them.
+  ///
+  /// # Arguments
+  ///
+  /// * `attrs` - the attributes to parse
+  /// * `allowed` - the names of the arguments allowed in these attributes
+  /// * `location` - a description of what the attributes are applied to
+  fn parse_all(
+    attrs: &[Attribute],
+    allowed: &[&str],
+    location: &str,
+  ) -> syn::Result<Vec<(syn::Ident, Self)>> {
+    let mut result: Vec<(syn::Ident, Self)> = Vec::new();
+    for attr in attrs.iter().filter(|v| v.path.is_ident("arbitrary")) {
+      let args = attr.parse_args_with(Punctuated::<NamedArg, Comma>::parse_terminated)?;
+      for NamedArg(ident, arg) in args {
+        let name = ident.to_string();
+        if !allowed.contains(&&*name) {
+          return Err(syn::Error::new(
+            ident.span(),
+            format!("argument '{name}' cannot be applied to {location}"),
+          ));
+        }
+        if result.iter().any(|(v, _)| *v == ident) {
+          return Err(syn::Error::new(
+            ident.span(),
+            format!("argument '{name}' can only be specified once"),
+          ));
+        }
+        result.push((ident, arg));
+      }
+    }
+    Ok(result)
+  }
+}
+
+/// An [`ArbitraryArg`] along with the ident that names it.
+struct NamedArg(syn::Ident, ArbitraryArg);
+
+impl Parse for NamedArg {
+  fn parse(input: ParseStream) -> syn::Result<Self> {
+    let option: syn::Ident = input.parse()?;
+    let option_str = option.to_string();
+
+    let arg = match &*option_str {
+      "strategy" => {
+        input.parse::<Eq>()?;
+        ArbitraryArg::Strategy(Box::new(input.parse()?))
+      }
+      "weight" => {
+        input.parse::<Eq>()?;
+        let weight: syn::LitInt = input.parse()?;
+        weight.base10_parse::<u32>()?;
+        ArbitraryArg::Weight(weight)
+      }
+      "recursive" => ArbitraryArg::Recursive,
+      "max_depth" => {
+        input.parse::<Eq>()?;
+        let depth: syn::LitInt = input.parse()?;
+        depth.base10_parse::<usize>()?;
+        ArbitraryArg::MaxDepth(depth)
+      }
+      _ => {
+        return Err(syn::Error::new(
+          option.span(),
+          format!("unknown arbitrary argument '{option_str}'"),
+        ))
+      }
+    };
+    Ok(NamedArg(option, arg))
+  }
+}
+
+/// The type being derived, for finding the fields that hold a value of the
+/// type itself -- either directly, or within a [`Box`].
+struct SelfType {
+  ty: String,
+}
+
+impl SelfType {
+  /// Creates the [`SelfType`] of the type with the specified name and
+  /// generics.
+  ///
+  /// # Arguments
+  ///
+  /// * `ident` - the name of the type
+  /// * `generics` - the generics of the type
+  fn new(ident: &syn::Ident, generics: &Generics) -> Self {
+    let (_, ty_generics, _) = generics.split_for_impl();
+    Self {
+      ty: quote!(#ident #ty_generics).to_string(),
+    }
+  }
+
+  /// Returns the expression that clones the value of the type itself from
+  /// the binding of a field of type `ty`, if the field holds one.
+  ///
+  /// # Arguments
+  ///
+  /// * `ty` - the type of the field
+  /// * `binding` - the binding of the field
+  fn subterm(&self, ty: &Type, binding: &syn::Ident) -> Option<TokenStream2> {
+    if self.is_self(ty) {
+      return Some(quote! { ::std::clone::Clone::clone(#binding) });
+    }
+    let path = match ty {
+      Type::Path(ty) if ty.qself.is_none() => &ty.path,
+      _ => return None,
+    };
+    let segment = path.segments.last()?;
+    let args = match &segment.arguments {
+      PathArguments::AngleBracketed(args) if segment.ident == "Box" => &args.args,
+      _ => return None,
+    };
+    match args.first() {
+      Some(GenericArgument::Type(inner)) if args.len() == 1 && self.is_self(inner) => {
+        Some(quote! { ::std::clone::Clone::clone(&**#binding) })
+      }
+      _ => None,
+    }
+  }
+
+  /// Tests whether `ty` names the type itself.
+  ///
+  /// # Arguments
+  ///
+  /// * `ty` - the type to test
+  fn is_self(&self, ty: &Type) -> bool {
+    let ty = ty.to_token_stream().to_string();
+    ty == "Self" || ty == self.ty
+  }
+}
+
+/// A single field of a derived type, which is generated and shrunk either by
+/// its type or by the strategy of its `#[arbitrary(strategy = ...)]`.
+struct ArbitraryField<'a> {
+  ident: Option<&'a syn::Ident>,
+  ty: &'a Type,
+  binding: syn::Ident,
+  strategy: Option<Expr>,
+}
+
+impl<'a> ArbitraryField<'a> {
+  /// Returns the expression that generates this field
+  fn generator(&self) -> TokenStream2 {
+    let rng = rng_ident();
+    let ty = self.ty;
+    match &self.strategy {
+      Some(strategy) => quote! { ::neotest_common::prop::Strategy::generate(&(#strategy), #rng) },
+      None => quote! { <#ty as ::neotest_common::prop::Arbitrary>::arbitrary(#rng) },
+    }
+  }
+
+  /// Returns the expression that produces the simpler values of this field,
+  /// from its binding.
+  fn shrinker(&self) -> TokenStream2 {
+    let binding = &self.binding;
+    match &self.strategy {
+      Some(strategy) => quote! { ::neotest_common::prop::Strategy::shrink(&(#strategy), #binding) },
+      None => quote! { ::neotest_common::prop::Arbitrary::shrink(#binding) },
+    }
+  }
+}
+
+/// The fields of a struct, or of a single variant of an enum.
+struct ArbitraryFields<'a> {
+  fields: Vec<ArbitraryField<'a>>,
+  is_named: bool,
+  is_unit: bool,
+}
+
+impl<'a> ArbitraryFields<'a> {
+  /// Creates the [`ArbitraryFields`] of the fields of a struct or variant.
+  ///
+  /// # Arguments
+  ///
+  /// * `fields` - the fields of the struct or variant
+  fn new(fields: &'a Fields) -> syn::Result<Self> {
+    let mut result: Vec<ArbitraryField<'a>> = Vec::with_capacity(fields.len());
+    for (i, field) in fields.iter().enumerate() {
+      let args = ArbitraryArg::parse_all(&field.attrs, &["strategy"], "a field")?;
+      let strategy = args.into_iter().find_map(|(_, arg)| match arg {
+        ArbitraryArg::Strategy(strategy) => Some(*strategy),
+        _ => None,
+      });
+      result.push(ArbitraryField {
+        ident: field.ident.as_ref(),
+        ty: &field.ty,
+        binding: format_ident!("__neotest_field_{}", i),
+        strategy,
+      });
+    }
+    Ok(Self {
+      fields: result,
+      is_named: matches!(fields, Fields::Named(_)),
+      is_unit: matches!(fields, Fields::Unit),
+    })
+  }
+
+  /// Returns the expression that generates each field
+  fn generators(&self) -> Vec<TokenStream2> {
+    self.fields.iter().map(ArbitraryField::generator).collect()
+  }
+
+  /// Returns the pattern that binds each field of a value at `path`
+  ///
+  /// # Arguments
+  ///
+  /// * `path` - the path of the struct or variant
+  fn pattern(&self, path: &syn::Path) -> TokenStream2 {
+    let bindings = self.fields.iter().map(|v| v.binding.to_token_stream());
+    self.construct(path, bindings.collect())
+  }
+
+  /// Returns the expression that constructs a value at `path` from the
+  /// expression of each field.
+  ///
+  /// # Arguments
+  ///
+  /// * `path` - the path of the struct or variant
+  /// * `values` - the expression of each field
+  fn construct(&self, path: &syn::Path, values: Vec<TokenStream2>) -> TokenStream2 {
+    if self.is_unit {
+      quote! { #path }
+    } else if self.is_named {
+      let names = self.fields.iter().map(|v| v.ident);
+      quote! { #path { #(#names: #values),* } }
+    } else {
+      quote! { #path(#(#values),*) }
+    }
+  }
+
+  /// Returns the statements that push the simpler values of a value at
+  /// `path`, whose fields are bound by [`pattern`] -- where each value shrinks
+  /// a single field.
+  ///
+  /// [`pattern`]: Self::pattern
+  ///
+  /// # Arguments
+  ///
+  /// * `path` - the path of the struct or variant
+  fn shrink_fields(&self, path: &syn::Path) -> TokenStream2 {
+    let result = result_ident();
+    let value = format_ident!("__neotest_value");
+    let mut tokens = TokenStream2::new();
+    for (i, field) in self.fields.iter().enumerate() {
+      let values: Vec<TokenStream2> = self
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(j, v)| match i == j {
+          true => value.to_token_stream(),
+          false => {
+            let binding = &v.binding;
+            quote! { ::std::clone::Clone::clone(#binding) }
+          }
+        })
+        .collect();
+      let shrinker = field.shrinker();
+      let shrunk = self.construct(path, values);
+      tokens.extend(quote! {
+        for #value in #shrinker {
+          #result.push(#shrunk);
+        }
+      });
+    }
+    tokens
+  }
+
+  /// Returns the body of the derived `shrink` of a struct.
+  ///
+  /// Structs that hold a value of their own type first shrink to that value.
+  ///
+  /// # Arguments
+  ///
+  /// * `path` - the path of the struct
+  /// * `self_ty` - the type being derived
+  fn shrink(&self, path: &syn::Path, self_ty: &SelfType) -> TokenStream2 {
+    if self.fields.is_empty() {
+      return quote! { ::std::vec::Vec::new() };
+    }
+    let result = result_ident();
+    let pattern = self.pattern(path);
+    let subterms = self.subterms(self_ty);
+    let shrink_fields = self.shrink_fields(path);
+    quote! {
+      let mut #result: ::std::vec::Vec<Self> = ::std::vec::Vec::new();
+      let #pattern = self;
+      #subterms
+      #shrink_fields
+      #result
+    }
+  }
+
+  /// Returns the statements that push each field that holds a value of the
+  /// type being derived, which are simpler than the value that holds them.
+  ///
+  /// # Arguments
+  ///
+  /// * `self_ty` - the type being derived
+  fn subterms(&self, self_ty: &SelfType) -> TokenStream2 {
+    let result = result_ident();
+    let subterms = self
+      .fields
+      .iter()
+      .filter_map(|v| self_ty.subterm(v.ty, &v.binding));
+    quote! { #(#result.push(#subterms);)* }
+  }
+}
+
+/// A single variant of an enum, along with its weight.
+struct ArbitraryVariant<'a> {
+  variant: &'a Variant,
+  fields: ArbitraryFields<'a>,
+  weight: u32,
+  is_recursive: bool,
+}
+
+impl<'a> ArbitraryVariant<'a> {
+  /// Returns the path of this variant
+  fn path(&self) -> syn::Path {
+    let ident = &self.variant.ident;
+    parse_quote!(Self::#ident)
+  }
+}
+
+/// The variants of a derived enum.
+struct ArbitraryVariants<'a> {
+  variants: Vec<ArbitraryVariant<'a>>,
+}
+
+impl<'a> ArbitraryVariants<'a> {
+  /// Creates the [`ArbitraryVariants`] of an enum, ensuring that a variant
+  /// can always be generated.
+  ///
+  /// # Arguments
+  ///
+  /// * `ident` - the name of the enum
+  /// * `data` - the variants of the enum
+  fn new(ident: &syn::Ident, data: &'a DataEnum) -> syn::Result<Self> {
+    let self_idents = [ident.clone(), syn::Ident::new("Self", Span::call_site())];
+    let mut variants: Vec<ArbitraryVariant<'a>> = Vec::with_capacity(data.variants.len());
+    for variant in data.variants.iter() {
+      let args = ArbitraryArg::parse_all(&variant.attrs, &["weight", "recursive"], "a variant")?;
+      let mut weight = 1;
+      let mut is_recursive = variant
+        .fields
+        .iter()
+        .any(|v| contains_any_ident(v.ty.to_token_stream(), &self_idents));
+      for (_, arg) in args {
+        match arg {
+          ArbitraryArg::Weight(v) => weight = v.base10_parse()?,
+          ArbitraryArg::Recursive => is_recursive = true,
+          _ => {}
+        }
+      }
+      variants.push(ArbitraryVariant {
+        variant,
+        fields: ArbitraryFields::new(&variant.fields)?,
+        weight,
+        is_recursive,
+      });
+    }
+
+    let name = ident.to_string();
+    if variants.iter().all(|v| v.weight == 0) {
+      return Err(syn::Error::new(
+        ident.span(),
+        format!("Arbitrary cannot be derived for enum '{name}', which has no variant with a nonzero weight"),
+      ));
+    }
+    let is_recursive = variants.iter().any(|v| v.is_recursive);
+    let has_leaf = variants.iter().any(|v| !v.is_recursive && v.weight > 0);
+    if is_recursive && !has_leaf {
+      return Err(syn::Error::new(
+        ident.span(),
+        format!("recursive enum '{name}' requires a variant that is not recursive, with a nonzero weight, to limit its depth"),
+      ));
+    }
+    Ok(Self { variants })
+  }
+
+  /// Returns the body of the derived `arbitrary`, which chooses a variant by
+  /// its weight -- and only chooses variants that are not recursive, once the
+  /// generator is nested more than `max_depth` times.
+  ///
+  /// # Arguments
+  ///
+  /// * `max_depth` - the maximum depth of recursive variants, if specified
+  fn arbitrary(&self, max_depth: Option<syn::LitInt>) -> TokenStream2 {
+    let rng = rng_ident();
+    let weights = self.variants.iter().map(|v| v.weight);
+    let leaf_weights = self
+      .variants
+      .iter()
+      .map(|v| if v.is_recursive { 0 } else { v.weight });
+    let arms = self.variants.iter().enumerate().map(|(i, v)| {
+      let value = v.fields.construct(&v.path(), v.fields.generators());
+      quote! { #i => #value, }
+    });
+    let len = self.variants.len();
+
+    let weights = if self.variants.iter().any(|v| v.is_recursive) {
+      let max_depth = match max_depth {
+        Some(depth) => quote!(#depth),
+        None => quote!(::neotest_common::prop::DEFAULT_MAX_DEPTH),
+      };
+      quote! {
+        match #rng.depth() > #max_depth {
+          true => [#(#leaf_weights),*],
+          false => [#(#weights),*],
+        }
+      }
+    } else {
+      quote! { [#(#weights),*] }
+    };
+    quote! {
+      #rng.recurse(|#rng| {
+        let __neotest_weights: [u32; #len] = #weights;
+        match #rng.weighted_index(&__neotest_weights) {
+          #(#arms)*
+          _ => ::std::unreachable!(),
+        }
+      })
+    }
+  }
+
+  /// Returns the body of the derived `shrink`.
+  ///
+  /// Values first shrink to each unit variant declared before their own, then
+  /// to each of their fields that holds a value of the enum itself, and then
+  /// by shrinking each of their fields.
+  ///
+  /// # Arguments
+  ///
+  /// * `self_ty` - the type being derived
+  fn shrink(&self, self_ty: &SelfType) -> TokenStream2 {
+    let result = result_ident();
+    let arms = self.variants.iter().enumerate().map(|(i, v)| {
+      let path = v.path();
+      let pattern = v.fields.pattern(&path);
+      let units = self.variants[..i]
+        .iter()
+        .filter(|v| v.fields.fields.is_empty() && v.weight > 0)
+        .map(ArbitraryVariant::path);
+      let subterms = v.fields.subterms(self_ty);
+      let shrink_fields = v.fields.shrink_fields(&path);
+      quote! {
+        #pattern => {
+          #(#result.push(#units);)*
+          #subterms
+          #shrink_fields
+        }
+      }
+    });
+    quote! {
+      let mut #result: ::std::vec::Vec<Self> = ::std::vec::Vec::new();
+      match self {
+        #(#arms)*
+      }
+      #result
+    }
+  }
+}
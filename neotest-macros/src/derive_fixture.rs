@@ -1,16 +1,352 @@
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{parse_macro_input, DeriveInput};
+use proc_macro2::{TokenStream as TokenStream2, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+use syn::parse::{Parse, ParseStream};
+use syn::token::Eq;
+use syn::{
+  parse_macro_input, parse_quote, Data, DeriveInput, Expr, Field, Fields, GenericParam, Generics,
+  Index, Member, Path, Type,
+};
 
 pub fn derive(input: TokenStream) -> TokenStream {
-  let DeriveInput { ident, .. } = parse_macro_input!(input);
+  let input: DeriveInput = parse_macro_input!(input);
 
-  let output = quote! {
-    impl ::neotest_common::Fixture for #ident {
+  match derive_impl(input) {
+    Ok(output) => output.into(),
+    Err(error) => error.to_compile_error().into(),
+  }
+}
+
+fn derive_impl(input: DeriveInput) -> syn::Result<TokenStream2> {
+  let DeriveInput {
+    ident,
+    mut generics,
+    data,
+    ..
+  } = input;
+
+  let (body, tear_down) = match FixtureFields::new(&data)? {
+    // Without any field attributes, the whole fixture is default-constructed.
+    None => {
+      generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(Self: ::std::default::Default));
+      (quote! { ::std::default::Default::default() }, None)
+    }
+    Some(fields) => {
+      fields.add_bounds(&mut generics);
+      (fields.to_token_stream(), fields.tear_down())
+    }
+  };
+  let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+  Ok(quote! {
+    impl #impl_generics ::neotest_common::Fixture for #ident #ty_generics #where_clause {
       fn prepare() -> ::neotest_common::Result<Self> {
-        Ok(Default::default())
+        Ok(#body)
       }
+
+      #tear_down
     }
-  };
-  output.into()
+  })
+}
+
+/// The means of initializing a single field of a derived fixture.
+///
+/// This is formed from the `#[fixture(...)]` attribute on the field, in the
+/// form of one of:
+///
+/// ```ignore
+/// #[fixture(default = <expr>)]
+/// #[fixture(with = <path>)]
+/// #[fixture(fixture)]
+/// ```
+enum FieldInit {
+  /// Initializes the field with [`Default::default`]
+  Default,
+  /// Initializes the field with the expression
+  Expr(Expr),
+  /// Initializes the field by calling the fallible function at the path
+  With(Path),
+  /// Initializes the field by preparing it as a nested fixture
+  Fixture,
+}
+
+impl Parse for FieldInit {
+  fn parse(input: ParseStream) -> syn::Result<Self> {
+    let option: syn::Ident = input.parse()?;
+    let option_str = option.to_string();
+
+    match &*option_str {
+      "default" => {
+        input.parse::<Eq>()?;
+        Ok(FieldInit::Expr(input.parse()?))
+      }
+      "with" => {
+        input.parse::<Eq>()?;
+        Ok(FieldInit::With(input.parse()?))
+      }
+      "fixture" => Ok(FieldInit::Fixture),
+      _ => Err(syn::Error::new(
+        option.span(),
+        format!("unknown fixture argument '{option_str}'"),
+      )),
+    }
+  }
+}
+
+impl FieldInit {
+  /// Determines how to initialize the specified field from its attributes.
+  ///
+  /// Returns [`None`] if the field has no `#[fixture(...)]` attribute.
+  ///
+  /// # Arguments
+  ///
+  /// * `field` - the field to initialize
+  fn from_field(field: &Field) -> syn::Result<Option<Self>> {
+    let mut result: Option<Self> = None;
+    for attr in field.attrs.iter().filter(|v| v.path.is_ident("fixture")) {
+      if result.is_some() {
+        return Err(syn::Error::new_spanned(
+          attr,
+          "fixture attribute can only be specified once per field",
+        ));
+      }
+      result = Some(attr.parse_args()?);
+    }
+    Ok(result)
+  }
+
+  /// Returns the trait that the field type must implement to be initialized
+  /// this way, if any.
+  fn bound(&self) -> Option<Path> {
+    match self {
+      FieldInit::Default => Some(parse_quote!(::std::default::Default)),
+      FieldInit::Fixture => Some(parse_quote!(::neotest_common::Fixture)),
+      FieldInit::Expr(_) | FieldInit::With(_) => None,
+    }
+  }
+}
+
+/// The fields of a derived fixture, which are each initialized individually.
+struct FixtureFields<'a> {
+  fields: &'a Fields,
+  inits: Vec<(&'a Type, FieldInit)>,
+}
+
+impl<'a> FixtureFields<'a> {
+  /// Creates the [`FixtureFields`] for the data of a derived fixture.
+  ///
+  /// Returns [`None`] if no field has a `#[fixture(...)]` attribute.
+  ///
+  /// # Arguments
+  ///
+  /// * `data` - the data of the derived type
+  fn new(data: &'a Data) -> syn::Result<Option<Self>> {
+    let (fields, is_struct) = match data {
+      Data::Struct(data) => (Some(&data.fields), true),
+      _ => (None, false),
+    };
+    let mut inits: Vec<(&'a Type, FieldInit)> = Vec::new();
+    let mut has_attributes = false;
+    for field in Self::all_fields(data) {
+      let init = FieldInit::from_field(field)?;
+      if init.is_some() && !is_struct {
+        return Err(syn::Error::new_spanned(
+          field,
+          "fixture attributes can only be used on the fields of structs",
+        ));
+      }
+      has_attributes |= init.is_some();
+      inits.push((&field.ty, init.unwrap_or(FieldInit::Default)));
+    }
+
+    match fields {
+      Some(fields) if has_attributes => Ok(Some(Self { fields, inits })),
+      _ => Ok(None),
+    }
+  }
+
+  /// Adds a bound to `generics` for every field type that depends on a generic
+  /// type parameter, so that it can be initialized.
+  ///
+  /// # Arguments
+  ///
+  /// * `generics` - the generics of the fixture to add bounds to
+  fn add_bounds(&self, generics: &mut Generics) {
+    let params: Vec<syn::Ident> = generics
+      .params
+      .iter()
+      .filter_map(|v| match v {
+        GenericParam::Type(v) => Some(v.ident.clone()),
+        _ => None,
+      })
+      .collect();
+
+    for (ty, init) in self.inits.iter() {
+      if let Some(bound) = init.bound() {
+        if contains_any_ident(ty.to_token_stream(), &params) {
+          let predicates = &mut generics.make_where_clause().predicates;
+          predicates.push(parse_quote!(#ty: #bound));
+        }
+      }
+    }
+  }
+
+  /// Returns the implementation of [`Fixture::tear_down`] that tears down
+  /// every nested fixture in the reverse order that they were declared, or
+  /// [`None`] if there are no nested fixtures.
+  ///
+  /// All nested fixtures are torn down, even if some fail to be, where the
+  /// error of the first to fail is returned.
+  ///
+  /// [`Fixture::tear_down`]: neotest_common::Fixture::tear_down
+  fn tear_down(&self) -> Option<TokenStream2> {
+    let nested: Vec<(Member, &Type)> = self.nested_fixtures(self.inits.len()).collect();
+    if nested.is_empty() {
+      return None;
+    }
+    let members = nested.iter().map(|(member, _)| member);
+    let locals = nested.iter().map(|(member, _)| Self::local(member));
+    let tear_downs = nested.iter().rev().map(|(member, ty)| {
+      let local = Self::local(member);
+      quote! {
+        if let Err(e) = <#ty as ::neotest_common::Fixture>::tear_down(#local, outcome) {
+          if result.is_ok() {
+            result = Err(e);
+          }
+        }
+      }
+    });
+
+    Some(quote! {
+      fn tear_down(self, outcome: &::neotest_common::TestOutcome) -> ::neotest_common::Result<()> {
+        let Self { #(#members: #locals,)* .. } = self;
+        let mut result: ::neotest_common::Result<()> = Ok(());
+        #(#tear_downs)*
+        result
+      }
+    })
+  }
+
+  /// Returns the member and type of every nested fixture that is declared
+  /// before the field at `index`.
+  ///
+  /// # Arguments
+  ///
+  /// * `index` - the index of the field to stop at
+  fn nested_fixtures(&self, index: usize) -> impl Iterator<Item = (Member, &Type)> + '_ {
+    self
+      .members()
+      .zip(self.inits.iter())
+      .take(index)
+      .filter(|(_, (_, init))| matches!(init, FieldInit::Fixture))
+      .map(|(member, (ty, _))| (member, *ty))
+  }
+
+  /// Returns the member of every field, in the order that they are declared.
+  fn members(&self) -> impl Iterator<Item = Member> + '_ {
+    self.fields.iter().enumerate().map(|(i, v)| match &v.ident {
+      Some(ident) => Member::Named(ident.clone()),
+      None => Member::Unnamed(Index::from(i)),
+    })
+  }
+
+  /// Returns the name of the local variable that holds the field `member`
+  /// while the fixture is prepared or torn down.
+  ///
+  /// # Arguments
+  ///
+  /// * `member` - the member of the field
+  fn local(member: &Member) -> syn::Ident {
+    match member {
+      Member::Named(ident) => format_ident!("__field_{ident}"),
+      Member::Unnamed(index) => format_ident!("__field_{}", index.index),
+    }
+  }
+
+  fn all_fields(data: &Data) -> Box<dyn Iterator<Item = &Field> + '_> {
+    match data {
+      Data::Struct(data) => Box::new(data.fields.iter()),
+      Data::Enum(data) => Box::new(data.variants.iter().flat_map(|v| v.fields.iter())),
+      Data::Union(data) => Box::new(data.fields.named.iter()),
+    }
+  }
+}
+
+impl<'a> ToTokens for FixtureFields<'a> {
+  /// Prepares each field in the order that they are declared, where a failure
+  /// to prepare one tears down the nested fixtures that were already prepared,
+  /// in the reverse order.
+  fn to_tokens(&self, tokens: &mut TokenStream2) {
+    let members: Vec<Member> = self.members().collect();
+    let locals: Vec<syn::Ident> = members.iter().map(Self::local).collect();
+    let prepares = self.inits.iter().enumerate().map(|(i, (ty, init))| {
+      let local = &locals[i];
+      let fallible = match init {
+        FieldInit::Default => {
+          return quote! { let #local = ::std::default::Default::default(); };
+        }
+        FieldInit::Expr(expr) => return quote! { let #local = #expr; },
+        FieldInit::With(path) => quote! { #path() },
+        FieldInit::Fixture => quote! { <#ty as ::neotest_common::Fixture>::prepare() },
+      };
+      let rollbacks = self
+        .nested_fixtures(i)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .map(|(member, ty)| {
+          let local = Self::local(&member);
+          quote! {
+            if let Err(e) = <#ty as ::neotest_common::Fixture>::tear_down(
+              #local,
+              &::neotest_common::TestOutcome::Failed,
+            ) {
+              ::std::eprintln!(
+                "failed to tear down fixture `{}`: {e}",
+                ::std::any::type_name::<#ty>(),
+              );
+            }
+          }
+        });
+      quote! {
+        let #local = match #fallible {
+          Ok(value) => value,
+          Err(error) => {
+            #(#rollbacks)*
+            return Err(::std::convert::Into::into(error));
+          }
+        };
+      }
+    });
+
+    let value = match self.fields {
+      Fields::Named(_) => quote! { Self { #(#members: #locals),* } },
+      Fields::Unnamed(_) => quote! { Self(#(#locals),*) },
+      Fields::Unit => quote! { Self },
+    };
+    quote! {
+      {
+        #(#prepares)*
+        #value
+      }
+    }
+    .to_tokens(tokens)
+  }
+}
+
+/// Checks whether the token stream contains any of the specified identifiers.
+///
+/// # Arguments
+///
+/// * `tokens` - the tokens to search
+/// * `idents` - the identifiers to search for
+pub(crate) fn contains_any_ident(tokens: TokenStream2, idents: &[syn::Ident]) -> bool {
+  tokens.into_iter().any(|tt| match tt {
+    TokenTree::Ident(ident) => idents.contains(&ident),
+    TokenTree::Group(group) => contains_any_ident(group.stream(), idents),
+    _ => false,
+  })
 }
@@ -3,27 +3,54 @@
 //!
 //! [`neotest`]: crate::neotest
 
+mod cases_from_input;
+mod cases_table;
+mod combinations_input;
 mod const_parameter_input;
+mod exclude_input;
+mod files_input;
 mod fixture_input;
+mod generated_parameter_input;
 mod parameter_input;
+mod property_parameter_input;
 mod subtest_input;
+mod suite_inputs;
 mod test_inputs;
 mod test_option;
 mod type_parameter_input;
+mod zip_input;
 
 // Re-export all submodule contents.
 
+#[doc(inline)]
+pub use cases_from_input::*;
+#[doc(inline)]
+pub use cases_table::*;
+#[doc(inline)]
+pub use combinations_input::*;
 #[doc(inline)]
 pub use const_parameter_input::*;
 #[doc(inline)]
+pub use exclude_input::*;
+#[doc(inline)]
+pub use files_input::*;
+#[doc(inline)]
 pub use fixture_input::*;
 #[doc(inline)]
+pub use generated_parameter_input::*;
+#[doc(inline)]
 pub use parameter_input::*;
 #[doc(inline)]
+pub use property_parameter_input::*;
+#[doc(inline)]
 pub use subtest_input::*;
 #[doc(inline)]
+pub use suite_inputs::*;
+#[doc(inline)]
 pub use test_inputs::*;
 #[doc(inline)]
 pub use test_option::*;
 #[doc(inline)]
 pub use type_parameter_input::*;
+#[doc(inline)]
+pub use zip_input::*;
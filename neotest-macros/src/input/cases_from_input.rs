@@ -0,0 +1,92 @@
+//! This internal-module defines the input of test-cases read from data files.
+use std::path::PathBuf;
+
+use syn::parse::{Parse, ParseStream};
+use syn::{LitStr, Result};
+
+use super::{CasesFormat, CasesTable};
+
+/// A struct containing the path of a file of test-cases, which provides the
+/// inputs of the parameters named by its columns.
+///
+/// The path is relative to the directory of the manifest of the crate being
+/// tested, and must be either a CSV (`.csv`) or a JSON-lines (`.jsonl`) file.
+///
+/// This input is formed from the the `cases_from` argument in the
+/// [`neotest`] attribute:
+///
+/// ```ignore
+/// #[neotest(
+///     /* ... */
+///     cases_from = "tests/data/parse.csv",
+///     /* ... */
+/// )]
+/// fn test_parse(input: &str, expected: u32) { /* ... */ }
+/// ```
+///
+/// [`neotest`]: crate::neotest
+#[derive(Clone)]
+pub struct CasesFromInput {
+  pub path: LitStr,
+}
+
+impl CasesFromInput {
+  /// Returns the absolute path of the file of test-cases
+  pub fn file_path(&self) -> Result<PathBuf> {
+    match std::env::var_os("CARGO_MANIFEST_DIR") {
+      Some(dir) => Ok(PathBuf::from(dir).join(self.path.value())),
+      None => Err(syn::Error::new(
+        self.path.span(),
+        "cases_from requires CARGO_MANIFEST_DIR to be set; build the tests with cargo",
+      )),
+    }
+  }
+
+  /// Reads the table of test-cases from the file.
+  ///
+  /// Errors in the contents of the file name both the file and the line of
+  /// the error.
+  pub fn read(&self) -> Result<CasesTable> {
+    let path = self.path.value();
+    let format = match CasesFormat::from_path(&path) {
+      Some(format) => format,
+      None => {
+        return Err(syn::Error::new(
+          self.path.span(),
+          format!("unsupported cases file '{path}'; expected a `.csv` or `.jsonl` file"),
+        ))
+      }
+    };
+    let text = std::fs::read_to_string(self.file_path()?).map_err(|e| {
+      syn::Error::new(
+        self.path.span(),
+        format!("failed to read cases file '{path}': {e}"),
+      )
+    })?;
+    CasesTable::parse(&text, format).map_err(|e| self.error(e.line, e.message))
+  }
+
+  /// Creates an error for the contents of the file at the specified line.
+  ///
+  /// # Arguments
+  ///
+  /// * `line` - the line of the file that the error is on
+  /// * `message` - the message of the error
+  pub fn error(&self, line: usize, message: impl std::fmt::Display) -> syn::Error {
+    syn::Error::new(
+      self.path.span(),
+      format!("{}:{line}: {message}", self.path.value()),
+    )
+  }
+}
+
+impl Parse for CasesFromInput {
+  /// Parses the input from the parse stream
+  ///
+  /// Expected input is in the form `"<path>"`.
+  fn parse(input: ParseStream) -> Result<Self> {
+    Ok(CasesFromInput {
+      path: input.parse()?,
+    })
+  }
+}
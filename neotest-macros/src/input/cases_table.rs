@@ -0,0 +1,564 @@
+//! This internal-module defines the table of test-cases read from a data file.
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// A table of test-cases, where each row provides a value for every column.
+///
+/// Tables are read from either CSV files, whose first line is a header naming
+/// each column, or from JSON-lines files, where each line is an object whose
+/// keys name the columns:
+///
+/// ```text
+/// input,expected
+/// 12,12
+/// "1,000",1000
+/// ```
+///
+/// ```text
+/// {"input": "12", "expected": 12}
+/// {"input": "1,000", "expected": 1000}
+/// ```
+///
+/// Values are kept as the text that they are written as, so that they can be
+/// parsed as whatever type their column is used as.
+pub struct CasesTable {
+  pub columns: Vec<String>,
+  /// The line that names the columns of the table
+  pub header_line: usize,
+  pub rows: Vec<CasesRow>,
+}
+
+/// A single row of a [`CasesTable`].
+pub struct CasesRow {
+  /// The line of the file that the row is written on
+  pub line: usize,
+  pub values: Vec<String>,
+}
+
+/// An error in the contents of a cases file, along with the line it was found
+/// on.
+pub struct CasesError {
+  pub line: usize,
+  pub message: String,
+}
+
+impl CasesError {
+  fn new(line: usize, message: impl Into<String>) -> Self {
+    Self {
+      line,
+      message: message.into(),
+    }
+  }
+}
+
+/// The format of a file of test-cases.
+#[derive(Clone, Copy)]
+pub enum CasesFormat {
+  Csv,
+  JsonLines,
+}
+
+impl CasesFormat {
+  /// Determines the format of a file from the extension of its `path`, if it
+  /// is a supported format.
+  ///
+  /// # Arguments
+  ///
+  /// * `path` - the path of the file
+  pub fn from_path(path: &str) -> Option<Self> {
+    match path.rsplit_once('.').map(|(_, extension)| extension) {
+      Some("csv") => Some(Self::Csv),
+      Some("jsonl" | "ndjson") => Some(Self::JsonLines),
+      _ => None,
+    }
+  }
+}
+
+impl CasesTable {
+  /// Parses a [`CasesTable`] from the `text` of a file in the specified
+  /// `format`.
+  ///
+  /// Blank lines are skipped in either format.
+  ///
+  /// # Arguments
+  ///
+  /// * `text` - the contents of the file
+  /// * `format` - the format of the file
+  pub fn parse(text: &str, format: CasesFormat) -> Result<Self, CasesError> {
+    let lines = text
+      .lines()
+      .enumerate()
+      .map(|(i, line)| (i + 1, line))
+      .filter(|(_, line)| !line.trim().is_empty());
+
+    let table = match format {
+      CasesFormat::Csv => Self::parse_csv(lines)?,
+      CasesFormat::JsonLines => Self::parse_json_lines(lines)?,
+    };
+    if table.rows.is_empty() {
+      return Err(CasesError::new(table.header_line, "file contains no cases"));
+    }
+    Ok(table)
+  }
+
+  fn parse_csv<'a, I>(mut lines: I) -> Result<Self, CasesError>
+  where
+    I: Iterator<Item = (usize, &'a str)>,
+  {
+    let (header_line, header) = match lines.next() {
+      Some(header) => header,
+      None => return Err(CasesError::new(1, "expected a header naming each column")),
+    };
+    let columns = parse_csv_line(header).map_err(|e| CasesError::new(header_line, e))?;
+    for (i, column) in columns.iter().enumerate() {
+      if columns[..i].contains(column) {
+        return Err(CasesError::new(
+          header_line,
+          format!("column '{column}' is named more than once"),
+        ));
+      }
+    }
+
+    let mut rows: Vec<CasesRow> = Vec::new();
+    for (line, text) in lines {
+      let values = parse_csv_line(text).map_err(|e| CasesError::new(line, e))?;
+      if values.len() != columns.len() {
+        return Err(CasesError::new(
+          line,
+          format!(
+            "expected {} fields, but found {}",
+            columns.len(),
+            values.len()
+          ),
+        ));
+      }
+      rows.push(CasesRow { line, values });
+    }
+    Ok(Self {
+      columns,
+      header_line,
+      rows,
+    })
+  }
+
+  /// Parses a table from lines of JSON objects, whose columns are the keys of
+  /// the first object in the order they are written.
+  fn parse_json_lines<'a, I>(lines: I) -> Result<Self, CasesError>
+  where
+    I: Iterator<Item = (usize, &'a str)>,
+  {
+    let mut table = Self {
+      columns: Vec::new(),
+      header_line: 1,
+      rows: Vec::new(),
+    };
+    for (line, text) in lines {
+      let entries = JsonObject::parse(text).map_err(|e| CasesError::new(line, e))?;
+      if table.rows.is_empty() {
+        table.header_line = line;
+        table.columns = entries.iter().map(|(key, _)| key.clone()).collect();
+      }
+
+      let mut values: Vec<String> = Vec::with_capacity(table.columns.len());
+      for column in table.columns.iter() {
+        match entries.iter().find(|(key, _)| key == column) {
+          Some((_, value)) => values.push(value.clone()),
+          None => return Err(CasesError::new(line, format!("missing key '{column}'"))),
+        }
+      }
+      if let Some((key, _)) = entries.iter().find(|(key, _)| !table.columns.contains(key)) {
+        return Err(CasesError::new(
+          line,
+          format!("unexpected key '{key}', which is not in the first case"),
+        ));
+      }
+      table.rows.push(CasesRow { line, values });
+    }
+    Ok(table)
+  }
+}
+
+/// Parses the fields of a single line of a CSV file.
+///
+/// Fields may be quoted to contain commas, where `""` is an escaped quote.
+/// Whitespace surrounding fields is ignored.
+///
+/// # Arguments
+///
+/// * `line` - the line to parse
+fn parse_csv_line(line: &str) -> Result<Vec<String>, String> {
+  let mut fields: Vec<String> = Vec::new();
+  let mut chars = line.chars().peekable();
+  loop {
+    skip_whitespace(&mut chars);
+    let mut field = String::new();
+    if chars.peek() == Some(&'"') {
+      chars.next();
+      loop {
+        match chars.next() {
+          Some('"') if chars.peek() == Some(&'"') => {
+            chars.next();
+            field.push('"');
+          }
+          Some('"') => break,
+          Some(c) => field.push(c),
+          None => return Err("unterminated quoted field".into()),
+        }
+      }
+      skip_whitespace(&mut chars);
+      fields.push(field);
+      match chars.next() {
+        Some(',') => continue,
+        None => return Ok(fields),
+        Some(c) => return Err(format!("unexpected '{c}' after quoted field")),
+      }
+    }
+    loop {
+      match chars.next() {
+        Some(',') => break,
+        Some('"') => return Err("unexpected '\"' within unquoted field".into()),
+        Some(c) => field.push(c),
+        None => {
+          fields.push(field.trim_end().to_string());
+          return Ok(fields);
+        }
+      }
+    }
+    fields.push(field.trim_end().to_string());
+  }
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+  while chars.next_if(|c| c.is_whitespace()).is_some() {}
+}
+
+/// A flat JSON object, whose values are strings, numbers, or booleans.
+///
+/// String values are unescaped, while numbers and booleans are kept as they
+/// are written.
+struct JsonObject;
+
+impl JsonObject {
+  fn parse(text: &str) -> Result<Vec<(String, String)>, String> {
+    let mut chars = text.chars().peekable();
+    let mut entries: Vec<(String, String)> = Vec::new();
+
+    skip_whitespace(&mut chars);
+    Self::expect(&mut chars, '{')?;
+    skip_whitespace(&mut chars);
+    if chars.peek() == Some(&'}') {
+      chars.next();
+    } else {
+      loop {
+        skip_whitespace(&mut chars);
+        let key = Self::parse_string(&mut chars)?;
+        if entries.iter().any(|(v, _)| *v == key) {
+          return Err(format!("key '{key}' is specified more than once"));
+        }
+        skip_whitespace(&mut chars);
+        Self::expect(&mut chars, ':')?;
+        skip_whitespace(&mut chars);
+        let value = Self::parse_value(&mut chars, &key)?;
+        entries.push((key, value));
+        skip_whitespace(&mut chars);
+        match chars.next() {
+          Some(',') => continue,
+          Some('}') => break,
+          Some(c) => return Err(format!("expected ',' or '}}', but found '{c}'")),
+          None => return Err("expected ',' or '}', but found the end of the line".into()),
+        }
+      }
+    }
+    skip_whitespace(&mut chars);
+    match chars.next() {
+      Some(c) => Err(format!("unexpected '{c}' after the end of the object")),
+      None => Ok(entries),
+    }
+  }
+
+  fn parse_value(chars: &mut Peekable<Chars>, key: &str) -> Result<String, String> {
+    match chars.peek() {
+      Some('"') => Self::parse_string(chars),
+      Some(c) if *c == '-' || c.is_ascii_digit() => {
+        let mut value = String::new();
+        while let Some(c) = chars.next_if(|c| matches!(c, '0'..='9' | '-' | '+' | '.' | 'e' | 'E'))
+        {
+          value.push(c);
+        }
+        Ok(value)
+      }
+      Some(c) if c.is_ascii_alphabetic() => {
+        let mut value = String::new();
+        while let Some(c) = chars.next_if(char::is_ascii_alphabetic) {
+          value.push(c);
+        }
+        match &*value {
+          "true" | "false" => Ok(value),
+          _ => Err(format!(
+            "unsupported value '{value}' for key '{key}'; expected a string, number, or boolean"
+          )),
+        }
+      }
+      _ => Err(format!(
+        "unsupported value for key '{key}'; expected a string, number, or boolean"
+      )),
+    }
+  }
+
+  fn parse_string(chars: &mut Peekable<Chars>) -> Result<String, String> {
+    Self::expect(chars, '"')?;
+    let mut value = String::new();
+    loop {
+      let c = match chars.next() {
+        Some('"') => return Ok(value),
+        Some('\\') => match chars.next() {
+          Some('n') => '\n',
+          Some('t') => '\t',
+          Some('r') => '\r',
+          Some('b') => '\u{8}',
+          Some('f') => '\u{c}',
+          Some('u') => {
+            let hex: String = chars.by_ref().take(4).collect();
+            u32::from_str_radix(&hex, 16)
+              .ok()
+              .and_then(char::from_u32)
+              .ok_or_else(|| format!("invalid unicode escape '\\u{hex}'"))?
+          }
+          Some(c @ ('"' | '\\' | '/')) => c,
+          Some(c) => return Err(format!("invalid escape '\\{c}'")),
+          None => return Err("unterminated string".into()),
+        },
+        Some(c) => c,
+        None => return Err("unterminated string".into()),
+      };
+      value.push(c);
+    }
+  }
+
+  fn expect(chars: &mut Peekable<Chars>, expected: char) -> Result<(), String> {
+    match chars.next() {
+      Some(c) if c == expected => Ok(()),
+      Some(c) => Err(format!("expected '{expected}', but found '{c}'")),
+      None => Err(format!(
+        "expected '{expected}', but found the end of the line"
+      )),
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn parse(text: &str, format: CasesFormat) -> CasesTable {
+    match CasesTable::parse(text, format) {
+      Ok(table) => table,
+      Err(e) => panic!("line {}: {}", e.line, e.message),
+    }
+  }
+
+  fn parse_error(text: &str, format: CasesFormat) -> CasesError {
+    match CasesTable::parse(text, format) {
+      Ok(_) => panic!("expected an error parsing {text:?}"),
+      Err(e) => e,
+    }
+  }
+
+  fn values(table: &CasesTable) -> Vec<Vec<&str>> {
+    table
+      .rows
+      .iter()
+      .map(|row| row.values.iter().map(String::as_str).collect())
+      .collect()
+  }
+
+  #[test]
+  fn test_format_from_path() {
+    assert!(matches!(
+      CasesFormat::from_path("a/b.csv"),
+      Some(CasesFormat::Csv)
+    ));
+    assert!(matches!(
+      CasesFormat::from_path("b.jsonl"),
+      Some(CasesFormat::JsonLines)
+    ));
+    assert!(matches!(
+      CasesFormat::from_path("b.ndjson"),
+      Some(CasesFormat::JsonLines)
+    ));
+    assert!(CasesFormat::from_path("b.json").is_none());
+    assert!(CasesFormat::from_path("csv").is_none());
+  }
+
+  #[test]
+  fn test_csv_columns_and_rows() {
+    let table = parse("input,expected\n12,12\nff,255\n", CasesFormat::Csv);
+
+    assert_eq!(table.columns, ["input", "expected"]);
+    assert_eq!(table.header_line, 1);
+    assert_eq!(values(&table), [["12", "12"], ["ff", "255"]]);
+    assert_eq!(table.rows[1].line, 3);
+  }
+
+  #[test]
+  fn test_csv_quoted_fields() {
+    let table = parse("a,b\n\"1,000\", \"x\" \n", CasesFormat::Csv);
+
+    assert_eq!(values(&table), [["1,000", "x"]]);
+  }
+
+  #[test]
+  fn test_csv_doubled_quotes() {
+    let table = parse("a\n\"say \"\"hi\"\"\"\n\"\"\n", CasesFormat::Csv);
+
+    assert_eq!(values(&table), [["say \"hi\""], [""]]);
+  }
+
+  #[test]
+  fn test_csv_trims_unquoted_fields() {
+    let table = parse(" a , b \n 1 ,  2\t\n", CasesFormat::Csv);
+
+    assert_eq!(table.columns, ["a", "b"]);
+    assert_eq!(values(&table), [["1", "2"]]);
+  }
+
+  #[test]
+  fn test_csv_skips_blank_lines() {
+    let table = parse("\n a\n\n1\n   \n2\n\n\n", CasesFormat::Csv);
+
+    assert_eq!(table.header_line, 2);
+    assert_eq!(values(&table), [["1"], ["2"]]);
+    assert_eq!(table.rows[0].line, 4);
+    assert_eq!(table.rows[1].line, 6);
+  }
+
+  #[test]
+  fn test_csv_ragged_rows() {
+    let error = parse_error("a,b\n1,2\n3\n", CasesFormat::Csv);
+    assert_eq!(error.line, 3);
+    assert_eq!(error.message, "expected 2 fields, but found 1");
+
+    let error = parse_error("a,b\n1,2,3\n", CasesFormat::Csv);
+    assert_eq!(error.line, 2);
+    assert_eq!(error.message, "expected 2 fields, but found 3");
+  }
+
+  #[test]
+  fn test_csv_malformed_quotes() {
+    let error = parse_error("a\n\"1\n", CasesFormat::Csv);
+    assert_eq!(error.line, 2);
+    assert_eq!(error.message, "unterminated quoted field");
+
+    let error = parse_error("a\n\"1\"2\n", CasesFormat::Csv);
+    assert_eq!(error.message, "unexpected '2' after quoted field");
+
+    let error = parse_error("a\n1\"2\"\n", CasesFormat::Csv);
+    assert_eq!(error.message, "unexpected '\"' within unquoted field");
+  }
+
+  #[test]
+  fn test_csv_repeated_column() {
+    let error = parse_error("a,b,a\n1,2,3\n", CasesFormat::Csv);
+
+    assert_eq!(error.line, 1);
+    assert_eq!(error.message, "column 'a' is named more than once");
+  }
+
+  #[test]
+  fn test_empty_files() {
+    let error = parse_error("\n\n", CasesFormat::Csv);
+    assert_eq!(error.message, "expected a header naming each column");
+
+    let error = parse_error("\na,b\n\n", CasesFormat::Csv);
+    assert_eq!(error.line, 2);
+    assert_eq!(error.message, "file contains no cases");
+
+    let error = parse_error("", CasesFormat::JsonLines);
+    assert_eq!(error.message, "file contains no cases");
+  }
+
+  #[test]
+  fn test_json_lines_columns_and_rows() {
+    let text = "\n{\"name\": \"zero\", \"value\": 0, \"ok\": true}\n\n\
+                {\"value\": -1.5e3, \"ok\": false, \"name\": \"\"}\n";
+    let table = parse(text, CasesFormat::JsonLines);
+
+    assert_eq!(table.columns, ["name", "value", "ok"]);
+    assert_eq!(table.header_line, 2);
+    assert_eq!(
+      values(&table),
+      [["zero", "0", "true"], ["", "-1.5e3", "false"]]
+    );
+    assert_eq!(table.rows[1].line, 4);
+  }
+
+  #[test]
+  fn test_json_lines_escapes() {
+    let text = r#"{"a": "\"q\" \\ \/ \n\t\r\b\f \u0041\u00e9"}"#;
+    let table = parse(text, CasesFormat::JsonLines);
+
+    assert_eq!(values(&table), [["\"q\" \\ / \n\t\r\u{8}\u{c} A\u{e9}"]]);
+  }
+
+  #[test]
+  fn test_json_lines_invalid_escapes() {
+    let error = parse_error(r#"{"a": "\x"}"#, CasesFormat::JsonLines);
+    assert_eq!(error.message, "invalid escape '\\x'");
+
+    let error = parse_error(r#"{"a": "\u00g1"}"#, CasesFormat::JsonLines);
+    assert_eq!(error.message, "invalid unicode escape '\\u00g1'");
+  }
+
+  #[test]
+  fn test_json_lines_mismatched_keys() {
+    let error = parse_error("{\"a\": 1, \"b\": 2}\n{\"a\": 1}\n", CasesFormat::JsonLines);
+    assert_eq!(error.line, 2);
+    assert_eq!(error.message, "missing key 'b'");
+
+    let error = parse_error("{\"a\": 1}\n{\"a\": 1, \"c\": 2}\n", CasesFormat::JsonLines);
+    assert_eq!(error.line, 2);
+    assert_eq!(
+      error.message,
+      "unexpected key 'c', which is not in the first case"
+    );
+
+    let error = parse_error("{\"a\": 1, \"a\": 2}\n", CasesFormat::JsonLines);
+    assert_eq!(error.message, "key 'a' is specified more than once");
+  }
+
+  #[test]
+  fn test_json_lines_malformed() {
+    let cases = [
+      ("[1]", "expected '{', but found '['"),
+      (
+        "{\"a\": 1",
+        "expected ',' or '}', but found the end of the line",
+      ),
+      ("{\"a\" 1}", "expected ':', but found '1'"),
+      ("{\"a\": 1;}", "expected ',' or '}', but found ';'"),
+      ("{\"a\": \"1}", "unterminated string"),
+      ("{\"a\": 1} x", "unexpected 'x' after the end of the object"),
+      ("{a: 1}", "expected '\"', but found 'a'"),
+    ];
+    for (text, message) in cases {
+      let error = parse_error(text, CasesFormat::JsonLines);
+      assert_eq!(error.line, 1, "{text}");
+      assert_eq!(error.message, message, "{text}");
+    }
+  }
+
+  #[test]
+  fn test_json_lines_unsupported_values() {
+    let error = parse_error("{\"a\": null}", CasesFormat::JsonLines);
+    assert_eq!(
+      error.message,
+      "unsupported value 'null' for key 'a'; expected a string, number, or boolean"
+    );
+
+    let error = parse_error("{\"a\": [1]}", CasesFormat::JsonLines);
+    assert_eq!(
+      error.message,
+      "unsupported value for key 'a'; expected a string, number, or boolean"
+    );
+  }
+}
@@ -0,0 +1,67 @@
+//! This internal-module defines how the combinations of parameter inputs are
+//! generated.
+use syn::parse::{Parse, ParseStream};
+use syn::Result;
+
+/// The combinations of parameter inputs that a test is run with.
+///
+/// This input is formed from either the `combinations` or the `n_wise`
+/// argument in the [`neotest`] attribute:
+///
+/// ```ignore
+/// #[neotest(
+///     /* ... */
+///     combinations = pairwise,
+///     /* ... */
+/// )]
+/// fn test_value(a: u32, b: u32, c: u32) { /* ... */ }
+/// ```
+///
+/// [`neotest`]: crate::neotest
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum Combinations {
+  /// Every combination of parameter inputs is tested
+  #[default]
+  All,
+
+  /// Only enough combinations are tested for every combination of inputs of
+  /// each `n` parameters to be tested at least once
+  NWise(usize),
+}
+
+impl Combinations {
+  /// Parses the value of the `n_wise` argument from the parse stream.
+  ///
+  /// Expected input is a positive integer.
+  ///
+  /// # Example
+  ///
+  /// ```ignore
+  /// 3
+  /// ```
+  pub fn parse_n_wise(input: ParseStream) -> Result<Self> {
+    let n: syn::LitInt = input.parse()?;
+    match n.base10_parse::<usize>()? {
+      0 => Err(syn::Error::new(n.span(), "n_wise must be at least 1")),
+      n => Ok(Combinations::NWise(n)),
+    }
+  }
+}
+
+impl Parse for Combinations {
+  /// Parses the value of the `combinations` argument from the parse stream.
+  ///
+  /// Expected input is either `all` or `pairwise`.
+  fn parse(input: ParseStream) -> Result<Self> {
+    let option: syn::Ident = input.parse()?;
+    let option_str = option.to_string();
+    match &*option_str {
+      "all" => Ok(Combinations::All),
+      "pairwise" => Ok(Combinations::NWise(2)),
+      _ => Err(syn::Error::new(
+        option.span(),
+        format!("unknown combinations '{option_str}'; expected `all` or `pairwise`"),
+      )),
+    }
+  }
+}
@@ -22,7 +22,6 @@ use super::ParameterInput;
 /// ```
 ///
 /// [`neotest`]: crate::neotest
-#[allow(dead_code)]
 #[derive(Clone)]
 pub struct ConstParameterInput {
   pub ident: syn::Ident,
@@ -40,6 +39,13 @@ impl Parse for ConstParameterInput {
   /// V as [0xdeadbeef, 0xbadf00d, 0xc0ffee]
   /// ```
   fn parse(input: ParseStream) -> Result<Self> {
+    if input.peek(syn::Lifetime) {
+      let lifetime: syn::Lifetime = input.parse()?;
+      return Err(syn::Error::new(
+        lifetime.span(),
+        "lifetime generics cannot be used as test inputs",
+      ));
+    }
     let result: ParameterInput = input.parse()?;
 
     Ok(ConstParameterInput {
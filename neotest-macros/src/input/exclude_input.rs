@@ -0,0 +1,97 @@
+//! This internal-module defines the predicate that excludes combinations of
+//! parameter inputs.
+use syn::parse::{Parse, ParseStream};
+use syn::{Expr, ExprClosure, Pat, Result};
+
+/// A struct containing a closure that decides whether a combination of
+/// parameter inputs is excluded from being tested.
+///
+/// Each argument of the closure names a parameter of the test, and is given a
+/// reference to the input of that parameter. Combinations for which the
+/// closure returns `true` are skipped, and reported as filtered.
+///
+/// This input is formed from the `exclude` argument in the [`neotest`]
+/// attribute:
+///
+/// ```ignore
+/// #[neotest(
+///     /* ... */
+///     exclude = |a, b| a == b,
+///     /* ... */
+/// )]
+/// fn test_value(a: u32, b: u32) { /* ... */ }
+/// ```
+///
+/// [`neotest`]: crate::neotest
+#[derive(Clone)]
+pub struct ExcludeInput {
+  pub closure: ExprClosure,
+}
+
+impl ExcludeInput {
+  /// Returns each argument of the closure, along with the parameter that it
+  /// names.
+  pub fn args(&self) -> impl Iterator<Item = (&Pat, &syn::Ident)> {
+    self
+      .closure
+      .inputs
+      .iter()
+      .map(|pat| (pat, Self::pat_ident(pat).unwrap()))
+  }
+
+  /// Returns the body of the closure
+  pub fn body(&self) -> &Expr {
+    &self.closure.body
+  }
+
+  /// Returns the identifier bound by an argument pattern of the closure, such
+  /// as `a`, `&a`, or `a: &u32`.
+  ///
+  /// # Arguments
+  ///
+  /// * `pat` - the pattern of the argument
+  fn pat_ident(pat: &Pat) -> Option<&syn::Ident> {
+    match pat {
+      Pat::Ident(pat) if pat.subpat.is_none() => Some(&pat.ident),
+      Pat::Reference(pat) => Self::pat_ident(&pat.pat),
+      Pat::Type(pat) => Self::pat_ident(&pat.pat),
+      _ => None,
+    }
+  }
+}
+
+impl Parse for ExcludeInput {
+  /// Parses the input from the parse stream
+  ///
+  /// Expected input is a closure, whose arguments name parameters of the test.
+  ///
+  /// # Example
+  ///
+  /// ```ignore
+  /// |a, b| *a > *b
+  /// ```
+  fn parse(input: ParseStream) -> Result<Self> {
+    let closure = match input.parse::<Expr>()? {
+      Expr::Closure(closure) => closure,
+      expr => {
+        return Err(syn::Error::new_spanned(
+          expr,
+          "exclude must be a closure whose arguments name parameters, such as `|a, b| a == b`",
+        ))
+      }
+    };
+    if let Some(asyncness) = &closure.asyncness {
+      return Err(syn::Error::new_spanned(
+        asyncness,
+        "exclude cannot be an async closure",
+      ));
+    }
+    if let Some(pat) = closure.inputs.iter().find(|v| Self::pat_ident(v).is_none()) {
+      return Err(syn::Error::new_spanned(
+        pat,
+        "arguments of exclude must each name a single parameter, such as `a` or `&a`",
+      ));
+    }
+    Ok(ExcludeInput { closure })
+  }
+}
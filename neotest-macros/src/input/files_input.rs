@@ -0,0 +1,376 @@
+//! This internal-module defines the set of parameter inputs formed from files.
+use std::path::{Path, PathBuf};
+
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_quote, Expr, LitStr, Result};
+
+use crate::common::ident;
+
+/// A struct containing a glob pattern of files, which form the inputs of a
+/// parameter.
+///
+/// The pattern is relative to the directory of the manifest of the crate being
+/// tested, and may use `*` and `?` to match within a single component of a
+/// path, or `**` to match any number of directories.
+///
+/// This input is formed from the `files` or `file_contents` inputs of the
+/// `parameter` argument in the [`neotest`] attribute:
+///
+/// ```ignore
+/// #[neotest(
+///     /* ... */
+///     parameter = path as files("tests/corpus/**/*.input"),
+///     parameter = contents as file_contents("tests/corpus/*.input"),
+///     /* ... */
+/// )]
+/// fn test_value(path: &Path, contents: &str) { /* ... */ }
+/// ```
+///
+/// [`neotest`]: crate::neotest
+pub struct FilesInput {
+  kind: FilesKind,
+  pattern: LitStr,
+}
+
+/// What each file of a [`FilesInput`] provides to its parameter.
+#[derive(Clone, Copy)]
+enum FilesKind {
+  /// The path of the file, as a `&'static Path`
+  Path,
+  /// The contents of the file, as a `&'static str`
+  Contents,
+}
+
+impl FilesInput {
+  /// Tests whether the next input in the parse stream is a [`FilesInput`]
+  ///
+  /// # Arguments
+  ///
+  /// * `input` - the stream to parse from
+  pub fn peek(input: ParseStream) -> bool {
+    input
+      .fork()
+      .parse::<syn::Ident>()
+      .map(|v| v == "files" || v == "file_contents")
+      .unwrap_or(false)
+  }
+
+  /// Expands the pattern into the input of each matching file, along with the
+  /// label that names the test-cases of each file.
+  ///
+  /// Files are labelled by their path relative to the directory that the
+  /// pattern starts matching from, without the extension -- e.g. the file
+  /// `tests/corpus/json/empty.input` is labelled `json_empty` when matched by
+  /// `tests/corpus/**/*.input`. Each file is also tracked by the compiler, so
+  /// that the test is rebuilt whenever it changes.
+  pub fn expand(&self) -> Result<Vec<(String, Expr)>> {
+    let dir = match std::env::var_os("CARGO_MANIFEST_DIR") {
+      Some(dir) => PathBuf::from(dir),
+      None => {
+        return Err(syn::Error::new(
+          self.pattern.span(),
+          "files requires CARGO_MANIFEST_DIR to be set; build the tests with cargo",
+        ))
+      }
+    };
+    let pattern = self.pattern.value();
+    let components: Vec<&str> = pattern.split('/').filter(|v| !v.is_empty()).collect();
+    let literal = components
+      .iter()
+      .take_while(|v| !v.contains(['*', '?']))
+      .count();
+    if literal == components.len() {
+      return Err(syn::Error::new(
+        self.pattern.span(),
+        format!("files pattern '{pattern}' does not contain a wildcard; use `*`, `?`, or `**`"),
+      ));
+    }
+
+    let base = components[..literal]
+      .iter()
+      .fold(dir, |path, component| path.join(component));
+    let mut files: Vec<PathBuf> = Vec::new();
+    find_files(&base, &components[literal..], &mut files);
+    files.sort();
+    files.dedup();
+    if files.is_empty() {
+      return Err(syn::Error::new(
+        self.pattern.span(),
+        format!("files pattern '{pattern}' did not match any files"),
+      ));
+    }
+
+    Ok(
+      files
+        .iter()
+        .map(|file| (Self::label(&base, file), self.expr(file)))
+        .collect(),
+    )
+  }
+
+  /// Returns the label of a file matched from the `base` directory
+  fn label(base: &Path, file: &Path) -> String {
+    let relative = file.strip_prefix(base).unwrap_or(file).with_extension("");
+    let label = ident::sanitize(&relative.to_string_lossy());
+    if label.is_empty() {
+      "file".into()
+    } else {
+      label
+    }
+  }
+
+  /// Returns the input expression of a file
+  fn expr(&self, file: &Path) -> Expr {
+    let file = file.to_string_lossy();
+    match self.kind {
+      FilesKind::Path => parse_quote! {
+        {
+          const _: &[u8] = ::std::include_bytes!(#file);
+          ::std::path::Path::new(#file)
+        }
+      },
+      FilesKind::Contents => parse_quote!(::std::include_str!(#file)),
+    }
+  }
+}
+
+impl Parse for FilesInput {
+  /// Parses the input from the parse stream
+  ///
+  /// Expected input is in the form `files("<pattern>")` or
+  /// `file_contents("<pattern>")`.
+  fn parse(input: ParseStream) -> Result<Self> {
+    let function: syn::Ident = input.parse()?;
+    let kind = match &*function.to_string() {
+      "files" => FilesKind::Path,
+      "file_contents" => FilesKind::Contents,
+      _ => {
+        return Err(syn::Error::new(
+          function.span(),
+          "expected either `files(\"<pattern>\")` or `file_contents(\"<pattern>\")`",
+        ))
+      }
+    };
+    let content;
+    syn::parenthesized!(content in input);
+    let pattern: LitStr = content.parse()?;
+
+    Ok(FilesInput { kind, pattern })
+  }
+}
+
+/// Recursively finds each file within `dir` that matches the remaining
+/// `components` of a pattern.
+///
+/// Symbolic links to directories are not followed, so that a link to a parent
+/// directory cannot make `**` recurse forever.
+///
+/// # Arguments
+///
+/// * `dir` - the directory to search
+/// * `components` - the remaining components of the pattern
+/// * `result` - the files found so far
+fn find_files(dir: &Path, components: &[&str], result: &mut Vec<PathBuf>) {
+  let (component, rest) = match components.split_first() {
+    Some(v) => v,
+    None => return,
+  };
+  let mut entries: Vec<PathBuf> = match std::fs::read_dir(dir) {
+    Ok(entries) => entries.filter_map(|v| v.ok()).map(|v| v.path()).collect(),
+    Err(_) => return,
+  };
+  entries.sort();
+
+  if *component == "**" {
+    // `**` matches this directory, as well as any directory within it
+    find_files(dir, rest, result);
+    for entry in entries.iter().filter(|v| is_walkable_dir(v)) {
+      find_files(entry, components, result);
+    }
+    return;
+  }
+  for entry in entries.iter() {
+    let name = match entry.file_name() {
+      Some(name) => name.to_string_lossy(),
+      None => continue,
+    };
+    if !matches_component(component, &name) {
+      continue;
+    }
+    if rest.is_empty() {
+      if entry.is_file() {
+        result.push(entry.clone());
+      }
+    } else if is_walkable_dir(entry) {
+      find_files(entry, rest, result);
+    }
+  }
+}
+
+/// Tests whether the path is a directory, rather than a symbolic link to one.
+///
+/// # Arguments
+///
+/// * `path` - the path to test
+fn is_walkable_dir(path: &Path) -> bool {
+  path.symlink_metadata().map(|v| v.is_dir()).unwrap_or(false)
+}
+
+/// Tests whether the name of a file matches a single component of a pattern,
+/// where `*` matches any sequence of characters and `?` matches any single
+/// character.
+///
+/// # Arguments
+///
+/// * `pattern` - the component of the pattern
+/// * `name` - the name of the file
+fn matches_component(pattern: &str, name: &str) -> bool {
+  let pattern: Vec<char> = pattern.chars().collect();
+  let name: Vec<char> = name.chars().collect();
+
+  // The positions after the last `*`, to backtrack to on a mismatch
+  let mut star: Option<(usize, usize)> = None;
+  let (mut p, mut n) = (0, 0);
+  while n < name.len() {
+    match pattern.get(p) {
+      Some('*') => {
+        star = Some((p + 1, n));
+        p += 1;
+      }
+      Some(c) if *c == '?' || *c == name[n] => {
+        p += 1;
+        n += 1;
+      }
+      _ => match star {
+        Some((star_p, star_n)) => {
+          p = star_p;
+          n = star_n + 1;
+          star = Some((star_p, star_n + 1));
+        }
+        None => return false,
+      },
+    }
+  }
+  pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  /// A temporary directory of files, which is removed when dropped.
+  struct TempDir(PathBuf);
+
+  impl TempDir {
+    fn new(name: &str, files: &[&str]) -> Self {
+      let dir = std::env::temp_dir().join(format!("neotest-{}-{name}", std::process::id()));
+      let _ = std::fs::remove_dir_all(&dir);
+      for file in files {
+        let path = dir.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, "").unwrap();
+      }
+      Self(dir)
+    }
+
+    fn find(&self, pattern: &str) -> Vec<String> {
+      let components: Vec<&str> = pattern.split('/').collect();
+      let mut files: Vec<PathBuf> = Vec::new();
+      find_files(&self.0, &components, &mut files);
+      files
+        .iter()
+        .map(|v| {
+          v.strip_prefix(&self.0)
+            .unwrap()
+            .to_string_lossy()
+            .replace('\\', "/")
+        })
+        .collect()
+    }
+  }
+
+  impl Drop for TempDir {
+    fn drop(&mut self) {
+      let _ = std::fs::remove_dir_all(&self.0);
+    }
+  }
+
+  #[test]
+  fn test_matches_star() {
+    assert!(matches_component("*", "a.input"));
+    assert!(matches_component("*", ""));
+    assert!(matches_component("*.input", "a.input"));
+    assert!(matches_component("*.input", ".input"));
+    assert!(matches_component("a*b*c", "aXbYbZc"));
+    assert!(matches_component("**", "a"));
+    assert!(!matches_component("*.input", "a.output"));
+    assert!(!matches_component("*.input", "a.input.bak"));
+    assert!(!matches_component("a*b", "ac"));
+  }
+
+  #[test]
+  fn test_matches_question_mark() {
+    assert!(matches_component("?", "a"));
+    assert!(matches_component("a?c", "abc"));
+    assert!(matches_component("?*", "abc"));
+    assert!(!matches_component("?", ""));
+    assert!(!matches_component("?", "ab"));
+    assert!(!matches_component("a?c", "ac"));
+  }
+
+  #[test]
+  fn test_matches_literal() {
+    assert!(matches_component("a.input", "a.input"));
+    assert!(matches_component("", ""));
+    assert!(!matches_component("a.input", "b.input"));
+    assert!(!matches_component("a", "A"));
+  }
+
+  #[test]
+  fn test_find_files_within_directory() {
+    let dir = TempDir::new("within", &["b.input", "a.input", "c.output", "d/e.input"]);
+
+    assert_eq!(dir.find("*.input"), ["a.input", "b.input"]);
+    assert_eq!(dir.find("?.output"), ["c.output"]);
+    assert_eq!(dir.find("*/*.input"), ["d/e.input"]);
+  }
+
+  #[test]
+  fn test_find_files_recursively() {
+    let dir = TempDir::new(
+      "recursive",
+      &["a.input", "b/c.input", "b/d/e.input", "f/g.output"],
+    );
+
+    assert_eq!(
+      dir.find("**/*.input"),
+      ["a.input", "b/c.input", "b/d/e.input"]
+    );
+    assert_eq!(dir.find("b/**/*.input"), ["b/c.input", "b/d/e.input"]);
+    assert_eq!(dir.find("**/d/*"), ["b/d/e.input"]);
+    assert!(dir.find("**/*.missing").is_empty());
+  }
+
+  #[test]
+  fn test_label() {
+    let base = Path::new("tests/corpus");
+
+    assert_eq!(
+      FilesInput::label(base, &base.join("json/empty.input")),
+      "json_empty"
+    );
+    assert_eq!(FilesInput::label(base, &base.join("a-b.c.input")), "a_b_c");
+    assert_eq!(FilesInput::label(base, &base.join("-.input")), "file");
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn test_find_files_skips_symlinked_directories() {
+    let dir = TempDir::new("symlinks", &["a/b.input"]);
+    std::os::unix::fs::symlink(&dir.0, dir.0.join("a/parent")).unwrap();
+    std::os::unix::fs::symlink(dir.0.join("a"), dir.0.join("c")).unwrap();
+
+    assert_eq!(dir.find("**/*.input"), ["a/b.input"]);
+    assert_eq!(dir.find("*/*.input"), ["a/b.input"]);
+  }
+}
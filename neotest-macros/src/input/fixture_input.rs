@@ -1,41 +1,191 @@
 //! This internal-module defines a data-representation of the fixture input.
-use syn::parse::ParseStream;
-use syn::Result;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::token::{Bracket, Comma, Eq, Paren};
+use syn::{PathArguments, Result, Type};
 
 /// A struct containing fixture input for a test.
 ///
-/// `FixtureInput` names just the type name of a fixture test.
+/// `FixtureInput` names the type of a fixture of the test, which may be any
+/// path -- including generic arguments, such as `support::Harness<T>`. The
+/// type may be followed by the names of test parameters to prepare it from,
+/// such as `Database(backend)`.
 ///
-/// This input is formed from the the `const_parameter` argument in the
-/// [`neotest`] attribute:
+/// This input is formed from the the `fixture` argument in the [`neotest`]
+/// attribute, which may either name a single fixture or a list of fixtures:
 ///
 /// ```ignore
 /// #[neotest(
 ///   /* ... */
-///   fixture = Fixture,
+///   fixture = [Database, TempDir],
+///   fixture(scope = suite) = Index,
+///   fixture = Connection(backend),
 ///   /* ... */
 /// )]
-/// fn test_value(f: &Fixture) { /* ... */ }
+/// fn test_value(
+///   db: &Database,
+///   dir: &mut TempDir,
+///   index: &Index,
+///   conn: &Connection,
+///   backend: Backend,
+/// ) { /* ... */ }
 /// ```
 ///
 /// [`neotest`]: crate::neotest
 #[derive(Clone)]
 pub struct FixtureInput {
-  pub ident: syn::Ident,
+  pub ty: syn::Type,
+  pub scope: FixtureScope,
+  pub params: Vec<syn::Ident>,
 }
 
-impl syn::parse::Parse for FixtureInput {
-  /// Parses the fixture name from the parse stream
+/// The scope that a fixture is prepared for.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum FixtureScope {
+  /// The fixture is prepared separately for every test-case
+  #[default]
+  Test,
+
+  /// The fixture is prepared once, and shared by every test-case in the test
+  /// binary
+  Suite,
+
+  /// The fixture is prepared once, and shared by every test-case in the
+  /// [`neotest_suite`] module -- being torn down after its last test.
   ///
-  /// Expected input is in the form of just `<ident>`.
+  /// [`neotest_suite`]: crate::neotest_suite
+  Module,
+}
+
+impl FixtureScope {
+  /// Parses the optional scope of a fixture from the parse stream.
+  ///
+  /// Expected input is either empty, or in the form `(scope = <scope>)` where
+  /// `<scope>` is one of `test`, `suite`, or `module`.
   ///
   /// # Example
   ///
   /// ```ignore
-  /// TestFixture
+  /// (scope = suite)
+  /// ```
+  pub fn parse_optional(input: ParseStream) -> Result<Self> {
+    if !input.peek(Paren) {
+      return Ok(FixtureScope::Test);
+    }
+    let content;
+    syn::parenthesized!(content in input);
+
+    let option: syn::Ident = content.parse()?;
+    if option != "scope" {
+      return Err(syn::Error::new(
+        option.span(),
+        format!("unknown fixture option '{option}'"),
+      ));
+    }
+    content.parse::<Eq>()?;
+
+    let scope: syn::Ident = content.parse()?;
+    let scope_str = scope.to_string();
+    match &*scope_str {
+      "test" => Ok(FixtureScope::Test),
+      "suite" => Ok(FixtureScope::Suite),
+      "module" => Ok(FixtureScope::Module),
+      _ => Err(syn::Error::new(
+        scope.span(),
+        format!("unknown fixture scope '{scope_str}'; expected `test`, `suite`, or `module`"),
+      )),
+    }
+  }
+}
+
+impl Parse for FixtureInput {
+  /// Parses the fixture type from the parse stream
+  ///
+  /// Expected input is in the form of `<type>`, optionally followed by the
+  /// parameters to prepare it from in the form `(<ident>, <ident>, ...)`.
+  ///
+  /// # Example
+  ///
+  /// ```ignore
+  /// crate::support::Harness<T>(backend)
   /// ```
   fn parse(input: ParseStream) -> Result<Self> {
-    let ident: syn::Ident = input.parse()?;
-    Ok(FixtureInput { ident })
+    let mut ty: syn::Type = input.parse()?;
+    let mut params = Self::take_params(&mut ty)?;
+    if input.peek(Paren) {
+      let content;
+      syn::parenthesized!(content in input);
+      let idents: Punctuated<syn::Ident, Comma> = content.parse_terminated(syn::Ident::parse)?;
+      params.extend(idents);
+    }
+    Ok(FixtureInput {
+      ty,
+      scope: FixtureScope::Test,
+      params,
+    })
+  }
+}
+
+impl FixtureInput {
+  /// Parses one or more fixture types from the parse stream
+  ///
+  /// Expected input is in the form of either `<type>`, or
+  /// `[<type>, <type>, ...]`.
+  ///
+  /// # Arguments
+  ///
+  /// * `input` - the stream to parse from
+  /// * `scope` - the scope of the parsed fixtures
+  pub fn parse_many(input: ParseStream, scope: FixtureScope) -> Result<Vec<Self>> {
+    let fixtures: Vec<Self> = if input.peek(Bracket) {
+      let content;
+      syn::bracketed!(content in input);
+      let fixtures: Punctuated<FixtureInput, Comma> = content.parse_terminated(Self::parse)?;
+      fixtures.into_iter().collect()
+    } else {
+      vec![input.parse()?]
+    };
+    Ok(fixtures.into_iter().map(|v| Self { scope, ..v }).collect())
+  }
+
+  /// Removes the parameters from a fixture type without generic arguments.
+  ///
+  /// Since `Database(backend)` is also valid syntax for a function trait type,
+  /// it is parsed as a path with parenthesized arguments -- which are instead
+  /// the names of the parameters.
+  ///
+  /// # Arguments
+  ///
+  /// * `ty` - the parsed fixture type
+  fn take_params(ty: &mut Type) -> Result<Vec<syn::Ident>> {
+    let segment = match ty {
+      Type::Path(path) if path.qself.is_none() => path.path.segments.last_mut(),
+      _ => None,
+    };
+    let segment = match segment {
+      Some(segment) if matches!(segment.arguments, PathArguments::Parenthesized(_)) => segment,
+      _ => return Ok(Vec::new()),
+    };
+    let arguments = match std::mem::take(&mut segment.arguments) {
+      PathArguments::Parenthesized(arguments) => arguments,
+      _ => unreachable!(),
+    };
+    if let syn::ReturnType::Type(..) = arguments.output {
+      return Err(syn::Error::new_spanned(
+        &arguments.output,
+        "expected fixture parameters in the form `(<ident>, ...)`",
+      ));
+    }
+    arguments
+      .inputs
+      .iter()
+      .map(|ty| {
+        let ident = match ty {
+          Type::Path(path) if path.qself.is_none() => path.path.get_ident().cloned(),
+          _ => None,
+        };
+        ident.ok_or_else(|| syn::Error::new_spanned(ty, "expected the name of a test parameter"))
+      })
+      .collect()
   }
 }
@@ -0,0 +1,55 @@
+//! This internal-module defines the set of runtime-generated parameter inputs.
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::{Expr, Result};
+
+/// A struct containing parameter inputs that are generated when the test is
+/// run, rather than being listed in the attribute.
+///
+/// Generated parameter inputs contain the identifier of the parameter being
+/// substituted, along with an expression that evaluates to an [`IntoIterator`]
+/// of every input.
+///
+/// This input is formed from the the `parameter` argument in the
+/// [`neotest`] attribute, when given `from` rather than `as`:
+///
+/// ```ignore
+/// #[neotest(
+///     /* ... */
+///     parameter = preset from Preset::all(),
+///     /* ... */
+/// )]
+/// fn test_value(preset: Preset) { /* ... */ }
+/// ```
+///
+/// [`neotest`]: crate::neotest
+#[derive(Clone)]
+pub struct GeneratedParameterInput {
+  pub ident: syn::Ident,
+  pub source: Expr,
+}
+
+impl Parse for GeneratedParameterInput {
+  /// Parses the input from the parse stream
+  ///
+  /// Expected input is in the form `<ident> from <expr>`.
+  ///
+  /// # Example
+  ///
+  /// ```ignore
+  /// v from 0..10
+  /// ```
+  fn parse(input: ParseStream) -> Result<Self> {
+    let ident: syn::Ident = input.parse()?;
+    let from = syn::Ident::parse_any(input)?;
+    if from != "from" {
+      return Err(syn::Error::new(
+        from.span(),
+        "expected `as [<expr0>, <expr1>, ...]` or `from <expr>`",
+      ));
+    }
+    let source: Expr = input.parse()?;
+
+    Ok(GeneratedParameterInput { ident, source })
+  }
+}
@@ -1,12 +1,19 @@
 //! This internal-module defines the set of parameter inputs.
-use syn::parse::Parse;
-use syn::token::As;
-use syn::ExprArray;
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::token::{As, Colon, Colon2, Comma};
+use syn::{Attribute, Expr, ExprArray};
+
+use super::FilesInput;
 
 /// A struct containing parameter inputs that can be specified for a test.
 ///
 /// Parameter inputs contain the identifier of the parameter being substituted,
-/// along with an array of each possibly valid input.
+/// along with an array of each possibly valid input. Each input may be given a
+/// label, which names the test-cases that use it, and by the attributes to
+/// apply to the test-cases that use it -- which may be any of `#[cfg]`,
+/// `#[ignore]`, or `#[should_panic]`.
 ///
 /// This input is formed from the the `parameter` argument in the
 /// [`neotest`] attribute:
@@ -16,9 +23,11 @@ use syn::ExprArray;
 ///     /* ... */
 ///     parameter = a as [0xdead, 0xbeef, 0xc0ffee],
 ///     parameter = b as ["hello", "world"],
+///     parameter = c as [zero: 0, max: u32::MAX],
+///     parameter = d as [1, #[ignore = "slow"] 2, #[cfg(unix)] 3],
 ///     /* ... */
 /// )]
-/// fn test_value(a: u32, b: &str) { /* ... */ }
+/// fn test_value(a: u32, b: &str, c: u32, d: u32) { /* ... */ }
 /// ```
 ///
 /// [`neotest`]: crate::neotest
@@ -26,23 +35,134 @@ use syn::ExprArray;
 pub struct ParameterInput {
   pub ident: syn::Ident,
   pub inputs: ExprArray,
+  /// The label of each input, which names the test-cases that use it
+  pub labels: Vec<Option<String>>,
+  /// The attributes of each input, which are applied to the test-cases that
+  /// use it
+  pub attrs: Vec<Vec<Attribute>>,
 }
 
 impl Parse for ParameterInput {
   /// Parses the input from the parse stream
   ///
-  /// Expected input is in the form `<ident> as [<expr0>, <expr1>, ...]`.
+  /// Expected input is in the form `<ident> as [<expr0>, <expr1>, ...]`, where
+  /// each expression may be preceded by attributes, and then by a label in the
+  /// form `<label>: `; or in the form `<ident> as files("<pattern>")`, where
+  /// each matching file is an input.
   ///
   /// # Example
   ///
   /// ```ignore
-  /// v as [1, 2, 3]
+  /// v as [1, 2, three: 3, #[ignore] four: 4]
   /// ```
-  fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+  fn parse(input: ParseStream) -> syn::Result<Self> {
     let ident: syn::Ident = input.parse()?;
     input.parse::<As>()?;
-    let inputs: syn::ExprArray = input.parse()?;
 
-    Ok(ParameterInput { ident, inputs })
+    if FilesInput::peek(input) {
+      let files: FilesInput = input.parse()?;
+      let mut inputs = ExprArray {
+        attrs: Vec::new(),
+        bracket_token: Default::default(),
+        elems: Punctuated::new(),
+      };
+      let mut labels: Vec<Option<String>> = Vec::new();
+      for (label, expr) in files.expand()? {
+        inputs.elems.push(expr);
+        labels.push(Some(label));
+      }
+      let attrs = vec![Vec::new(); labels.len()];
+      return Ok(ParameterInput {
+        ident,
+        inputs,
+        labels,
+        attrs,
+      });
+    }
+
+    let content;
+    let bracket_token = syn::bracketed!(content in input);
+    let elems: Punctuated<LabelledExpr, Comma> = content.parse_terminated(LabelledExpr::parse)?;
+
+    let mut labels: Vec<Option<String>> = Vec::with_capacity(elems.len());
+    let mut attrs: Vec<Vec<Attribute>> = Vec::with_capacity(elems.len());
+    let mut inputs = ExprArray {
+      attrs: Vec::new(),
+      bracket_token,
+      elems: Punctuated::new(),
+    };
+    for pair in elems.into_pairs() {
+      let (elem, comma) = pair.into_tuple();
+      let label = elem
+        .label
+        .map(|label| (label.unraw().to_string(), label.span()));
+      if let Some((label, span)) = &label {
+        if labels.iter().flatten().any(|v| v == label) {
+          return Err(syn::Error::new(
+            *span,
+            format!(
+              "Label '{}' is used more than once for parameter '{}'.",
+              label, ident
+            ),
+          ));
+        }
+      }
+      labels.push(label.map(|(label, _)| label));
+      attrs.push(elem.attrs);
+      inputs.elems.push_value(elem.expr);
+      if let Some(comma) = comma {
+        inputs.elems.push_punct(comma);
+      }
+    }
+
+    Ok(ParameterInput {
+      ident,
+      inputs,
+      labels,
+      attrs,
+    })
+  }
+}
+
+/// A single input of a parameter, along with its attributes and optional
+/// label.
+struct LabelledExpr {
+  attrs: Vec<Attribute>,
+  label: Option<syn::Ident>,
+  expr: Expr,
+}
+
+impl LabelledExpr {
+  /// The attributes that may be applied to the test-cases of an input
+  const ATTRIBUTES: [&'static str; 3] = ["cfg", "ignore", "should_panic"];
+}
+
+impl Parse for LabelledExpr {
+  fn parse(input: ParseStream) -> syn::Result<Self> {
+    let attrs = input.call(Attribute::parse_outer)?;
+    for attr in attrs.iter() {
+      let is_allowed = Self::ATTRIBUTES.iter().any(|v| attr.path.is_ident(v));
+      if !is_allowed {
+        return Err(syn::Error::new_spanned(
+          attr,
+          "only `#[cfg]`, `#[ignore]` and `#[should_panic]` can be applied to a parameter input",
+        ));
+      }
+    }
+
+    // Paths such as `u32::MAX` also begin with an ident and a colon
+    let is_labelled = input.peek(syn::Ident) && input.peek2(Colon) && !input.peek2(Colon2);
+    let label = if is_labelled {
+      let label: syn::Ident = input.parse()?;
+      input.parse::<Colon>()?;
+      Some(label)
+    } else {
+      None
+    };
+    Ok(LabelledExpr {
+      attrs,
+      label,
+      expr: input.parse()?,
+    })
   }
 }
@@ -0,0 +1,69 @@
+//! This internal-module defines the set of parameter inputs that are generated
+//! by a strategy.
+use syn::parse::{Parse, ParseStream};
+use syn::token::{As, Bracket};
+use syn::{Expr, Result};
+
+use super::FilesInput;
+
+/// A struct containing parameter inputs that are generated by a strategy, for
+/// tests that check a property against many generated values.
+///
+/// Property parameter inputs contain the identifier of the parameter being
+/// substituted, along with an expression that evaluates to the strategy that
+/// generates its values.
+///
+/// This input is formed from the the `parameter` argument in the
+/// [`neotest`] attribute, when given `as` followed by an expression rather
+/// than a list of inputs:
+///
+/// ```ignore
+/// #[neotest(
+///     /* ... */
+///     parameter = value as any::<u32>(),
+///     /* ... */
+/// )]
+/// fn test_value(value: u32) { /* ... */ }
+/// ```
+///
+/// [`neotest`]: crate::neotest
+#[derive(Clone)]
+pub struct PropertyParameterInput {
+  pub ident: syn::Ident,
+  pub strategy: Expr,
+}
+
+impl PropertyParameterInput {
+  /// Tests whether the next input in the parse stream is a
+  /// [`PropertyParameterInput`], rather than a list of inputs or files.
+  ///
+  /// # Arguments
+  ///
+  /// * `input` - the stream to parse from
+  pub fn peek(input: ParseStream) -> bool {
+    let fork = input.fork();
+    fork.parse::<syn::Ident>().is_ok()
+      && fork.parse::<As>().is_ok()
+      && !fork.peek(Bracket)
+      && !FilesInput::peek(&fork)
+  }
+}
+
+impl Parse for PropertyParameterInput {
+  /// Parses the input from the parse stream
+  ///
+  /// Expected input is in the form `<ident> as <expr>`.
+  ///
+  /// # Example
+  ///
+  /// ```ignore
+  /// v as vec(any::<u8>(), 0..64)
+  /// ```
+  fn parse(input: ParseStream) -> Result<Self> {
+    let ident: syn::Ident = input.parse()?;
+    input.parse::<As>()?;
+    let strategy: Expr = input.parse()?;
+
+    Ok(PropertyParameterInput { ident, strategy })
+  }
+}
@@ -0,0 +1,85 @@
+//! This internal-module defines the inputs of the [`neotest_suite`] attribute.
+//!
+//! [`neotest_suite`]: crate::neotest_suite
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::token::{Comma, Eq};
+use syn::{Meta, Result};
+
+use super::{TestInputs, TestOption};
+
+/// The inputs to a [`neotest_suite`] module, which are applied to every
+/// `#[neotest]` test within the module.
+///
+/// These are formed of the same options as the [`neotest`] attribute, along
+/// with an additional `attributes` option that lists attributes to apply to
+/// every test:
+///
+/// ```ignore
+/// #[neotest_suite(
+///   /* ... */
+///   fixture(scope = module) = Database,
+///   parameter = a as [1, 2, 3],
+///   attributes = [ignore, allow(unused)],
+///   /* ... */
+/// )]
+/// mod tests { /* ... */ }
+/// ```
+///
+/// [`neotest`]: crate::neotest
+/// [`neotest_suite`]: crate::neotest_suite
+pub struct SuiteInputs {
+  pub inputs: TestInputs,
+  pub attributes: Vec<Meta>,
+}
+
+impl Parse for SuiteInputs {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let mut result = SuiteInputs {
+      inputs: syn::parse2(Default::default())?,
+      attributes: Vec::default(),
+    };
+
+    while !input.is_empty() {
+      if Self::peek_attributes(input) {
+        input.parse::<syn::Ident>()?;
+        input.parse::<Eq>()?;
+
+        let content;
+        syn::bracketed!(content in input);
+        let attributes: Punctuated<Meta, Comma> = content.parse_terminated(Meta::parse)?;
+        result.attributes.extend(attributes);
+      } else {
+        let option: TestOption = input.parse()?;
+        if let TestOption::CasesFrom(option) = &option {
+          return Err(syn::Error::new(
+            option.span(),
+            "argument 'cases_from' can only be specified for individual tests",
+          ));
+        }
+
+        result.inputs.push_option(option, input)?;
+      }
+      if !input.is_empty() {
+        input.parse::<Comma>()?;
+      }
+    }
+
+    Ok(result)
+  }
+}
+
+impl SuiteInputs {
+  /// Tests whether the next option in the parse stream is `attributes`
+  ///
+  /// # Arguments
+  ///
+  /// * `input` - the stream to parse from
+  fn peek_attributes(input: ParseStream) -> bool {
+    input
+      .fork()
+      .parse::<syn::Ident>()
+      .map(|v| v == "attributes")
+      .unwrap_or(false)
+  }
+}
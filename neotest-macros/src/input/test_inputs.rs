@@ -1,22 +1,58 @@
 //! This internal-module defines the set of possible test options that can be
 //! specified in attribute parameters.
+use std::time::Duration;
+
 use syn::punctuated::Punctuated;
 use syn::token::Comma;
-use syn::{FnArg, Generics, Signature};
+use syn::{Attribute, FnArg, Generics, Signature};
 
 use crate::syn_utils::TryIdent;
 
-use super::{ConstParameterInput, FixtureInput, ParameterInput, TypeParameterInput};
+use super::{
+  CasesFromInput, Combinations, ConstParameterInput, ExcludeInput, FixtureInput,
+  GeneratedParameterInput, ParameterInput, PropertyParameterInput, TypeParameterInput, ZipInput,
+};
 
 #[derive(Clone)]
 pub struct TestInputs {
-  pub fixture: Option<FixtureInput>,
+  pub fixtures: Vec<FixtureInput>,
   pub parameters: Vec<ParameterInput>,
+  /// The parameters whose inputs are generated when the test is run
+  pub generated_parameters: Vec<GeneratedParameterInput>,
+  /// The parameters whose inputs are generated by a strategy
+  pub property_parameters: Vec<PropertyParameterInput>,
   pub const_parameters: Vec<ConstParameterInput>,
   pub type_parameters: Vec<TypeParameterInput>,
+  pub zips: Vec<ZipInput>,
+  /// The file of test-cases that provides the inputs of parameters, if any
+  pub cases_from: Option<CasesFromInput>,
+  /// The combinations of parameter inputs to test, if specified
+  pub combinations: Option<Combinations>,
+  /// The predicate that excludes combinations of parameter inputs, if any
+  pub exclude: Option<ExcludeInput>,
+  /// The attribute of the runtime that runs an `async fn` test, such as
+  /// `tokio::main(flavor = "current_thread")`
+  pub executor: Option<syn::Meta>,
+  /// The timeout of each test-case
+  pub timeout: Option<Duration>,
+  /// The number of cases that a test with property parameters is run with
+  pub cases: Option<syn::LitInt>,
 }
 
 impl TestInputs {
+  /// Parses the [`TestInputs`] from the arguments of a `#[neotest]` attribute
+  ///
+  /// # Arguments
+  ///
+  /// * `attr` - the attribute to parse
+  pub fn from_attribute(attr: &Attribute) -> syn::Result<Self> {
+    if attr.tokens.is_empty() {
+      syn::parse2(Default::default())
+    } else {
+      attr.parse_args()
+    }
+  }
+
   /// Reorders all test inputs to be in the same order as parameters defined in
   /// the [`Signature`].
   ///
@@ -26,7 +62,25 @@ impl TestInputs {
   pub fn reorder(&mut self, sig: &Signature) {
     self.parameters.sort_by(|a, b| {
       Self::index_of_arg(&sig.inputs, &a.ident).cmp(&Self::index_of_arg(&sig.inputs, &b.ident))
-    })
+    });
+    self.generated_parameters.sort_by(|a, b| {
+      Self::index_of_arg(&sig.inputs, &a.ident).cmp(&Self::index_of_arg(&sig.inputs, &b.ident))
+    });
+    self.property_parameters.sort_by(|a, b| {
+      Self::index_of_arg(&sig.inputs, &a.ident).cmp(&Self::index_of_arg(&sig.inputs, &b.ident))
+    });
+  }
+
+  /// Tests whether the specified ident is bound to either a parameter input,
+  /// a generated parameter input, or a property parameter input.
+  ///
+  /// # Arguments
+  ///
+  /// * `ident` - the ident of the test parameter
+  pub fn is_parameter(&self, ident: &syn::Ident) -> bool {
+    self.parameters.iter().any(|v| v.ident == *ident)
+      || self.generated_parameters.iter().any(|v| v.ident == *ident)
+      || self.property_parameters.iter().any(|v| v.ident == *ident)
   }
 
   /// Finds the index of the specified ident in the list of function args
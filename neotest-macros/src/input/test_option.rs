@@ -1,21 +1,31 @@
 //! This internal-module defines an intermediate format used for parsing test
 //! options from the [`crate::neotest`] attribute definition.
+use neotest_common::__internal::__parse_duration;
 use syn::parse::{Parse, ParseStream};
 use syn::token::{Comma, Eq};
 use syn::Result;
 
-use super::TestInputs;
+use super::{Combinations, FixtureInput, FixtureScope, PropertyParameterInput, TestInputs};
 
 /// An option argument that can be specified as part of the [`neotest`] attribute.
 ///
 /// All options contain their respective idents.
 ///
 /// [`neotest`]: crate::neotest
+#[allow(dead_code)]
 pub enum TestOption {
-  Fixture(syn::Ident),
+  Fixture(syn::Ident, FixtureScope),
   Parameter(syn::Ident),
   TypeParameter(syn::Ident),
   ConstParameter(syn::Ident),
+  Executor(syn::Ident),
+  Timeout(syn::Ident),
+  Zip(syn::Ident),
+  Combinations(syn::Ident),
+  NWise(syn::Ident),
+  CasesFrom(syn::Ident),
+  Exclude(syn::Ident),
+  Cases(syn::Ident),
 }
 
 impl Parse for TestOption {
@@ -24,10 +34,25 @@ impl Parse for TestOption {
     let option_str = option.to_string();
 
     match &*option_str {
-      "fixture" => Ok(TestOption::Fixture(option)),
+      "fixture" => Ok(TestOption::Fixture(
+        option,
+        FixtureScope::parse_optional(input)?,
+      )),
       "parameter" => Ok(TestOption::Parameter(option)),
       "type_parameter" => Ok(TestOption::TypeParameter(option)),
       "const_parameter" => Ok(TestOption::ConstParameter(option)),
+      "timeout" => Ok(TestOption::Timeout(option)),
+      "zip" => Ok(TestOption::Zip(option)),
+      "combinations" => Ok(TestOption::Combinations(option)),
+      "n_wise" => Ok(TestOption::NWise(option)),
+      "cases_from" => Ok(TestOption::CasesFrom(option)),
+      "exclude" => Ok(TestOption::Exclude(option)),
+      "cases" => Ok(TestOption::Cases(option)),
+      "executor" if cfg!(feature = "executor") => Ok(TestOption::Executor(option)),
+      "executor" => Err(syn::Error::new(
+        option.span(),
+        "argument 'executor' requires the `executor` feature of neotest",
+      )),
       _ => Err(syn::Error::new(
         option.span(),
         format!("unknown argument '{option_str}'"),
@@ -39,42 +64,164 @@ impl Parse for TestOption {
 impl Parse for TestInputs {
   fn parse(input: ParseStream) -> Result<Self> {
     let mut result = TestInputs {
-      fixture: None,
+      fixtures: Vec::default(),
       parameters: Vec::default(),
+      generated_parameters: Vec::default(),
+      property_parameters: Vec::default(),
       const_parameters: Vec::default(),
       type_parameters: Vec::default(),
+      zips: Vec::default(),
+      cases_from: None,
+      combinations: None,
+      exclude: None,
+      executor: None,
+      timeout: None,
+      cases: None,
     };
 
     while !input.is_empty() {
       let option: TestOption = input.parse()?;
 
-      input.parse::<Eq>()?;
+      result.push_option(option, input)?;
+      if !input.is_empty() {
+        input.parse::<Comma>()?;
+      }
+    }
 
-      match option {
-        TestOption::Fixture(ident) => {
-          if result.fixture.is_some() {
-            return Err(syn::Error::new(
-              ident.span(),
-              "fixture argument can only be specified once",
-            ));
-          }
-          result.fixture = Some(input.parse()?);
+    Ok(result)
+  }
+}
+
+impl TestInputs {
+  /// Parses the value of the specified `option` from the parse stream, and
+  /// adds it to these inputs.
+  ///
+  /// # Arguments
+  ///
+  /// * `option` - the option being parsed
+  /// * `input` - the stream to parse from
+  pub fn push_option(&mut self, option: TestOption, input: ParseStream) -> Result<()> {
+    // Only `zip(...)` takes its value without an `=`
+    if !matches!(option, TestOption::Zip(_)) {
+      input.parse::<Eq>()?;
+    }
+    match option {
+      TestOption::Fixture(_, scope) => {
+        self
+          .fixtures
+          .extend(FixtureInput::parse_many(input, scope)?);
+      }
+      TestOption::Parameter(_) => {
+        if PropertyParameterInput::peek(input) {
+          self.property_parameters.push(input.parse()?);
+        } else if input.peek2(syn::Token![as]) {
+          self.parameters.push(input.parse()?);
+        } else {
+          self.generated_parameters.push(input.parse()?);
+        }
+      }
+      TestOption::TypeParameter(_) => {
+        self.type_parameters.push(input.parse()?);
+      }
+      TestOption::ConstParameter(_) => {
+        self.const_parameters.push(input.parse()?);
+      }
+      TestOption::Executor(option) => {
+        if self.executor.is_some() {
+          return Err(syn::Error::new(
+            option.span(),
+            "argument 'executor' can only be specified once",
+          ));
+        }
+        self.executor = Some(input.parse()?);
+      }
+      TestOption::Zip(_) => {
+        self.zips.push(input.parse()?);
+      }
+      TestOption::Combinations(option) => {
+        let combinations = input.parse()?;
+        self.set_combinations(option, combinations)?;
+      }
+      TestOption::NWise(option) => {
+        let combinations = Combinations::parse_n_wise(input)?;
+        self.set_combinations(option, combinations)?;
+      }
+      TestOption::CasesFrom(option) => {
+        if self.cases_from.is_some() {
+          return Err(syn::Error::new(
+            option.span(),
+            "argument 'cases_from' can only be specified once",
+          ));
         }
-        TestOption::Parameter(_) => {
-          result.parameters.push(input.parse()?);
+        self.cases_from = Some(input.parse()?);
+      }
+      TestOption::Exclude(option) => {
+        if self.exclude.is_some() {
+          return Err(syn::Error::new(
+            option.span(),
+            "argument 'exclude' can only be specified once",
+          ));
         }
-        TestOption::TypeParameter(_) => {
-          result.type_parameters.push(input.parse()?);
+        self.exclude = Some(input.parse()?);
+      }
+      TestOption::Cases(option) => {
+        if self.cases.is_some() {
+          return Err(syn::Error::new(
+            option.span(),
+            "argument 'cases' can only be specified once",
+          ));
         }
-        TestOption::ConstParameter(_) => {
-          result.const_parameters.push(input.parse()?);
+        let cases: syn::LitInt = input.parse()?;
+        if !matches!(cases.base10_parse::<u32>(), Ok(v) if v > 0) {
+          return Err(syn::Error::new(
+            cases.span(),
+            format!("invalid cases '{cases}'; expected a positive number of cases such as 256"),
+          ));
         }
+        self.cases = Some(cases);
       }
-      if !input.is_empty() {
-        input.parse::<Comma>()?;
+      TestOption::Timeout(option) => {
+        if self.timeout.is_some() {
+          return Err(syn::Error::new(
+            option.span(),
+            "argument 'timeout' can only be specified once",
+          ));
+        }
+        let timeout: syn::LitStr = input.parse()?;
+        // A zero timeout could never be met, so it is rejected like any other
+        // invalid timeout
+        match __parse_duration(&timeout.value()) {
+          Some(duration) if !duration.is_zero() => self.timeout = Some(duration),
+          _ => {
+            return Err(syn::Error::new(
+              timeout.span(),
+              format!(
+                "invalid timeout '{}'; expected a duration such as \"5s\" or \"250ms\"",
+                timeout.value()
+              ),
+            ))
+          }
+        }
       }
     }
+    Ok(())
+  }
 
-    Ok(result)
+  /// Sets the combinations of parameter inputs to test, which may only be set
+  /// once.
+  ///
+  /// # Arguments
+  ///
+  /// * `option` - the option that specified the combinations
+  /// * `combinations` - the combinations to test
+  fn set_combinations(&mut self, option: syn::Ident, combinations: Combinations) -> Result<()> {
+    if self.combinations.is_some() {
+      return Err(syn::Error::new(
+        option.span(),
+        "combinations can only be specified once, with either 'combinations' or 'n_wise'",
+      ));
+    }
+    self.combinations = Some(combinations);
+    Ok(())
   }
 }
@@ -24,7 +24,6 @@ use crate::syn_utils::TypeSequence;
 /// ```
 ///
 /// [`neotest`]: crate::neotest
-#[allow(dead_code)]
 #[derive(Clone)]
 pub struct TypeParameterInput {
   pub ident: syn::Ident,
@@ -42,6 +41,13 @@ impl syn::parse::Parse for TypeParameterInput {
   /// T as [u8, String, ::std::vec::Vec]
   /// ```
   fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+    if input.peek(syn::Lifetime) {
+      let lifetime: syn::Lifetime = input.parse()?;
+      return Err(syn::Error::new(
+        lifetime.span(),
+        "lifetime generics cannot be used as test inputs",
+      ));
+    }
     let ident: syn::Ident = input.parse()?;
     input.parse::<As>()?;
     let inputs: TypeSequence = input.parse()?;
@@ -0,0 +1,64 @@
+//! This internal-module defines the set of zipped parameter inputs.
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+
+/// A struct naming parameters whose inputs are paired by position, rather than
+/// being combined with every input of each other.
+///
+/// The i-th test-case of zipped parameters receives the i-th input of each of
+/// the parameters, so all of them must have the same number of inputs.
+///
+/// This input is formed from the `zip` argument in the [`neotest`] attribute:
+///
+/// ```ignore
+/// #[neotest(
+///     /* ... */
+///     parameter = input as ["1", "22", "333"],
+///     parameter = expected as [1, 22, 333],
+///     zip(input, expected),
+///     /* ... */
+/// )]
+/// fn test_value(input: &str, expected: u32) { /* ... */ }
+/// ```
+///
+/// [`neotest`]: crate::neotest
+#[derive(Clone)]
+pub struct ZipInput {
+  pub idents: Punctuated<syn::Ident, Comma>,
+}
+
+impl ZipInput {
+  /// Tests whether the specified parameter is one of the zipped parameters
+  ///
+  /// # Arguments
+  ///
+  /// * `ident` - the name of the parameter
+  pub fn contains(&self, ident: &syn::Ident) -> bool {
+    self.idents.iter().any(|v| v == ident)
+  }
+}
+
+impl Parse for ZipInput {
+  /// Parses the input from the parse stream
+  ///
+  /// Expected input is in the form `(<ident>, <ident>, ...)`.
+  ///
+  /// # Example
+  ///
+  /// ```ignore
+  /// (input, expected)
+  /// ```
+  fn parse(input: ParseStream) -> syn::Result<Self> {
+    let content;
+    let paren = syn::parenthesized!(content in input);
+    let idents: Punctuated<syn::Ident, Comma> = content.parse_terminated(syn::Ident::parse)?;
+    if idents.len() < 2 {
+      return Err(syn::Error::new(
+        paren.span,
+        "zip requires at least two parameters",
+      ));
+    }
+    Ok(ZipInput { idents })
+  }
+}
@@ -1,20 +1,39 @@
 use input::SubtestInput;
+use parameter_set::{ParameterSet, ResolvedSet, SetTarget};
 use proc_macro::TokenStream;
 use quote::ToTokens;
 use syn::parse_macro_input;
 
 pub(crate) mod common;
+pub(crate) mod derive_arbitrary;
 pub(crate) mod derive_fixture;
 pub(crate) mod input;
+pub(crate) mod parameter_set;
 pub(crate) mod suite;
 pub(crate) mod syn_utils;
 
 /// A derive-macro for automatically implementing the [`Fixture`] trait.
 ///
-/// When deriving from [`Fixture`], it's necessary to also implement [`Default`].
-/// It's recommended to do this through the derive-macro the same way; otherwise
-/// it'd be clearer to just implement [`Fixture`] directly and define the `prepare`
-/// function for setup.
+/// By default, deriving from [`Fixture`] requires also implementing
+/// [`Default`], which is used to prepare the fixture. It's recommended to do
+/// this through the derive-macro the same way; otherwise it'd be clearer to
+/// just implement [`Fixture`] directly and define the `prepare` function for
+/// setup.
+///
+/// Alternatively, each field can be initialized individually through the
+/// `#[fixture(...)]` attribute, which takes one of:
+///
+/// * `#[fixture(default = <expr>)]`, which initializes the field with `<expr>`
+/// * `#[fixture(with = <path>)]`, which initializes the field by calling the
+///   function at `<path>`. This function must return a [`Result`], and any
+///   error fails the preparation of the fixture.
+/// * `#[fixture(fixture)]`, which prepares the field as a nested [`Fixture`]
+///
+/// When any field has a `#[fixture(...)]` attribute, all remaining fields are
+/// initialized with [`Default::default`] -- and the fixture itself no longer
+/// needs to implement [`Default`]. Nested fixtures are torn down with
+/// [`Fixture::tear_down`] in the reverse order that they are declared, and
+/// those already prepared are also torn down if a later field fails to be.
 ///
 /// # Examples
 ///
@@ -29,12 +48,100 @@ pub(crate) mod syn_utils;
 /// }
 /// ```
 ///
+/// Field initialization:
+///
+/// ```
+/// # use neotest_macros::Fixture;
+/// # use neotest_common::Fixture;
+/// # use neotest_common::Result;
+/// #[derive(Default, Fixture)]
+/// struct Database {
+///     // ...
+/// }
+///
+/// fn open_log() -> Result<Vec<String>> {
+///     Ok(vec!["opened".to_string()])
+/// }
+///
+/// #[derive(Fixture)]
+/// struct MyFixture {
+///     #[fixture(default = 42)]
+///     answer: u32,
+///     #[fixture(with = open_log)]
+///     log: Vec<String>,
+///     #[fixture(fixture)]
+///     db: Database,
+///     name: String,
+/// }
+/// ```
+///
 /// [`Fixture`]: neotest_common::Fixture
-#[proc_macro_derive(Fixture)]
+/// [`Fixture::tear_down`]: neotest_common::Fixture::tear_down
+/// [`Result`]: std::result::Result
+#[proc_macro_derive(Fixture, attributes(fixture))]
 pub fn fixture(input: TokenStream) -> TokenStream {
   derive_fixture::derive(input)
 }
 
+/// A derive-macro for automatically implementing the [`Arbitrary`] trait, so
+/// that values of a struct or enum can be generated for property tests.
+///
+/// Each field is generated and shrunk by its own [`Arbitrary`] implementation,
+/// and so the type must also implement [`Clone`] and [`Debug`]. Values shrink
+/// one field at a time, while enums first shrink to the unit variants declared
+/// before their own, and to the values of their own type that they hold.
+///
+/// Generation is customized with the `#[arbitrary(...)]` attribute:
+///
+/// * `#[arbitrary(strategy = <expr>)]` on a field, which generates and shrinks
+///   the field with the [`Strategy`] of `<expr>` -- e.g. `1..=6u8`.
+/// * `#[arbitrary(weight = <number>)]` on a variant, which generates the
+///   variant in proportion to its weight. Variants have a weight of `1` by
+///   default, and a weight of `0` never generates the variant.
+/// * `#[arbitrary(max_depth = <number>)]` on an enum, which limits the number
+///   of nested recursive variants. Recursive variants are those whose fields
+///   name the enum itself, or that are marked `#[arbitrary(recursive)]` -- for
+///   enums that contain themselves through another type. Once nested more
+///   than `max_depth` times, only variants that are not recursive are
+///   generated. This defaults to [`DEFAULT_MAX_DEPTH`].
+///
+/// # Examples
+///
+/// Basic use:
+///
+/// ```
+/// # use neotest_macros::Arbitrary;
+/// #[derive(Arbitrary, Clone, Debug)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+/// ```
+///
+/// Customized generation:
+///
+/// ```
+/// # use neotest_macros::Arbitrary;
+/// # use neotest_common::prop::vec;
+/// #[derive(Arbitrary, Clone, Debug)]
+/// #[arbitrary(max_depth = 3)]
+/// enum Expr {
+///     #[arbitrary(weight = 3)]
+///     Literal(#[arbitrary(strategy = -100..100i64)] i64),
+///     Negate(Box<Expr>),
+///     Sum(#[arbitrary(strategy = vec(neotest_common::prop::any::<Expr>(), 0..4))] Vec<Expr>),
+/// }
+/// ```
+///
+/// [`Arbitrary`]: neotest_common::prop::Arbitrary
+/// [`Strategy`]: neotest_common::prop::Strategy
+/// [`DEFAULT_MAX_DEPTH`]: neotest_common::prop::DEFAULT_MAX_DEPTH
+/// [`Debug`]: std::fmt::Debug
+#[proc_macro_derive(Arbitrary, attributes(arbitrary))]
+pub fn arbitrary(input: TokenStream) -> TokenStream {
+  derive_arbitrary::derive(input)
+}
+
 /// An attribute that indicates that an impl block is a test fixture.
 ///
 /// This macro enables type fixtures to be written using `self` arguments rather
@@ -58,9 +165,9 @@ pub fn fixture(input: TokenStream) -> TokenStream {
 ///
 /// This can be rewritten as:
 ///
-/// ```ignore
-/// # use neotest::neotest_fixture;
-/// # use neotest::Fixture;
+/// ```
+/// # use neotest_macros::{Fixture, neotest_fixture};
+/// # use neotest_common::Fixture;
 /// #[derive(Default, Fixture)]
 /// struct TestFixture {};
 ///
@@ -75,11 +182,124 @@ pub fn fixture(input: TokenStream) -> TokenStream {
 ///
 /// Functionally, these two will be the exact same; so which is preferred is
 /// just a matter of preference.
+///
+/// Each `#[neotest]` method must take the fixture as either `self`, `&self`, or
+/// `&mut self`, and accepts all the same arguments as a free `#[neotest]`
+/// function (aside from `fixture`, which is always the `Self` type). Other
+/// fixtures may still be injected through the remaining arguments. Any
+/// methods without the `#[neotest]` attribute are left untouched.
 #[proc_macro_attribute]
 pub fn neotest_fixture(attribute: TokenStream, item: TokenStream) -> TokenStream {
+  if let Some(deferred) = defer_parameter_set(SetTarget::Fixture, &attribute, &item) {
+    return deferred;
+  }
   let _ = parse_macro_input!(attribute as syn::parse::Nothing);
+  let item = parse_macro_input!(item as syn::ItemImpl);
 
-  item
+  match suite::FixtureSuite::from_impl(item) {
+    Ok(suite) => suite.to_token_stream().into(),
+    Err(error) => TokenStream::from(error.to_compile_error()),
+  }
+}
+
+/// An attribute that groups the `#[neotest]` tests of an inline module into a
+/// single test-suite, akin to an xUnit test class.
+///
+/// This accepts all the same arguments as [`neotest`], each of which is applied
+/// as a default to every `#[neotest]` function within the module that is able
+/// to accept it -- e.g. a `parameter = a as [...]` is only applied to tests
+/// that have a parameter `a`, and fixtures are only applied to the tests that
+/// take them. Any argument specified by a test itself takes precedence.
+///
+/// Additionally, this accepts:
+///
+/// * `attributes`, which lists attributes to apply to every test.
+///   This is in the form:
+///   ```text
+///   attributes = [<attribute>, <attribute>, ...]
+///   ```
+///
+/// * Module-scoped fixtures, which are prepared once for all tests within the
+///   module, and torn down after the last test of the module that uses it.
+///   This is in the form:
+///   ```text
+///   fixture(scope = module) = <Fixture type>
+///   ```
+///   Module-scoped fixtures must be taken by shared reference (`&Fixture`), and
+///   both `Send` and `Sync`. These are only torn down once all tests that use
+///   them have run -- including `#[ignore]`d tests when run with `--ignored`
+///   or `--include-ignored` -- which does not occur if some of the tests are
+///   filtered out by name.
+///
+/// All inputs are resolved from within the module. Only the `#[neotest]`
+/// functions defined directly within the module are affected.
+///
+/// # Examples
+///
+/// ```
+/// # use neotest_macros::{neotest, neotest_suite, Fixture};
+/// # use neotest_common::Fixture;
+/// #[derive(Default, Fixture)]
+/// pub struct Database {
+///     /* ... */
+/// }
+///
+/// #[neotest_suite(
+///     fixture(scope = module) = Database,
+///     parameter = a as [1, 2, 3],
+///     attributes = [allow(unused)]
+/// )]
+/// mod tests {
+///     use super::*;
+///
+///     #[neotest]
+///     fn test_something(db: &Database, a: u32) {
+///         /* ... */
+///     }
+///
+///     #[neotest(parameter = a as [4, 5])]
+///     fn test_something_else(db: &Database, a: u32) {
+///         /* ... */
+///     }
+/// }
+/// # fn main() {}
+/// ```
+///
+/// Module-scoped fixtures cannot be taken by value or by mutable reference:
+///
+/// ```compile_fail
+/// # use neotest_macros::{neotest, neotest_suite, Fixture};
+/// # use neotest_common::Fixture;
+/// #[derive(Default, Fixture)]
+/// pub struct Database {
+///     /* ... */
+/// }
+///
+/// #[neotest_suite(fixture(scope = module) = Database)]
+/// mod tests {
+///     use super::*;
+///
+///     // error: Module-scoped test fixture 'Database' must be taken by shared
+///     // reference, e.g. `&Database`.
+///     #[neotest]
+///     fn test_something(db: &mut Database) {
+///         /* ... */
+///     }
+/// }
+/// # fn main() {}
+/// ```
+#[proc_macro_attribute]
+pub fn neotest_suite(attribute: TokenStream, item: TokenStream) -> TokenStream {
+  if let Some(deferred) = defer_parameter_set(SetTarget::Suite, &attribute, &item) {
+    return deferred;
+  }
+  let inputs = parse_macro_input!(attribute as input::SuiteInputs);
+  let item = parse_macro_input!(item as syn::ItemMod);
+
+  match suite::ModuleSuite::from_module(inputs, item) {
+    Ok(suite) => suite.to_token_stream().into(),
+    Err(error) => TokenStream::from(error.to_compile_error()),
+  }
 }
 
 struct Subtest {
@@ -90,14 +310,16 @@ impl quote::ToTokens for Subtest {
   fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
     let context = common::ident::context();
     let block = &self.block;
-    let stmt: syn::Stmt = syn::parse_quote! {
+    // This is an expression rather than a statement so that `subtest!` may
+    // also appear as the final expression of a test.
+    let expr: syn::Expr = syn::parse_quote! {
       if #context.can_execute_subtest() {
         #[allow(unused)]
         let mut #context = #context.subtest();
         #block
-      };
+      }
     };
-    stmt.to_tokens(tokens);
+    expr.to_tokens(tokens);
   }
 }
 
@@ -113,21 +335,99 @@ pub fn subtest(item: TokenStream) -> TokenStream {
 /// The minimal version of this macro will evaluate into just a normal Rust
 /// `#[test]`. Additional features are also supported:
 ///
-/// * `fixture` which creates test-fixtures.
+/// * `fixture` which names the test-fixtures of the test.
 ///   This is in the form:
 ///   ```text
-///   fixture = <Fixture name>
+///   fixture = <Fixture type>
+///   fixture = [<Fixture type>, <Fixture type>, ...]
 ///   ```
-///   This parameter can only be specified at most once per test.
+///   Fixture types may be any path, and may depend on the generic inputs of
+///   the test -- e.g. `fixture = support::Harness<T>`.
+///
+///   Fixtures are prepared separately for each test-case by default. Fixtures
+///   that are expensive to prepare may instead be shared by every test in the
+///   test binary with `fixture(scope = suite) = <Fixture type>`. Such fixtures
+///   are prepared at most once -- with any failure reported to each test that
+///   requires it -- and must be taken by shared reference (`&Fixture`).
+///   Every test function parameter that is not bound to a `parameter` input is
+///   injected as a fixture of its own type, in any position; naming fixtures
+///   here additionally verifies that the test takes them. Each fixture may only
+///   be named once per test.
+///
+///   Fixtures may also be prepared from the values of `parameter` inputs by
+///   naming those parameters after the fixture type, such as
+///   `fixture = Database(backend)`. Such fixtures implement
+///   `ParameterizedFixture` rather than `Fixture`, and are prepared separately
+///   for each test-case from a clone of its parameter values.
 ///
 /// * `parameter` which produces parameter-based inputs.
 ///   This is in the form:
 ///   ```text
-///   parameter = <param name> as [<expr0>, <expr1>, ...]
+///   parameter = <param name> as [<expr0>, <label>: <expr1>, ...]
 ///   ```
 ///   This parameter can be specified multiple times per test, but only once per
 ///   `<param name>`.
 ///
+///   Test-cases are named after their inputs, such as `a_0_b_hello`. Inputs
+///   that are integer, boolean, character, or string literals are named by
+///   their value when it is valid in an identifier; any other input may be
+///   named with a label, such as `max: u32::MAX`, and is otherwise named by
+///   its index, such as `a_idx2`. Test-cases whose names collide are made
+///   unique with the index of the test-case.
+///
+///   Inputs may be preceded by `#[cfg]`, `#[ignore]`, or `#[should_panic]`
+///   attributes, which apply only to the test-cases that use that input --
+///   such as `#[ignore = "bug 42"] 3` or `#[cfg(unix)] 4`. These attributes
+///   replace the same attributes of the test itself, and the test-cases that
+///   have them are only run on their own, rather than by the test.
+///
+///   Inputs may also be formed from each file that matches a glob pattern,
+///   relative to the directory of the crate's manifest:
+///   ```text
+///   parameter = <param name> as files("<pattern>")
+///   parameter = <param name> as file_contents("<pattern>")
+///   ```
+///   where `*` and `?` match within a single component of a path, and `**`
+///   matches any number of directories; symbolic links to directories are not
+///   followed. `files` provides the path of each file
+///   as a `&'static Path`, while `file_contents` provides its contents as a
+///   `&'static str`. Test-cases are named after the path of each file relative
+///   to where the pattern starts matching, without its extension. Matched files
+///   are tracked, so that changing them rebuilds the test; however a file that
+///   is added is only found once the test is rebuilt for some other reason, such
+///   as by `cargo:rerun-if-changed=<directory>` in a build script.
+///
+///   Inputs may also be given by a named set, defined with [`parameter_set`]:
+///   ```text
+///   parameter = <param name> as <SET NAME>
+///   ```
+///
+///   Inputs may instead be generated when the test is run, from any expression
+///   that evaluates to an [`IntoIterator`]:
+///   ```text
+///   parameter = <param name> from <expr>
+///   ```
+///   Generated inputs run as cases within a single test, rather than as tests
+///   of their own. Every case is run even after one fails, and each failing
+///   case is reported by its index and the `Debug` value of its inputs. The
+///   inputs of several generated parameters are combined, and so must also be
+///   `Clone`. Generated parameters cannot be zipped.
+///
+///   Inputs may also be generated by a strategy from `neotest::prop`, which
+///   is any expression other than a list, files, or the name of a set:
+///   ```text
+///   parameter = <param name> as any::<u32>()
+///   parameter = <param name> as vec(any::<u8>(), 0..64)
+///   ```
+///   The test-case is run against `cases` generated values, from a generator
+///   seeded by the `NEOTEST_SEED` environment variable if it is set. The first
+///   case that fails is shrunk to the simplest values that still fail, and is
+///   reported along with the seed that replays it. Parameters generated by
+///   strategies cannot be zipped, or combined with parameters generated by
+///   `from`. A lone path always names a set, so a strategy held by a constant
+///   or static must be written as an expression -- e.g. `(SMALL_STRATEGY)` --
+///   as `SMALL_STRATEGY` alone is reported as not being a parameter set.
+///
 /// * `type_parameter` which produces generic type inputs
 ///   This is in the form:
 ///   ```text
@@ -144,6 +444,98 @@ pub fn subtest(item: TokenStream) -> TokenStream {
 ///   This parameter can be specified multiple times per test, but only once per
 ///   `<generic const input name>`.
 ///
+/// * `zip` which pairs the inputs of parameters by position, rather than
+///   combining them.
+///   This is in the form:
+///   ```text
+///   zip(<param name>, <param name>, ...)
+///   ```
+///   The i-th test-case receives the i-th input of each zipped parameter, so
+///   every zipped parameter must have the same number of inputs. Zipped
+///   parameters are still combined with any other parameters of the test.
+///
+/// * `combinations` or `n_wise` which reduce the combinations of parameter
+///   inputs that are tested.
+///   This is in the form:
+///   ```text
+///   combinations = <all | pairwise>
+///   n_wise = <n>
+///   ```
+///   With `pairwise` (equivalent to `n_wise = 2`), only enough test-cases are
+///   generated for every pair of inputs of any two parameters to be tested
+///   together at least once -- rather than every combination of all of them.
+///   The test-cases are chosen deterministically, and keep the same names for
+///   the same inputs. Zipped parameters are treated as a single parameter.
+///
+/// * `exclude` which skips combinations of parameter inputs.
+///   This is in the form:
+///   ```text
+///   exclude = |<param name>, <param name>, ...| <expr>
+///   ```
+///   where each argument of the closure names a parameter input of the test,
+///   and is given a reference to the input of that parameter. Combinations for
+///   which the closure returns `true` are skipped. When every input the
+///   closure uses is a literal and the closure only uses comparisons,
+///   arithmetic, and boolean logic, the combination is decided when the test
+///   is expanded, and its test-case is reported as ignored; otherwise, it is
+///   decided when the test-case is run, before any fixtures are prepared. The
+///   test harness has no way to skip a test once it has started, so a
+///   test-case skipped when it is run is reported as passing, and only notes
+///   that it was filtered by `exclude` in its captured output. This can only
+///   be specified once per test.
+///
+/// * `cases_from` which reads the inputs of parameters from a data file.
+///   This is in the form:
+///   ```text
+///   cases_from = "<path>"
+///   ```
+///   where `<path>` is relative to the directory of the crate's manifest, and
+///   is either a CSV (`.csv`) file with a header row, or a JSON-lines
+///   (`.jsonl`) file of flat objects. Each column names a parameter of the
+///   test, and each row becomes a test-case named after its values. Values are
+///   parsed with `FromStr` as the type of their parameter, except for `&str`
+///   parameters which are given the values as written. Malformed files and
+///   invalid values of primitive types are compile errors naming the line of
+///   the file, and the test is rebuilt whenever the file changes -- although
+///   `isize` and `usize` values are only checked against the range of `i64`
+///   and `u64`, and otherwise fail when run on a target with narrower
+///   pointers. This can only be specified once per test, and not for a
+///   `#[neotest_suite]`.
+///
+/// * `cases` which sets the number of values generated for the parameters that
+///   are generated by strategies.
+///   This is in the form:
+///   ```text
+///   cases = <number>
+///   ```
+///   Tests with these parameters are run against 256 cases by default.
+///
+/// * `timeout` which fails each test-case that does not complete in time.
+///   This is in the form:
+///   ```text
+///   timeout = "<number><unit>"
+///   ```
+///   where `<unit>` is one of `ms`, `s`, `m`, or `h` -- e.g. `timeout = "5s"`;
+///   a timeout of zero is rejected, since it could never be met.
+///   Test-cases with a timeout are run on a separate thread, and fail with a
+///   `TimeoutError` naming the test, its parameter input, and its subtest path
+///   if the timeout is exceeded. Tests without a timeout use the default from
+///   the `NEOTEST_TIMEOUT` environment variable, if set when running the tests.
+///
+/// * `executor` which names the attribute of the async runtime that runs an
+///   `async fn` test, and requires the `executor` feature.
+///   This is in the form:
+///   ```text
+///   executor = <attribute>
+///   ```
+///   e.g. `executor = tokio::main(flavor = "current_thread")`. Without this,
+///   `async fn` tests are run on a small built-in single-threaded executor,
+///   which is sufficient for futures that do not depend on a specific runtime.
+///
+/// Tests may be `async fn`, in which case `.await` may be used anywhere in the
+/// test -- including within `subtest!` bodies. Fixtures of async tests are
+/// prepared and torn down as an `AsyncFixture`, which every `Fixture` also is.
+///
 /// When executing tests with multiple parameter inputs, this will produce the
 /// complete graph of all possible inputs -- e.g. for parameters `(a, b)` with
 /// inputs `a as [0, 1]`  and `b as [2, 3]`, this will call the test 4 times
@@ -154,12 +546,15 @@ pub fn subtest(item: TokenStream) -> TokenStream {
 /// * `a = 1`, `b = 2`,
 /// * `a = 1`, `b = 3`,
 ///
-/// To execute tests with the same sets of inputs, use tuples instead:
+/// To execute tests with the same sets of inputs, use `zip` or tuples instead:
 ///
 /// ```
 /// # use neotest_macros::neotest;
+/// #[neotest(parameter = a as [1, 2], parameter = b as [1, 4], zip(a, b))]
+/// fn test_something(a: u32, b: u32){ /* ... */ }
+///
 /// #[neotest(parameter = a as [(1, 1), (2, 2), /* etc */])]
-/// fn test_something(a: (u32, u32)){ /* ... */ }
+/// fn test_something_else(a: (u32, u32)){ /* ... */ }
 /// ```
 ///
 /// # Examples
@@ -189,13 +584,87 @@ pub fn subtest(item: TokenStream) -> TokenStream {
 ///     /* ... */
 /// }
 ///
-/// // calls TestFixture::set_up on begin, and TestFixture::tear_down on end
+/// // calls TestFixture::prepare on begin, and TestFixture::tear_down on end
 /// #[neotest(fixture = TestFixture)]
 /// fn test_something_with_fixture(f: &TestFixture) {
 ///     /* ... */
 /// }
 /// ```
 ///
+/// Using multiple fixtures, which are injected by type in any position:
+///
+/// ```
+/// # use neotest_macros::{neotest, Fixture};
+/// # use neotest_common::Fixture;
+/// #[derive(Default, Fixture)]
+/// struct Database {
+///     /* ... */
+/// }
+///
+/// #[derive(Default, Fixture)]
+/// struct TempDir {
+///     /* ... */
+/// }
+///
+/// #[neotest(fixture = [Database, TempDir], parameter = a as [1, 2])]
+/// fn test_something_with_fixtures(db: &Database, a: u32, dir: &mut TempDir) {
+///     /* ... */
+/// }
+/// ```
+///
+/// Using fixtures that are prepared from the parameters of each test-case:
+///
+/// ```
+/// # use neotest_macros::neotest;
+/// # use neotest_common::{ParameterizedFixture, Result};
+/// #[derive(Clone, Copy)]
+/// enum Backend {
+///     Memory,
+///     Disk,
+/// }
+///
+/// struct Database {
+///     backend: Backend,
+/// }
+///
+/// impl ParameterizedFixture<Backend> for Database {
+///     fn prepare_with(backend: Backend) -> Result<Self> {
+///         Ok(Self { backend })
+///     }
+/// }
+///
+/// // Prepares a separate Database for each Backend
+/// #[neotest(
+///     fixture = Database(backend),
+///     parameter = backend as [Backend::Memory, Backend::Disk],
+/// )]
+/// fn test_something_with_backend(db: &Database, backend: Backend) {
+///     /* ... */
+/// }
+/// ```
+///
+/// Async tests:
+///
+/// ```
+/// # use neotest_macros::neotest;
+/// # use neotest_common::{AsyncFixture, Result};
+/// struct Connection {
+///     /* ... */
+/// }
+///
+/// impl AsyncFixture for Connection {
+///     async fn prepare() -> Result<Self> {
+///         Ok(Self { /* ... */ })
+///     }
+/// }
+///
+/// // Runs on the built-in executor, or the one named by `executor = ...`
+/// #[neotest(fixture = Connection)]
+/// async fn test_something_async(conn: &mut Connection) {
+///     /* ... */
+/// }
+/// ```
+///
 /// Test parameter inputs:
 ///
 /// ```
@@ -208,12 +677,42 @@ pub fn subtest(item: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+/// Test parameter inputs with attributes of their own:
+///
+/// ```
+/// # use neotest_macros::neotest;
+///
+/// // Only the test-case with 0 is expected to panic
+/// #[neotest(parameter = a as [1, 2, #[should_panic] 0])]
+/// fn test_something_with_divisor(a: u32) {
+///     assert_eq!(a / a, 1);
+/// }
+/// ```
+///
+/// Test parameter inputs generated at runtime:
+///
+/// ```
+/// # use neotest_macros::neotest;
+/// fn presets() -> Vec<u32> {
+///     vec![16, 256, 4096]
+/// }
+///
+/// // Calls test_something_with_generated_parameter with each preset, as
+/// // cases of a single test
+/// #[neotest(parameter = a from presets())]
+/// fn test_something_with_generated_parameter(a: u32) {
+///     /* ... */
+/// }
+/// ```
+///
 /// Test generic type-parameter inputs:
 ///
-/// ```ignore
+/// ```
 /// # use neotest_macros::neotest;
 ///
-/// // Calls test_something_with_generic_type_parameter with T as u32 and u64
+/// // Calls test_something_with_generic_type_parameter with T as u32 and u64,
+/// // through the tests `test_something_with_generic_type_parameter::u32` and
+/// // `test_something_with_generic_type_parameter::u64`
 /// #[neotest(type_parameter = T as [u32, u64])]
 /// fn test_something_with_generic_type_parameter<T>() {
 ///     /* ... */
@@ -222,20 +721,113 @@ pub fn subtest(item: TokenStream) -> TokenStream {
 ///
 /// Test generic const-parameter inputs:
 ///
-/// ```ignore
+/// ```
 /// # use neotest_macros::neotest;
 ///
 /// // Calls test_something_with_generic_const_parameter with VALUE as 0xdead
-/// // and 0xbeef
+/// // and 0xbeef, through the tests
+/// // `test_something_with_generic_const_parameter::VALUE_0xdead` and
+/// // `test_something_with_generic_const_parameter::VALUE_0xbeef`
 /// #[neotest(const_parameter = VALUE as [0xdead, 0xbeef])]
 /// fn test_something_with_generic_const_parameter<const VALUE: u32>() {
 ///     /* ... */
 /// }
 /// ```
 ///
+/// Every generic parameter must be given inputs of its own kind, exactly once:
+///
+/// ```compile_fail
+/// # use neotest_macros::neotest;
+/// // error: Generic test input 'T' specified more than once.
+/// #[neotest(type_parameter = T as [u32], type_parameter = T as [u64])]
+/// fn test_something<T>() {}
+/// ```
+///
+/// ```compile_fail
+/// # use neotest_macros::neotest;
+/// // error: Test input 'T' is a generic type parameter, and cannot be given
+/// // const values.
+/// #[neotest(const_parameter = T as [1, 2])]
+/// fn test_something<T>() {}
+/// ```
+///
+/// ```compile_fail
+/// # use neotest_macros::neotest;
+/// // error: Test input 'N' is a generic const parameter, and cannot be given
+/// // types.
+/// #[neotest(type_parameter = N as [u32])]
+/// fn test_something<const N: u32>() {}
+/// ```
+///
+/// ```compile_fail
+/// # use neotest_macros::neotest;
+/// // error: Test input 'a' names a lifetime, which cannot be used as a test
+/// // input.
+/// #[neotest(type_parameter = a as [u32])]
+/// fn test_something<'a>() {}
+/// ```
+///
+/// ```compile_fail
+/// # use neotest_macros::neotest;
+/// // error: Test generic parameter 'U' is not bound to test input.
+/// #[neotest(type_parameter = T as [u32])]
+/// fn test_something<T, U>() {}
+/// ```
+///
+/// ```compile_fail
+/// # use neotest_macros::neotest;
+/// // error: Test input 'T' is not a valid generic parameter.
+/// #[neotest(type_parameter = T as [u32])]
+/// fn test_something() {}
+/// ```
+///
+/// Every input must be given at least one argument:
+///
+/// ```compile_fail
+/// # use neotest_macros::neotest;
+/// // error: Test input 'T' must be given at least one argument.
+/// #[neotest(type_parameter = T as [])]
+/// fn test_something<T>() {}
+/// ```
+///
+/// ```compile_fail
+/// # use neotest_macros::neotest;
+/// // error: Test input 'N' must be given at least one argument.
+/// #[neotest(const_parameter = N as [])]
+/// fn test_something<const N: u32>() {}
+/// ```
+///
+/// ```compile_fail
+/// # use neotest_macros::neotest;
+/// // error: Test input 'a' must be given at least one argument.
+/// #[neotest(parameter = a as [])]
+/// fn test_something(a: u32) {}
+/// ```
+///
+/// Test-cases that must complete in time:
+///
+/// ```
+/// # use neotest_macros::neotest;
+///
+/// // Fails with a `TimeoutError` if it does not complete within 250ms
+/// #[neotest(timeout = "250ms")]
+/// fn test_something_with_timeout() {
+///     /* ... */
+/// }
+/// ```
+///
+/// A timeout of zero is rejected:
+///
+/// ```compile_fail
+/// # use neotest_macros::neotest;
+/// #[neotest(timeout = "0s")]
+/// fn test_something_with_zero_timeout() {}
+/// # fn main() {}
+/// ```
+///
 /// Combined with everything:
 ///
-/// ```ignore
+/// ```
 /// # use neotest_macros::neotest;
 /// # use neotest_common::{Fixture, Result};
 ///
@@ -261,7 +853,7 @@ pub fn subtest(item: TokenStream) -> TokenStream {
 /// //     * (a, b) = (1, 4), (1, 5), (2, 4), (2, 5), (3, 4), (3, 5)
 /// //   * VALUE = 0xbeef
 /// //     * (a, b) = (1, 4), (1, 5), (2, 4), (2, 5), (3, 4), (3, 5)
-/// // * T = u64
+/// // * T = i32
 /// //   * VALUE = 0xdead
 /// //     * (a, b) = (1, 4), (1, 5), (2, 4), (2, 5), (3, 4), (3, 5)
 /// //   * VALUE = 0xbeef
@@ -270,15 +862,19 @@ pub fn subtest(item: TokenStream) -> TokenStream {
 ///     fixture = TestFixture,
 ///     parameter = a as [1, 2, 3],
 ///     parameter = b as [4, 5],
-///     type_parameter = T as [u32, i32]
+///     type_parameter = T as [u32, i32],
 ///     const_parameter = VALUE as [0xdead, 0xbeef]
 /// )]
-/// fn test_something_with_everything<const VALUE: u32, T>(f: &TestFixture, a: T, b: T) {
+/// fn test_something_with_everything<T, const VALUE: u32>(f: &TestFixture, a: T, b: T) {
 ///   /* ... */
 /// }
 /// ```
 #[proc_macro_attribute]
 pub fn neotest(attribute: TokenStream, item: TokenStream) -> TokenStream {
+  if let Some(deferred) = defer_parameter_set(SetTarget::Test, &attribute, &item) {
+    return deferred;
+  }
+
   // Parse input
   let input = syn::parse_macro_input!(attribute as input::TestInputs);
   let item = syn::parse_macro_input!(item as syn::ItemFn);
@@ -288,3 +884,87 @@ pub fn neotest(attribute: TokenStream, item: TokenStream) -> TokenStream {
     Err(error) => TokenStream::from(error.to_compile_error()),
   }
 }
+
+/// A macro that defines a named set of parameter inputs, which can be used by
+/// the `parameter` argument of any [`neotest`] or [`neotest_suite`] attribute
+/// in place of a list of inputs.
+///
+/// This is in the form:
+///
+/// ```text
+/// parameter_set!(<vis> <NAME> = [<expr0>, <expr1>, ...]);
+/// ```
+///
+/// where inputs may be labelled, or formed from files, exactly as for the
+/// `parameter` argument. Each use of the set expands into a separate executor
+/// for every input, just as if the inputs were listed in the attribute.
+///
+/// Sets are defined as macros, and so are resolved the same way: a set may be
+/// used by its path from anywhere its visibility allows -- e.g. a
+/// `pub(crate)` set defined in a `sets` module can be used as
+/// `parameter = a as crate::sets::EDGE_U32`. Sets used by the tests within a
+/// [`neotest_suite`] module are named from the scope that contains the module,
+/// rather than from within it. Sets are only visible within the crate that
+/// defines them, and so cannot be `pub`:
+///
+/// ```compile_fail
+/// # use neotest_macros::parameter_set;
+/// // error: parameter sets cannot be `pub`, since they are only visible within
+/// // their crate; use `pub(crate)` instead
+/// parameter_set!(pub EDGE_U32 = [0, 1, u32::MAX]);
+/// # fn main() {}
+/// ```
+///
+/// # Examples
+///
+/// ```ignore
+/// # use neotest::{neotest, parameter_set};
+/// parameter_set!(EDGE_U32 = [zero: 0, one: 1, max: u32::MAX]);
+///
+/// // Calls test_something through the tests `test_something::a_zero`,
+/// // `test_something::a_one`, and `test_something::a_max`
+/// #[neotest(parameter = a as EDGE_U32)]
+/// fn test_something(a: u32) {
+///     /* ... */
+/// }
+/// # fn main() {}
+/// ```
+#[proc_macro]
+pub fn parameter_set(item: TokenStream) -> TokenStream {
+  let set = parse_macro_input!(item as ParameterSet);
+  set.to_token_stream().into()
+}
+
+/// An internal macro that substitutes the inputs of a parameter set into the
+/// attribute that uses it, and then expands the attribute.
+///
+/// This is invoked by the macro that defines each parameter set, through its
+/// re-export from `neotest`, and should never be used directly.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __with_parameter_set(item: TokenStream) -> TokenStream {
+  let resolved = parse_macro_input!(item as ResolvedSet);
+  let args = resolved.args.into();
+  let item = resolved.item.into();
+  match resolved.target {
+    SetTarget::Test => neotest(args, item),
+    SetTarget::Suite => neotest_suite(args, item),
+    SetTarget::Fixture => neotest_fixture(args, item),
+  }
+}
+
+/// Defers the expansion of an attribute to the first parameter set that it
+/// uses, if any.
+///
+/// # Arguments
+///
+/// * `target` - the attribute being expanded
+/// * `attribute` - the arguments of the attribute
+/// * `item` - the item that the attribute is applied to
+fn defer_parameter_set(
+  target: SetTarget,
+  attribute: &TokenStream,
+  item: &TokenStream,
+) -> Option<TokenStream> {
+  parameter_set::defer(target, &attribute.clone().into(), &item.clone().into()).map(Into::into)
+}
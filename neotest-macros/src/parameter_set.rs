@@ -0,0 +1,376 @@
+//! This internal module defines named parameter sets, which provide the same
+//! parameter inputs to many tests.
+//!
+//! Attribute macros cannot see the definitions of other items, so sets are
+//! resolved through a callback: a set is defined as a `macro_rules!` macro,
+//! and an attribute that names a set defers its expansion to that macro --
+//! which hands the inputs of the set back to [`__with_parameter_set`], along
+//! with the original attribute and item. The inputs are then substituted for
+//! the name of the set, and the attribute is expanded again.
+//!
+//! [`__with_parameter_set`]: crate::__with_parameter_set
+use proc_macro2::{Delimiter, Group, Ident, Spacing, Span, TokenStream, TokenTree};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::parse::{Parse, ParseStream};
+use syn::token::Eq;
+use syn::{Result, Visibility};
+
+use crate::common::path;
+use crate::input::ParameterInput;
+
+/// The definition of a named parameter set, formed from the input of the
+/// [`parameter_set`] macro:
+///
+/// ```ignore
+/// parameter_set!(pub(crate) EDGE_U32 = [zero: 0, 1, u32::MAX]);
+/// ```
+///
+/// This expands into a hidden `macro_rules!` macro that hands its inputs to
+/// [`__with_parameter_set`] -- by its path through `neotest`, since users of the
+/// set are not expected to depend on `neotest_macros` -- which is then imported
+/// with the name of the set. A marker constant of the same name is also
+/// defined, so that uses of a name that is not a set can be reported as such:
+///
+/// ```ignore
+/// #[doc(hidden)]
+/// macro_rules! __neotest_parameter_set_EDGE_U32 {
+///   ($($callback:tt)*) => {
+///     ::neotest::__with_parameter_set! { ([zero: 0, 1, u32::MAX]) $($callback)* }
+///   };
+/// }
+/// pub(crate) use __neotest_parameter_set_EDGE_U32 as EDGE_U32;
+/// #[doc(hidden)]
+/// pub(crate) const EDGE_U32: ::neotest_common::__internal::__ParameterSet =
+///   ::neotest_common::__internal::__ParameterSet;
+/// ```
+///
+/// [`parameter_set`]: crate::parameter_set
+/// [`__with_parameter_set`]: crate::__with_parameter_set
+pub struct ParameterSet {
+  vis: Visibility,
+  ident: Ident,
+  inputs: TokenStream,
+}
+
+impl Parse for ParameterSet {
+  /// Parses the set from the parse stream
+  ///
+  /// Expected input is in the form `<vis> <ident> = [<expr0>, <expr1>, ...]`,
+  /// where each expression may be preceded by a label in the form `<label>: `;
+  /// or in the form `<vis> <ident> = files("<pattern>")`.
+  fn parse(input: ParseStream) -> Result<Self> {
+    let vis: Visibility = input.parse()?;
+    // Sets are macros that are not exported, and so cannot be used outside of
+    // the crate that defines them
+    if let Visibility::Public(public) = &vis {
+      return Err(syn::Error::new(
+        public.pub_token.span,
+        "parameter sets cannot be `pub`, since they are only visible within their crate; use `pub(crate)` instead",
+      ));
+    }
+    let ident: Ident = input.parse()?;
+    input.parse::<Eq>()?;
+    let inputs: TokenStream = input.parse()?;
+
+    // Inputs are checked as the inputs of a parameter, so that mistakes are
+    // reported where the set is defined rather than where it is used
+    syn::parse2::<ParameterInput>(quote! { #ident as #inputs })?;
+    Ok(Self { vis, ident, inputs })
+  }
+}
+
+impl ToTokens for ParameterSet {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    let vis = &self.vis;
+    let ident = &self.ident;
+    let inputs = &self.inputs;
+    let definition = format_ident!("__neotest_parameter_set_{}", ident);
+    let internal = path::crate_internal();
+    quote! {
+      #[doc(hidden)]
+      macro_rules! #definition {
+        ($($callback:tt)*) => {
+          ::neotest::__with_parameter_set! { (#inputs) $($callback)* }
+        };
+      }
+      #[allow(unused_imports)]
+      #vis use #definition as #ident;
+      #[doc(hidden)]
+      #[allow(dead_code, non_upper_case_globals)]
+      #vis const #ident: #internal::__ParameterSet = #internal::__ParameterSet;
+    }
+    .to_tokens(tokens);
+  }
+}
+
+/// The attribute whose expansion was deferred to resolve a parameter set.
+#[derive(Clone, Copy)]
+pub enum SetTarget {
+  Test,
+  Suite,
+  Fixture,
+}
+
+impl SetTarget {
+  fn ident(self) -> Ident {
+    match self {
+      SetTarget::Test => format_ident!("neotest"),
+      SetTarget::Suite => format_ident!("neotest_suite"),
+      SetTarget::Fixture => format_ident!("neotest_fixture"),
+    }
+  }
+}
+
+impl Parse for SetTarget {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let ident: Ident = input.parse()?;
+    match &*ident.to_string() {
+      "neotest" => Ok(SetTarget::Test),
+      "neotest_suite" => Ok(SetTarget::Suite),
+      "neotest_fixture" => Ok(SetTarget::Fixture),
+      _ => Err(syn::Error::new(
+        ident.span(),
+        "unknown parameter set target",
+      )),
+    }
+  }
+}
+
+/// Defers the expansion of an attribute to the first parameter set that is
+/// named by either its arguments, or the `#[neotest]` attributes within its
+/// item.
+///
+/// Returns [`None`] if no parameter set is named, and so the attribute can be
+/// expanded as-is.
+///
+/// # Arguments
+///
+/// * `target` - the attribute being expanded
+/// * `args` - the arguments of the attribute
+/// * `item` - the item that the attribute is applied to
+pub fn defer(target: SetTarget, args: &TokenStream, item: &TokenStream) -> Option<TokenStream> {
+  let mut set: Option<TokenStream> = None;
+  let mut find = |args: TokenStream| {
+    if set.is_none() {
+      set = find_set(&args);
+    }
+    args
+  };
+  find(args.clone());
+  map_attribute_args(item.clone(), &mut find);
+
+  let set = set?;
+  let target = target.ident();
+  let internal = path::crate_internal();
+  // A lone path that is not a set -- such as a constant strategy -- would
+  // otherwise only be reported as a missing macro
+  let span = set.clone().into_iter().next().map(|v| v.span());
+  let check = quote_spanned! { span.unwrap_or_else(Span::call_site)=>
+    #internal::__parameter_set(&#set);
+  };
+  Some(quote! {
+    const _: () = {
+      fn __check_parameter_set() {
+        #check
+      }
+    };
+    #set! { (#set) #target (#args) #item }
+  })
+}
+
+/// The input of [`__with_parameter_set`], which substitutes the inputs of a
+/// parameter set for its name within a deferred attribute.
+///
+/// This is in the form `(<inputs>) (<set>) <target> (<args>) <item>`.
+///
+/// [`__with_parameter_set`]: crate::__with_parameter_set
+pub struct ResolvedSet {
+  pub target: SetTarget,
+  pub args: TokenStream,
+  pub item: TokenStream,
+}
+
+impl Parse for ResolvedSet {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let inputs: Group = input.parse()?;
+    let set: Group = input.parse()?;
+    let target: SetTarget = input.parse()?;
+    let args: Group = input.parse()?;
+    let item: TokenStream = input.parse()?;
+
+    let path = set.stream().to_string();
+    let mut count = 0;
+    let mut replace = |args: TokenStream| replace_set(args, &path, &inputs.stream(), &mut count);
+    let args = replace(args.stream());
+    let item = map_attribute_args(item, &mut replace);
+    if count == 0 {
+      return Err(syn::Error::new(
+        set.span(),
+        format!("parameter set '{path}' is not used by any `parameter`"),
+      ));
+    }
+    Ok(Self { target, args, item })
+  }
+}
+
+/// Finds the path of the first parameter set named by the `parameter`
+/// arguments of an attribute.
+///
+/// # Arguments
+///
+/// * `args` - the arguments of the attribute
+fn find_set(args: &TokenStream) -> Option<TokenStream> {
+  let tokens: Vec<TokenTree> = args.clone().into_iter().collect();
+  (0..tokens.len()).find_map(|i| set_at(&tokens, i).map(|(path, _)| path))
+}
+
+/// Replaces each use of the parameter set named `set` within the arguments of
+/// an attribute with its `inputs`.
+///
+/// # Arguments
+///
+/// * `args` - the arguments of the attribute
+/// * `set` - the path of the set, as a string
+/// * `inputs` - the inputs of the set
+/// * `count` - the number of uses replaced so far
+fn replace_set(
+  args: TokenStream,
+  set: &str,
+  inputs: &TokenStream,
+  count: &mut usize,
+) -> TokenStream {
+  let tokens: Vec<TokenTree> = args.into_iter().collect();
+  let mut result = TokenStream::new();
+  let mut i = 0;
+  while i < tokens.len() {
+    match set_at(&tokens, i) {
+      Some((path, len)) if path.to_string() == *set => {
+        tokens[i..i + 4]
+          .iter()
+          .for_each(|v| v.to_tokens(&mut result));
+        inputs.to_tokens(&mut result);
+        *count += 1;
+        i += 4 + len;
+      }
+      _ => {
+        tokens[i].to_tokens(&mut result);
+        i += 1;
+      }
+    }
+  }
+  result
+}
+
+/// Returns the path of the parameter set named by the `parameter` argument
+/// starting at index `i`, along with the number of tokens of the path.
+///
+/// Parameters whose inputs are a list, that are formed from files, or that are
+/// generated by a strategy other than a lone path, do not name a set.
+///
+/// # Arguments
+///
+/// * `tokens` - the tokens of the arguments of an attribute
+/// * `i` - the index of the argument
+fn set_at(tokens: &[TokenTree], i: usize) -> Option<(TokenStream, usize)> {
+  let is_parameter = matches!(
+    &tokens[i..],
+    [TokenTree::Ident(option), TokenTree::Punct(eq), TokenTree::Ident(_), TokenTree::Ident(as_), ..]
+      if option == "parameter" && eq.as_char() == '=' && as_ == "as"
+  );
+  if !is_parameter {
+    return None;
+  }
+  let rest = &tokens[i + 4..];
+  match rest {
+    [TokenTree::Group(_), ..] => return None,
+    [TokenTree::Ident(v), TokenTree::Group(_), ..] if v == "files" || v == "file_contents" => {
+      return None
+    }
+    _ => {}
+  }
+
+  // Paths are formed of idents separated by `::`
+  let len = rest
+    .iter()
+    .take_while(|v| match v {
+      TokenTree::Ident(_) => true,
+      TokenTree::Punct(v) => v.as_char() == ':',
+      _ => false,
+    })
+    .count();
+  // Any other expression, such as `any::<u32>()`, is a strategy
+  let is_path = match rest.get(len) {
+    None => true,
+    Some(TokenTree::Punct(v)) => v.as_char() == ',',
+    Some(_) => false,
+  };
+  if len == 0 || !is_path {
+    return None;
+  }
+  Some((rest[..len].iter().cloned().collect(), len))
+}
+
+/// Maps the arguments of each `#[neotest]` or `#[neotest_suite]` attribute
+/// within an item, including the attributes of nested items.
+///
+/// # Arguments
+///
+/// * `item` - the item to map the attributes of
+/// * `f` - the function that maps the arguments of each attribute
+fn map_attribute_args(
+  item: TokenStream,
+  f: &mut dyn FnMut(TokenStream) -> TokenStream,
+) -> TokenStream {
+  let tokens: Vec<TokenTree> = item.into_iter().collect();
+  let mut result = TokenStream::new();
+  for (i, token) in tokens.iter().enumerate() {
+    let group = match token {
+      TokenTree::Group(group) => group,
+      token => {
+        token.to_tokens(&mut result);
+        continue;
+      }
+    };
+    let is_attribute = group.delimiter() == Delimiter::Bracket
+      && matches!(
+        i.checked_sub(1).map(|i| &tokens[i]),
+        Some(TokenTree::Punct(v)) if v.as_char() == '#' && v.spacing() == Spacing::Alone
+      );
+    let stream = match group.delimiter() {
+      Delimiter::Bracket if is_attribute => map_attribute(group.stream(), f),
+      Delimiter::Brace => map_attribute_args(group.stream(), f),
+      _ => group.stream(),
+    };
+    let mut mapped = Group::new(group.delimiter(), stream);
+    mapped.set_span(group.span());
+    mapped.to_tokens(&mut result);
+  }
+  result
+}
+
+/// Maps the arguments of a single attribute, if it is either `#[neotest]` or
+/// `#[neotest_suite]`.
+///
+/// # Arguments
+///
+/// * `attribute` - the tokens within the brackets of the attribute
+/// * `f` - the function that maps the arguments of the attribute
+fn map_attribute(
+  attribute: TokenStream,
+  f: &mut dyn FnMut(TokenStream) -> TokenStream,
+) -> TokenStream {
+  let mut tokens: Vec<TokenTree> = attribute.into_iter().collect();
+  let is_neotest = matches!(
+    tokens.iter().rev().nth(1),
+    Some(TokenTree::Ident(v)) if v == "neotest" || v == "neotest_suite"
+  );
+  match tokens.last_mut() {
+    Some(TokenTree::Group(group)) if is_neotest && group.delimiter() == Delimiter::Parenthesis => {
+      let mut mapped = Group::new(Delimiter::Parenthesis, f(group.stream()));
+      mapped.set_span(group.span());
+      *group = mapped;
+    }
+    _ => {}
+  }
+  tokens.into_iter().collect()
+}
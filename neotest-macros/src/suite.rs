@@ -1,7 +1,15 @@
 mod attributes;
+mod case;
+mod data_cases;
+mod dispatch_count;
 mod dispatcher;
 mod dispatcher_call;
+mod exclusion;
 mod executor;
+mod fixture;
+mod fixture_suite;
+mod generics;
+mod module_suite;
 mod parameters;
 mod section_graph;
 mod section_path;
@@ -12,12 +20,28 @@ mod test;
 #[doc(inline)]
 pub use attributes::*;
 #[doc(inline)]
+pub use case::*;
+#[doc(inline)]
+pub use data_cases::*;
+#[doc(inline)]
+pub use dispatch_count::*;
+#[doc(inline)]
 pub use dispatcher::*;
 #[doc(inline)]
 pub use dispatcher_call::*;
 #[doc(inline)]
+pub use exclusion::*;
+#[doc(inline)]
 pub use executor::*;
 #[doc(inline)]
+pub use fixture::*;
+#[doc(inline)]
+pub use fixture_suite::*;
+#[doc(inline)]
+pub use generics::*;
+#[doc(inline)]
+pub use module_suite::*;
+#[doc(inline)]
 pub use parameters::*;
 #[doc(inline)]
 pub use section_graph::*;
@@ -0,0 +1,227 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens, TokenStreamExt};
+use syn::ext::IdentExt;
+use syn::{parse_quote, Block, ItemFn};
+
+use crate::common::{ident, path, ty};
+
+use super::{TestDispatcher, TestGenerics, TestParameters};
+
+/// A single concrete invocation of the test dispatcher.
+///
+/// Each test-case is a function defined alongside the test itself, so that the
+/// generic arguments and parameter expressions are resolved in the same scope
+/// that the test was written in -- rather than from within the generated
+/// executor modules, whose names may shadow them (e.g. an executor named `u32`).
+///
+/// This will tokenize into a function definition in the form of:
+///
+/// ```ignore
+/// fn __neotest_test_name_case_0(__context: ::neotest_common::__internal::__Context)
+///   -> ::neotest_common::TestResult
+/// {
+///   test_name_dispatcher::<u32>(param0, param1, __context)
+/// }
+/// ```
+///
+/// Test-cases with generated parameters instead run the dispatcher once for
+/// every generated input, and report each of these inputs that failed:
+///
+/// ```ignore
+/// fn __neotest_test_name_case_0(__context: ::neotest_common::__internal::__Context)
+///   -> ::neotest_common::TestResult
+/// {
+///   let mut __neotest_cases = ::neotest_common::__internal::__GeneratedCases::new();
+///   for __neotest_param_a in ::std::iter::IntoIterator::into_iter(gen_cases()) {
+///     __neotest_cases.run(::std::format!("a = {:?}", &__neotest_param_a), || {
+///       test_name_dispatcher(__neotest_param_a, param1, __context.clone())
+///     });
+///   }
+///   __neotest_cases.finish()
+/// }
+/// ```
+///
+/// Test-cases with property parameters instead run the dispatcher against the
+/// values generated by the strategy of each parameter:
+///
+/// ```ignore
+/// fn __neotest_test_name_case_0(__context: ::neotest_common::__internal::__Context)
+///   -> ::neotest_common::TestResult
+/// {
+///   ::neotest_common::__internal::__Property::new(::neotest_common::prop::DEFAULT_CASES).run(
+///     (any::<u32>(),),
+///     |(__neotest_param_a,)| ::std::format!("a = {:?}", __neotest_param_a),
+///     |(__neotest_param_a,)| test_name_dispatcher(__neotest_param_a, __context.clone()),
+///   )
+/// }
+/// ```
+pub struct TestCase {
+  ident: syn::Ident,
+  generics: TestGenerics,
+  params: TestParameters,
+}
+
+impl TestCase {
+  /// Constructs a new [`TestCase`]
+  ///
+  /// # Arguments
+  ///
+  /// * `ident` - the name of the test-case function
+  /// * `generics` - the generic arguments to dispatch with
+  /// * `params` - the parameters to dispatch with
+  pub fn new(ident: syn::Ident, generics: TestGenerics, params: TestParameters) -> Self {
+    Self {
+      ident,
+      generics,
+      params,
+    }
+  }
+
+  /// Returns the name of the test-case function
+  pub fn case_name(&self) -> &syn::Ident {
+    &self.ident
+  }
+
+  pub fn to_tokens_with_call(&self, tokens: &mut TokenStream, dispatcher: &TestDispatcher) {
+    let name = &self.ident;
+    let context_ident = ident::context();
+    let context_ty = ty::context();
+    let output = ty::test_result();
+    let dispatcher_name = dispatcher.dispatcher_name();
+    let generics = &self.generics;
+
+    let mut args = TokenStream::new();
+    let body: Block = if !self.params.generated().is_empty() {
+      let context: syn::Expr = parse_quote!(#context_ident.clone());
+      self.params.to_tokens_with_context(&mut args, &context);
+      self.generated_cases(quote! { #dispatcher_name #generics (#args) }, dispatcher)
+    } else if !self.params.properties().is_empty() {
+      let context: syn::Expr = parse_quote!(#context_ident.clone());
+      self.params.to_tokens_with_context(&mut args, &context);
+      self.property_cases(quote! { #dispatcher_name #generics (#args) }, dispatcher)
+    } else {
+      self
+        .params
+        .to_tokens_with_context(&mut args, &context_ident);
+      parse_quote! {{ #dispatcher_name #generics (#args) }}
+    };
+
+    let item: ItemFn = parse_quote! {
+      fn #name(#context_ident: #context_ty) #output #body
+    };
+    tokens.append_all(dispatcher.attrs().iter());
+    // Inputs that are configured out may name items that do not exist
+    let cfgs = self
+      .params
+      .attrs()
+      .iter()
+      .filter(|v| v.path.is_ident("cfg"));
+    tokens.append_all(cfgs);
+    item.to_tokens(tokens);
+  }
+
+  /// Creates the body of a test-case that runs the `call` to the dispatcher
+  /// for every combination of the inputs of its generated parameters.
+  ///
+  /// The test-case only counts as a single use of each module fixture, so a
+  /// use is reserved for each generated case before it is dispatched, and the
+  /// counted use is released once all cases have run.
+  ///
+  /// # Arguments
+  ///
+  /// * `call` - the call to the dispatcher
+  /// * `dispatcher` - the dispatcher being called
+  fn generated_cases(&self, call: TokenStream, dispatcher: &TestDispatcher) -> Block {
+    let internal = path::crate_internal();
+    let module_fixtures = dispatcher.module_fixtures();
+    let test_name = dispatcher.test_name();
+    let cases = ident::generated_cases();
+    let generated = self.params.generated();
+    let params: Vec<syn::Ident> = generated
+      .iter()
+      .map(|v| ident::new_generated_parameter(&v.ident))
+      .collect();
+    let format = generated
+      .iter()
+      .map(|v| format!("{} = {{:?}}", v.ident.unraw()))
+      .collect::<Vec<_>>()
+      .join(", ");
+
+    let mut body = quote! {
+      #(#internal::__retain_module_fixture(&#module_fixtures);)*
+      #cases.run(::std::format!(#format, #(&#params),*), || #call);
+    };
+    let mut inputs = TokenStream::new();
+    if let [input] = generated {
+      let source = &input.source;
+      let param = &params[0];
+      body = quote! {
+        for #param in ::std::iter::IntoIterator::into_iter(#source) { #body }
+      };
+    } else {
+      // Inputs are only generated once, regardless of how they are combined
+      for (input, param) in generated.iter().zip(params.iter()).rev() {
+        let source = &input.source;
+        let values = ident::new_generated_inputs(&input.ident);
+        inputs = quote! {
+          let #values: ::std::vec::Vec<_> = ::std::iter::IntoIterator::into_iter(#source).collect();
+          #inputs
+        };
+        body = quote! {
+          for #param in #values.iter() { #body }
+        };
+      }
+    }
+    parse_quote! {{
+      let mut #cases = #internal::__GeneratedCases::new();
+      #inputs
+      #body
+      #cases.finish()
+      #(.and(#internal::__skip_module_fixture(&#module_fixtures, #test_name)))*
+    }}
+  }
+
+  /// Creates the body of a test-case that runs the `call` to the dispatcher
+  /// against the values generated by the strategy of each of its property
+  /// parameters.
+  ///
+  /// Module fixtures are reserved and released in the same way as for the
+  /// cases of generated parameters.
+  ///
+  /// # Arguments
+  ///
+  /// * `call` - the call to the dispatcher
+  /// * `dispatcher` - the dispatcher being called
+  fn property_cases(&self, call: TokenStream, dispatcher: &TestDispatcher) -> Block {
+    let internal = path::crate_internal();
+    let module_fixtures = dispatcher.module_fixtures();
+    let test_name = dispatcher.test_name();
+    let properties = self.params.properties();
+    let strategies = properties.iter().map(|v| &v.strategy);
+    let params: Vec<syn::Ident> = properties
+      .iter()
+      .map(|v| ident::new_generated_parameter(&v.ident))
+      .collect();
+    let format = properties
+      .iter()
+      .map(|v| format!("{} = {{:?}}", v.ident.unraw()))
+      .collect::<Vec<_>>()
+      .join(", ");
+    let cases = match self.params.cases() {
+      Some(cases) => quote!(#cases),
+      None => quote!(::neotest_common::prop::DEFAULT_CASES),
+    };
+
+    parse_quote! {{
+      #internal::__Property::new(#cases).run(
+        (#(#strategies,)*),
+        |(#(#params,)*)| ::std::format!(#format, #(#params),*),
+        |(#(#params,)*)| {
+          #(#internal::__retain_module_fixture(&#module_fixtures);)*
+          #call
+        },
+      )
+      #(.and(#internal::__skip_module_fixture(&#module_fixtures, #test_name)))*
+    }}
+  }
+}
@@ -0,0 +1,210 @@
+use std::path::PathBuf;
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::punctuated::Punctuated;
+use syn::{parse_quote, Expr, ExprArray, FnArg, Signature, Type};
+
+use crate::common::path;
+use crate::input::{ParameterInput, TestInputs, ZipInput};
+use crate::syn_utils::TryIdent;
+
+/// The test-cases of a test that are read from a data file, given by the
+/// `cases_from` input.
+///
+/// Each column of the file provides the inputs of the parameter of the same
+/// name, and all columns are zipped together so that each row of the file
+/// forms a single test-case.
+///
+/// This tokenizes into an inclusion of the file, so that the test is rebuilt
+/// whenever the file changes:
+///
+/// ```ignore
+/// const _: &[u8] = include_bytes!("/path/to/crate/tests/data/parse.csv");
+/// ```
+pub struct DataCases {
+  file: PathBuf,
+}
+
+impl DataCases {
+  /// Reads the file of test-cases of the test, if it has one, and adds the
+  /// parameter input of each of its columns to the test `inputs`.
+  ///
+  /// Values are parsed with [`FromStr`] as the type of their parameter when
+  /// the test is run -- except for `&str` parameters, which are given the
+  /// values as they are. Values of primitive types are also checked when the
+  /// test is expanded, so that invalid values are reported as compile errors.
+  ///
+  /// # Arguments
+  ///
+  /// * `inputs` - the test inputs
+  /// * `sig` - the signature of the test
+  ///
+  /// [`FromStr`]: std::str::FromStr
+  pub fn apply(inputs: &mut TestInputs, sig: &Signature) -> syn::Result<Option<Self>> {
+    let cases_from = match inputs.cases_from.take() {
+      Some(cases_from) => cases_from,
+      None => return Ok(None),
+    };
+    let table = cases_from.read()?;
+    let path = cases_from.path.value();
+
+    let mut zip = ZipInput {
+      idents: Punctuated::new(),
+    };
+    for (index, column) in table.columns.iter().enumerate() {
+      let arg = sig.inputs.iter().find_map(|arg| match arg {
+        FnArg::Typed(pat_type) => arg
+          .try_ident()
+          .filter(|ident| ident.unraw() == column)
+          .map(|ident| (ident, &*pat_type.ty)),
+        FnArg::Receiver(_) => None,
+      });
+      let (ident, ty) = match arg {
+        Some(arg) => arg,
+        None => {
+          return Err(cases_from.error(
+            table.header_line,
+            format!("column '{column}' does not name a parameter of the test"),
+          ))
+        }
+      };
+      if inputs.is_parameter(ident) {
+        return Err(cases_from.error(
+          table.header_line,
+          format!("column '{column}' names a parameter that is already given inputs"),
+        ));
+      }
+
+      let mut param = ParameterInput {
+        ident: ident.clone(),
+        inputs: ExprArray {
+          attrs: Vec::new(),
+          bracket_token: Default::default(),
+          elems: Punctuated::new(),
+        },
+        labels: Vec::with_capacity(table.rows.len()),
+        attrs: vec![Vec::new(); table.rows.len()],
+      };
+      for row in table.rows.iter() {
+        let value = &row.values[index];
+        let location = format!("{path}:{}", row.line);
+        let expr = Self::value_expr(ty, value, column, &location)
+          .map_err(|message| cases_from.error(row.line, message))?;
+        param.inputs.elems.push(expr);
+        param.labels.push(Self::label(value));
+      }
+      inputs.parameters.push(param);
+      zip.idents.push(ident.clone());
+    }
+    if zip.idents.len() > 1 {
+      inputs.zips.push(zip);
+    }
+
+    Ok(Some(Self {
+      file: cases_from.file_path()?,
+    }))
+  }
+
+  /// Creates the expression of a single value of a column, which is used as
+  /// the specified type.
+  ///
+  /// # Arguments
+  ///
+  /// * `ty` - the type of the parameter of the column
+  /// * `value` - the value to use
+  /// * `column` - the name of the column
+  /// * `location` - the file and line of the value
+  fn value_expr(ty: &Type, value: &str, column: &str, location: &str) -> Result<Expr, String> {
+    let internal = path::crate_internal();
+    match ty {
+      Type::Reference(reference) if Self::primitive_name(&reference.elem) == Some("str") => {
+        Ok(parse_quote!(#value))
+      }
+      Type::Reference(reference) => {
+        let expr = Self::value_expr(&reference.elem, value, column, location)?;
+        Ok(parse_quote!(&#expr))
+      }
+      Type::Paren(paren) => Self::value_expr(&paren.elem, value, column, location),
+      ty => {
+        if !Self::is_valid_primitive(ty, value) {
+          let ty = ty.to_token_stream();
+          return Err(format!(
+            "'{value}' is not a valid `{ty}` for column '{column}'"
+          ));
+        }
+        Ok(parse_quote!(#internal::__parse_case::<#ty>(#value, #column, #location)))
+      }
+    }
+  }
+
+  /// Tests whether the value is valid for the type, if the type is a
+  /// primitive whose values can be checked when the test is expanded.
+  ///
+  /// The pointer width of the target is not known when the test is expanded,
+  /// so `isize` and `usize` values are only checked to be within the range of
+  /// `i64` and `u64`; values that do not fit a narrower target instead fail
+  /// when the test-case is run.
+  ///
+  /// # Arguments
+  ///
+  /// * `ty` - the type of the value
+  /// * `value` - the value to check
+  fn is_valid_primitive(ty: &Type, value: &str) -> bool {
+    match Self::primitive_name(ty) {
+      Some("bool") => value.parse::<bool>().is_ok(),
+      Some("char") => value.parse::<char>().is_ok(),
+      Some("f32") => value.parse::<f32>().is_ok(),
+      Some("f64") => value.parse::<f64>().is_ok(),
+      Some("i8") => value.parse::<i8>().is_ok(),
+      Some("i16") => value.parse::<i16>().is_ok(),
+      Some("i32") => value.parse::<i32>().is_ok(),
+      Some("i64") => value.parse::<i64>().is_ok(),
+      Some("i128") => value.parse::<i128>().is_ok(),
+      Some("isize") => value.parse::<i64>().is_ok(),
+      Some("u8") => value.parse::<u8>().is_ok(),
+      Some("u16") => value.parse::<u16>().is_ok(),
+      Some("u32") => value.parse::<u32>().is_ok(),
+      Some("u64") => value.parse::<u64>().is_ok(),
+      Some("u128") => value.parse::<u128>().is_ok(),
+      Some("usize") => value.parse::<u64>().is_ok(),
+      _ => true,
+    }
+  }
+
+  /// Returns the name of the type if it is a primitive type
+  fn primitive_name(ty: &Type) -> Option<&'static str> {
+    const PRIMITIVES: [&str; 17] = [
+      "bool", "char", "str", "f32", "f64", "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16",
+      "u32", "u64", "u128", "usize",
+    ];
+    let ident = match ty {
+      Type::Path(path) if path.qself.is_none() => path.path.get_ident()?,
+      _ => return None,
+    };
+    PRIMITIVES.iter().copied().find(|v| ident == v)
+  }
+
+  /// Returns the label of a value, which names the test-cases that use it, if
+  /// the value is valid within an identifier.
+  ///
+  /// # Arguments
+  ///
+  /// * `value` - the value to label
+  fn label(value: &str) -> Option<String> {
+    let is_valid =
+      !value.is_empty() && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    is_valid.then(|| value.to_string())
+  }
+}
+
+impl ToTokens for DataCases {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    let file = self.file.to_string_lossy();
+    quote! {
+      const _: &[u8] = ::std::include_bytes!(#file);
+    }
+    .to_tokens(tokens);
+  }
+}
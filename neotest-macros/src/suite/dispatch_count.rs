@@ -0,0 +1,121 @@
+use std::iter::Sum;
+use std::ops::{Add, AddAssign};
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::Attribute;
+
+/// The number of times that the test-cases of a test are dispatched to, which
+/// is the number of times that the test uses each of its module fixtures.
+///
+/// Test-cases whose inputs are given `#[cfg]` attributes are only dispatched
+/// to when they are compiled, and so are only counted under those same
+/// predicates. This will tokenize into a constant expression in the form of:
+///
+/// ```ignore
+/// 4usize + if ::std::cfg!(all(unix)) { 1usize } else { 0 }
+/// ```
+#[derive(Clone, Default)]
+pub struct DispatchCount {
+  count: usize,
+  conditional: Vec<(Vec<TokenStream>, usize)>,
+}
+
+impl DispatchCount {
+  /// Constructs a new [`DispatchCount`] of test-cases that are always
+  /// dispatched to.
+  ///
+  /// # Arguments
+  ///
+  /// * `count` - the number of dispatches
+  pub fn new(count: usize) -> Self {
+    Self {
+      count,
+      conditional: Vec::new(),
+    }
+  }
+
+  /// Makes every dispatch of this count conditional on the predicates of the
+  /// `#[cfg]` attributes within `attrs`.
+  ///
+  /// # Arguments
+  ///
+  /// * `attrs` - the attributes of the test-cases being dispatched to
+  pub fn with_cfgs<'a, I>(self, attrs: I) -> Self
+  where
+    I: IntoIterator<Item = &'a Attribute>,
+  {
+    let predicates: Vec<TokenStream> = attrs
+      .into_iter()
+      .filter(|v| v.path.is_ident("cfg"))
+      .filter_map(|v| v.parse_args().ok())
+      .collect();
+    if predicates.is_empty() {
+      return self;
+    }
+    let mut result = Self::default();
+    result.push_conditional(predicates.clone(), self.count);
+    for (inner, count) in self.conditional {
+      let mut predicates = predicates.clone();
+      predicates.extend(inner);
+      result.push_conditional(predicates, count);
+    }
+    result
+  }
+
+  /// Adds dispatches that are conditional on all of the `predicates`,
+  /// merging them with any others of the same predicates.
+  ///
+  /// # Arguments
+  ///
+  /// * `predicates` - the predicates of the dispatches
+  /// * `count` - the number of dispatches
+  fn push_conditional(&mut self, predicates: Vec<TokenStream>, count: usize) {
+    if count == 0 {
+      return;
+    }
+    let key = quote!(#(#predicates),*).to_string();
+    let existing = self
+      .conditional
+      .iter_mut()
+      .find(|(v, _)| quote!(#(#v),*).to_string() == key);
+    match existing {
+      Some((_, existing)) => *existing += count,
+      None => self.conditional.push((predicates, count)),
+    }
+  }
+}
+
+impl AddAssign for DispatchCount {
+  fn add_assign(&mut self, rhs: Self) {
+    self.count += rhs.count;
+    for (predicates, count) in rhs.conditional {
+      self.push_conditional(predicates, count);
+    }
+  }
+}
+
+impl Add for DispatchCount {
+  type Output = Self;
+
+  fn add(mut self, rhs: Self) -> Self {
+    self += rhs;
+    self
+  }
+}
+
+impl Sum for DispatchCount {
+  fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+    iter.fold(Self::default(), Add::add)
+  }
+}
+
+impl ToTokens for DispatchCount {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    let count = self.count;
+    let conditional = self.conditional.iter().map(|(predicates, count)| {
+      quote! { + if ::std::cfg!(all(#(#predicates),*)) { #count } else { 0 } }
+    });
+    quote! { #count #(#conditional)* }.to_tokens(tokens);
+  }
+}
@@ -1,36 +1,53 @@
-use proc_macro2::{Span, TokenStream};
-use quote::{ToTokens, TokenStreamExt};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens, TokenStreamExt};
 use syn::punctuated::Punctuated;
 use syn::token::Comma;
-use syn::{parse_quote, Attribute, FnArg, Signature, Stmt};
+use syn::{
+  parse_quote, Attribute, Expr, FnArg, GenericParam, Generics, ItemFn, Meta, Path, Signature, Stmt,
+};
 
-use crate::common::ident;
+use crate::common::{ident, path};
+use crate::input::FixtureScope;
 use crate::syn_utils::{
-  FunctionCall, FunctionDefinition, ResolveFnArg, ResolveFnArgDecl, TryIdent,
+  FunctionCall, FunctionDefinition, ResolveFnArg, ResolveFnArgDecl, TryIdent, Turbofish,
 };
 
-use super::test::Test;
-
-struct FixtureData {
-  ident: syn::Ident,
-  arg: FnArg,
-}
+use super::{Test, TestExclusion, TestFixture};
 
+/// The function that prepares the fixtures of a test, calls it, and tears the
+/// fixtures down afterwards.
+///
+/// The dispatcher of an `async fn` test is itself synchronous, and runs all of
+/// this on the executor of the test -- which is a simple built-in executor
+/// unless one is specified with `executor = <attribute>`.
+///
+/// Test-cases that are excluded by the `exclude` input of the test return from
+/// the dispatcher before any fixtures are prepared.
 pub struct TestDispatcher {
+  test_name: String,
   attrs: Vec<Attribute>,
   sig: Signature,
-  fixture: Option<FixtureData>,
+  fixtures: Vec<TestFixture>,
+  is_async: bool,
+  executor: Option<Meta>,
+  exclusion: Option<TestExclusion>,
 }
 
 impl TestDispatcher {
-  pub fn new(test: &Test, fixture: Option<syn::Ident>) -> Self {
+  pub fn new(
+    test: &Test,
+    fixtures: Vec<TestFixture>,
+    executor: Option<Meta>,
+    exclusion: Option<TestExclusion>,
+  ) -> Self {
     Self {
+      test_name: test.name().to_string(),
       attrs: test.attrs().into(),
-      sig: Self::dispatcher_signature(test.signature().clone(), fixture.as_ref()),
-      fixture: fixture.map(|v| FixtureData {
-        ident: v,
-        arg: test.signature().inputs.first().unwrap().clone(),
-      }),
+      sig: Self::dispatcher_signature(test.signature().clone(), &fixtures),
+      fixtures,
+      is_async: test.signature().asyncness.is_some(),
+      executor,
+      exclusion,
     }
   }
 
@@ -38,69 +55,342 @@ impl TestDispatcher {
     &self.sig.ident
   }
 
-  fn dispatcher_signature(mut sig: Signature, fixture: Option<&syn::Ident>) -> Signature {
+  /// Returns the fixtures that are prepared by the dispatcher
+  pub fn fixtures(&self) -> &[TestFixture] {
+    &self.fixtures
+  }
+
+  /// Returns the name of the test, as written by the user
+  pub fn test_name(&self) -> &str {
+    &self.test_name
+  }
+
+  /// Returns the idents of the statics that hold each module fixture that is
+  /// prepared by the dispatcher
+  pub fn module_fixtures(&self) -> Vec<syn::Ident> {
+    self
+      .fixtures
+      .iter()
+      .filter(|v| v.scope() == FixtureScope::Module)
+      .map(|v| ident::new_module_fixture(v.ty()))
+      .collect()
+  }
+
+  /// Returns the attributes that are applied to the dispatcher
+  pub fn attrs(&self) -> &[Attribute] {
+    &self.attrs
+  }
+
+  fn dispatcher_signature(mut sig: Signature, fixtures: &[TestFixture]) -> Signature {
     sig.ident = ident::new_test_dispatch(&sig.ident);
-    sig.inputs = Self::dispatcher_arguments(sig.inputs, fixture);
+    sig.asyncness = None;
+    sig.inputs = Self::dispatcher_arguments(sig.inputs, fixtures);
     sig
   }
 
+  /// Removes all fixture arguments from the inputs, since these are prepared
+  /// by the dispatcher rather than being provided to it.
   fn dispatcher_arguments(
     inputs: Punctuated<FnArg, Comma>,
-    fixture: Option<&syn::Ident>,
+    fixtures: &[TestFixture],
   ) -> Punctuated<FnArg, Comma> {
-    if fixture.is_some() {
-      // TODO(mrodusek): can this be done more easily? It feels like we should be
-      // able to just swap a FnArg from the Punctuated.
-      let mut new_inputs: Punctuated<FnArg, Comma> = Default::default();
-      new_inputs.extend(inputs.into_iter().skip(1));
-      new_inputs
-    } else {
-      inputs
-    }
+    inputs
+      .into_iter()
+      .enumerate()
+      .filter(|(i, _)| !fixtures.iter().any(|v| v.index() == *i))
+      .map(|(_, v)| v)
+      .collect()
   }
 }
 
 impl TestDispatcher {
   pub fn to_tokens_with_call(&self, tokens: &mut TokenStream, test: &Test) {
-    const FIXTURE_NAME: &str = "__fixture";
-    let fixture_ident = syn::Ident::new(FIXTURE_NAME, Span::call_site());
+    let generic_idents = Self::generic_idents(&self.sig.generics);
+    let callee = test.callee(Turbofish(&generic_idents));
 
     // Define the test function
     tokens.append_all(self.attrs.iter());
     FunctionDefinition(&self.sig).surround(tokens, |tokens| {
-      self.prepend_fixture(&fixture_ident, tokens);
-      FunctionCall(test.test_name()).arguments(tokens, |tokens| {
-        self.prepend_fixture_arg(&fixture_ident, tokens);
-        for arg in self.sig.inputs.iter() {
-          arg.try_ident().to_tokens(tokens);
+      if let Some(exclusion) = &self.exclusion {
+        exclusion.to_tokens_with_check(&self.module_fixtures(), &self.test_name, tokens);
+      }
+      let mut body = TokenStream::new();
+      self.prepare_fixtures(test, &mut body);
+
+      let mut call = TokenStream::new();
+      FunctionCall(&callee).arguments(&mut call, |tokens| {
+        for (i, arg) in test.signature().inputs.iter().enumerate() {
+          match self.fixtures.iter().find(|v| v.index() == i) {
+            // Suite fixtures are already prepared as references
+            Some(fixture) if fixture.scope() == FixtureScope::Suite => {
+              fixture.ident().to_tokens(tokens)
+            }
+            Some(fixture) => ResolveFnArg::new(&fixture.ident(), arg).to_tokens(tokens),
+            None => arg.try_ident().to_tokens(tokens),
+          }
           Comma::default().to_tokens(tokens);
         }
-      })
+      });
+      self.append_call(test, call, &mut body);
+      if self.is_async {
+        self.block_on(body, tokens);
+      } else {
+        body.to_tokens(tokens);
+      }
     });
   }
 
-  fn prepend_fixture(&self, fixture_ident: &syn::Ident, tokens: &mut TokenStream) {
-    match &self.fixture {
-      Some(v) => {
-        let ident = &v.ident;
-        let fixture_arg = &v.arg;
-        let resolve_arg_decl = ResolveFnArgDecl::new(fixture_ident, fixture_arg);
+  /// Runs the body of the dispatcher of an `async fn` test to completion on
+  /// the executor of the test.
+  ///
+  /// # Arguments
+  ///
+  /// * `body` - the statements of the dispatcher, which may use `.await`
+  /// * `tokens` - the token-stream to write to
+  fn block_on(&self, body: TokenStream, tokens: &mut TokenStream) {
+    let block_on: Path = match &self.executor {
+      Some(executor) => {
+        let block_on = ident::block_on();
+        let define: ItemFn = parse_quote! {
+          #[#executor]
+          async fn #block_on<F: ::std::future::Future>(future: F) -> F::Output {
+            future.await
+          }
+        };
+        define.to_tokens(tokens);
+        block_on.into()
+      }
+      None => {
+        let internal = path::crate_internal();
+        parse_quote!(#internal::__block_on)
+      }
+    };
+    quote! { #block_on(async move { #body }) }.to_tokens(tokens);
+  }
 
-        let block: Stmt = parse_quote! {
-          let #resolve_arg_decl = #ident::prepare().unwrap();
+  /// Appends the call to the test, which tears down each fixture afterwards
+  /// that the test only borrows, and releases each module fixture.
+  ///
+  /// Fixtures that are owned by the test cannot be torn down, since the test
+  /// is free to consume them; and suite fixtures are shared with other tests.
+  /// Fixtures are torn down in the reverse order that they were prepared in.
+  ///
+  /// # Arguments
+  ///
+  /// * `test` - the test being called
+  /// * `call` - the expression calling the test
+  /// * `tokens` - the token-stream to write to
+  fn append_call(&self, test: &Test, call: TokenStream, tokens: &mut TokenStream) {
+    let internal = path::crate_internal();
+    let test_name = test.name().to_string();
+    let result_ident = format_ident!("__result");
+    let outcome_ident = format_ident!("__outcome");
+    let finishers: Vec<Stmt> = self
+      .fixtures
+      .iter()
+      .rev()
+      .filter_map(|v| {
+        let fixture_ident = v.ident();
+        let finish: Expr = match v.scope() {
+          FixtureScope::Test if !ResolveFnArg::new(&fixture_ident, v.arg()).is_reference() => {
+            return None
+          }
+          FixtureScope::Test if v.is_parameterized() => {
+            let ty = v.ty();
+            let params_ty = v.params_ty();
+            parse_quote! {
+              #internal::__tear_down_with(
+                #fixture_ident,
+                <#ty as ::neotest_common::ParameterizedFixture<#params_ty>>::tear_down,
+                #test_name,
+                &#outcome_ident,
+                #result_ident,
+              )
+            }
+          }
+          FixtureScope::Test if self.is_async => parse_quote! {
+            #internal::__tear_down_async(#fixture_ident, #test_name, &#outcome_ident, #result_ident)
+              .await
+          },
+          FixtureScope::Test => parse_quote! {
+            #internal::__tear_down(#fixture_ident, #test_name, &#outcome_ident, #result_ident)
+          },
+          FixtureScope::Module => parse_quote! {
+            #internal::__release(#fixture_ident, #test_name, &#outcome_ident, #result_ident)
+          },
+          FixtureScope::Suite => return None,
         };
-        block.to_tokens(tokens);
+        Some(parse_quote!(let #result_ident = #finish;))
+      })
+      .collect();
+
+    if finishers.is_empty() {
+      if self.is_async {
+        quote! { #call.await }.to_tokens(tokens);
+      } else {
+        call.to_tokens(tokens);
       }
-      None => {}
+      return;
     }
+    // The context is moved into the test, so whether it expects to panic is
+    // read before calling it
+    let context_ident = ident::context();
+    let expects_panic_ident = format_ident!("__expects_panic");
+    let define: Stmt = parse_quote! {
+      let #expects_panic_ident = #context_ident.expects_panic();
+    };
+    define.to_tokens(tokens);
+    let define: Stmt = if self.is_async {
+      parse_quote! {
+        let #result_ident = #internal::__catch_unwind(#call).await;
+      }
+    } else {
+      parse_quote! {
+        let #result_ident = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| #call));
+      }
+    };
+    define.to_tokens(tokens);
+    let define: Stmt = parse_quote! {
+      let #outcome_ident = #internal::__outcome(&#result_ident, #expects_panic_ident);
+    };
+    define.to_tokens(tokens);
+    tokens.append_all(finishers);
+    let conclude: Expr = parse_quote! {
+      #internal::__conclude(#result_ident)
+    };
+    conclude.to_tokens(tokens);
+  }
+
+  /// Returns the identifiers of all generic parameters that need to be
+  /// forwarded to the test through a turbofish.
+  ///
+  /// Lifetimes are omitted, since these are always inferred.
+  ///
+  /// # Arguments
+  ///
+  /// * `generics` - the generics of the dispatcher
+  fn generic_idents(generics: &Generics) -> Vec<syn::Ident> {
+    generics
+      .params
+      .iter()
+      .filter(|v| !matches!(v, GenericParam::Lifetime(_)))
+      .filter_map(TryIdent::try_ident)
+      .cloned()
+      .collect()
   }
 
-  fn prepend_fixture_arg(&self, fixture_ident: &syn::Ident, tokens: &mut TokenStream) {
-    if let Some(fixture) = &self.fixture {
-      let fixture_arg = &fixture.arg;
-      let resolve_arg = ResolveFnArg::new(fixture_ident, fixture_arg);
-      resolve_arg.to_tokens(tokens);
-      Comma::default().to_tokens(tokens);
+  /// Prepares each fixture of the test, in the order that they are listed.
+  ///
+  /// Test-scoped fixtures that are torn down are held by a guard until every
+  /// fixture has been prepared, so that they are still torn down -- in reverse
+  /// order -- if a later fixture fails to be prepared. Fixtures of `async fn`
+  /// tests cannot be torn down by a guard, and so are instead torn down before
+  /// the setup error is returned.
+  ///
+  /// # Arguments
+  ///
+  /// * `test` - the test being called
+  /// * `tokens` - the token-stream to write to
+  fn prepare_fixtures(&self, test: &Test, tokens: &mut TokenStream) {
+    let internal = path::crate_internal();
+    let test_name = test.name().to_string();
+    // The fixtures prepared so far that must be torn down if the test is abandoned
+    let mut prepared: Vec<PreparedFixture> = Vec::new();
+    for fixture in self.fixtures.iter() {
+      let ty = fixture.ty();
+      let fixture_ident = fixture.ident();
+      let resolve_arg_decl = ResolveFnArgDecl::new(&fixture_ident, fixture.arg());
+      let is_torn_down = matches!(fixture.scope(), FixtureScope::Test)
+        && ResolveFnArg::new(&fixture_ident, fixture.arg()).is_reference();
+      let guard = format_ident!("__guard_{}", fixture.index());
+      let (prepare, tear_down): (Expr, Option<Expr>) = match fixture.scope() {
+        FixtureScope::Test if fixture.is_parameterized() => {
+          let params_ty = fixture.params_ty();
+          let params = fixture.params_expr();
+          (
+            parse_quote!(<#ty as ::neotest_common::ParameterizedFixture<#params_ty>>::prepare_with(#params)),
+            Some(
+              parse_quote!(<#ty as ::neotest_common::ParameterizedFixture<#params_ty>>::tear_down),
+            ),
+          )
+        }
+        FixtureScope::Test if self.is_async => (
+          parse_quote!(<#ty as ::neotest_common::AsyncFixture>::prepare().await),
+          None,
+        ),
+        FixtureScope::Test => (
+          parse_quote!(<#ty as ::neotest_common::Fixture>::prepare()),
+          Some(parse_quote!(<#ty as ::neotest_common::Fixture>::tear_down)),
+        ),
+        FixtureScope::Suite => (parse_quote!(#internal::__shared::<#ty>()), None),
+        FixtureScope::Module => {
+          let module_fixture = ident::new_module_fixture(ty);
+          (
+            parse_quote!(#internal::__module_fixture(&#module_fixture)),
+            None,
+          )
+        }
+      };
+
+      // Preparation failures are reported as errors of the test, rather than
+      // panicking, so that they can be distinguished from test failures.
+      let setup_error: Expr = parse_quote! {
+        ::neotest_common::SetupError::new(::std::any::type_name::<#ty>(), #test_name, e)
+      };
+      let value: Expr = if self.is_async && !prepared.is_empty() {
+        let abandoned = prepared.iter().rev().map(|v| match v {
+          PreparedFixture::Async(ident) => quote! {
+            #internal::__abandon_async(#ident, #test_name).await;
+          },
+          PreparedFixture::Guarded(guard, _) => quote! { ::std::mem::drop(#guard); },
+        });
+        parse_quote! {
+          match #prepare {
+            ::std::result::Result::Ok(v) => v,
+            ::std::result::Result::Err(e) => {
+              #(#abandoned)*
+              return ::std::result::Result::Err(::std::convert::From::from(#setup_error));
+            }
+          }
+        }
+      } else {
+        parse_quote!(#prepare.map_err(|e| #setup_error)?)
+      };
+
+      let block: Stmt = match (is_torn_down, tear_down) {
+        (true, Some(tear_down)) => {
+          let block = parse_quote! {
+            let #guard = #internal::__FixtureGuard::new(#value, #tear_down, #test_name);
+          };
+          let decl = quote!(#resolve_arg_decl);
+          prepared.push(PreparedFixture::Guarded(guard, decl));
+          block
+        }
+        (true, None) => {
+          prepared.push(PreparedFixture::Async(fixture_ident.clone()));
+          parse_quote!(let #resolve_arg_decl = #value;)
+        }
+        (false, _) => parse_quote!(let #resolve_arg_decl = #value;),
+      };
+      block.to_tokens(tokens);
+    }
+
+    // Every fixture was prepared, so the test is now responsible for them
+    for fixture in prepared {
+      if let PreparedFixture::Guarded(guard, decl) = fixture {
+        let release: Stmt = parse_quote!(let #decl = #guard.into_inner(););
+        release.to_tokens(tokens);
+      }
     }
   }
 }
+
+/// A fixture that has been prepared for a test, which must be torn down if
+/// the test is abandoned before it runs.
+enum PreparedFixture {
+  /// A fixture held by the guard of the ident, along with the declaration of
+  /// the variable that it is released to.
+  Guarded(syn::Ident, TokenStream),
+  /// A fixture of an `async fn` test, held by the variable of the ident
+  Async(syn::Ident),
+}
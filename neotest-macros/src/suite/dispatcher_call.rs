@@ -9,60 +9,76 @@ use syn::{
 use crate::common::{ident, ty};
 use crate::syn_utils::FunctionCall;
 
-use crate::suite::{SectionPath, TestDispatcher, TestParameters};
+use crate::suite::SectionPath;
 
-/// A representation of a call to the underlying test dispatch function
+/// A representation of a call to the underlying test-case function
 ///
 /// This will tokenize with [`ToTokens`] into a stream of:
 ///
 /// ```ignore
 /// let __context = ::neotest_common::__internal::__Context::path([&[p0,p1]]);
-/// super::__neotest_test_dispatch(v0, v1, __context)
+/// super::__neotest_test_case_0(__context)
 /// ```
 ///
 /// Where `super::` is prepended as many times as its needed for the proper scope.
 pub struct DispatcherCall {
-  parameters: TestParameters,
+  case: syn::Ident,
   section_path: SectionPath,
   depth: usize,
+  expects_panic: bool,
 }
 
-impl Default for DispatcherCall {
-  fn default() -> Self {
+impl DispatcherCall {
+  /// Constructs a new [`DispatcherCall`] that invokes the specified test-case
+  /// from an executor at the specified module `depth`.
+  ///
+  /// # Arguments
+  ///
+  /// * `case` - the name of the test-case function to call
+  /// * `depth` - the number of modules deep that the call is made from
+  pub fn new(case: syn::Ident, depth: usize) -> Self {
     Self {
-      parameters: TestParameters::new(Default::default()),
+      case,
       section_path: Default::default(),
-      depth: 0,
+      depth,
+      expects_panic: false,
     }
   }
-}
 
-impl DispatcherCall {
-  pub fn new(parameters: TestParameters) -> Self {
+  /// Produces the same call for a test-case that is expected to panic, so
+  /// that its fixtures are torn down as passing when it does.
+  pub fn expecting_panic(&self) -> Self {
     Self {
-      parameters,
-      section_path: Default::default(),
-      depth: 1, // Parameters always start at depth 1
+      case: self.case.clone(),
+      section_path: self.section_path.clone(),
+      depth: self.depth,
+      expects_panic: true,
     }
   }
 
   pub fn subsection(&self, subtest: usize) -> Self {
     Self {
-      parameters: self.parameters.clone(),
+      case: self.case.clone(),
       section_path: self.section_path.subsection(subtest),
       depth: self.depth + 1,
+      expects_panic: self.expects_panic,
     }
   }
 }
 
-impl DispatcherCall {
-  pub fn to_tokens_with_call(&self, tokens: &mut TokenStream, dispatch: &TestDispatcher) {
+impl ToTokens for DispatcherCall {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
     let context_ident = ident::context();
     let context_ty = ty::context();
     let section_path = &self.section_path;
 
-    let define: Stmt = parse_quote! {
-      let #context_ident = #context_ty::path(#section_path);
+    let define: Stmt = match self.expects_panic {
+      true => parse_quote! {
+        let #context_ident = #context_ty::path(#section_path).expecting_panic();
+      },
+      false => parse_quote! {
+        let #context_ident = #context_ty::path(#section_path);
+      },
     };
     define.to_tokens(tokens);
 
@@ -70,10 +86,8 @@ impl DispatcherCall {
       Super::default().to_tokens(tokens);
       Colon2::default().to_tokens(tokens);
     }
-    FunctionCall(dispatch.dispatcher_name()).arguments(tokens, |tokens| {
-      self
-        .parameters
-        .to_tokens_with_context(tokens, &context_ident);
+    FunctionCall(&self.case).arguments(tokens, |tokens| {
+      context_ident.to_tokens(tokens);
     });
   }
 }
@@ -0,0 +1,109 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::Expr;
+
+use crate::common::{ident, path};
+use crate::input::ExcludeInput;
+use crate::syn_utils::ConstValue;
+
+/// The predicate of a test that excludes combinations of parameter inputs,
+/// given by the `exclude` input.
+///
+/// Test-cases whose inputs are all literals are decided when the test is
+/// expanded, so that excluded test-cases are reported as ignored and never run
+/// at all. Every other test-case is decided by the dispatcher when it is run --
+/// before any of its fixtures are prepared -- and so is reported as passing:
+///
+/// ```ignore
+/// let __excluded: bool = {
+///   let a = &a;
+///   let b = &b;
+///   a == b
+/// };
+/// if __excluded {
+///   return ::neotest_common::__internal::__filter_case();
+/// }
+/// ```
+#[derive(Clone)]
+pub struct TestExclusion {
+  input: ExcludeInput,
+}
+
+impl TestExclusion {
+  /// Constructs a new [`TestExclusion`] from the `exclude` input of a test
+  ///
+  /// # Arguments
+  ///
+  /// * `input` - the predicate of the test
+  pub fn new(input: ExcludeInput) -> Self {
+    Self { input }
+  }
+
+  /// Tests whether the test-case with the specified parameter inputs is known
+  /// to be excluded when the test is expanded.
+  ///
+  /// This is only the case if the predicate can be evaluated from the literal
+  /// inputs of the test-case; otherwise, the test-case is decided when it is
+  /// run.
+  ///
+  /// # Arguments
+  ///
+  /// * `params` - the input of each parameter of the test-case
+  pub fn excludes(&self, params: &[(&syn::Ident, &Expr)]) -> bool {
+    let args: Vec<(&syn::Ident, Option<ConstValue>)> = self
+      .input
+      .args()
+      .map(|(_, ident)| {
+        let value = params
+          .iter()
+          .find(|(v, _)| *v == ident)
+          .and_then(|(_, expr)| ConstValue::from_literal(expr));
+        (ident, value)
+      })
+      .collect();
+    let lookup = |ident: &syn::Ident| {
+      args
+        .iter()
+        .find(|(v, _)| *v == ident)
+        .and_then(|(_, value)| value.clone())
+    };
+    ConstValue::eval(self.input.body(), &lookup) == Some(ConstValue::Bool(true))
+  }
+
+  /// Writes the check that returns from the dispatcher early if its test-case
+  /// is excluded.
+  ///
+  /// Each module fixture used by the test is still released, since the
+  /// test-case counts as one of its uses.
+  ///
+  /// # Arguments
+  ///
+  /// * `module_fixtures` - the module fixtures used by the test
+  /// * `test_name` - the name of the test
+  /// * `tokens` - the token-stream to write to
+  pub fn to_tokens_with_check(
+    &self,
+    module_fixtures: &[syn::Ident],
+    test_name: &str,
+    tokens: &mut TokenStream,
+  ) {
+    let internal = path::crate_internal();
+    let excluded = ident::excluded();
+    let (pats, idents): (Vec<_>, Vec<_>) = self.input.args().unzip();
+    let body = self.input.body();
+    quote! {
+      let #excluded: bool = {
+        #(
+          #[allow(unused_variables)]
+          let #pats = &#idents;
+        )*
+        #body
+      };
+      if #excluded {
+        #(#internal::__skip_module_fixture(&#module_fixtures, #test_name)?;)*
+        return #internal::__filter_case();
+      }
+    }
+    .to_tokens(tokens);
+  }
+}
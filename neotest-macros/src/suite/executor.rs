@@ -1,52 +1,110 @@
+use std::time::Duration;
+
 use proc_macro2::TokenStream;
-use quote::ToTokens;
-use syn::{parse_quote, Signature, Stmt, VisPublic, Visibility};
+use quote::{ToTokens, TokenStreamExt};
+use syn::{parse_quote, Attribute, Expr, ReturnType, Signature, Stmt, VisPublic, Visibility};
 
-use crate::common::ty;
+use crate::common::{attribute, path, ty};
 use crate::suite::TestAttributes;
 use crate::syn_utils::{FunctionDefinition, ModuleDefinition};
 
-use super::{DispatcherCall, Test, TestDispatcher, TestParameters};
+use super::{DispatchCount, DispatcherCall, Test, TestCase};
 
 /// The name of a test executor function.
 ///
 /// This is a function that will act as an entry-point into other tests.
+/// Executors either dispatch to a single test-case, or group other executors
+/// together -- in which case invoking the executor invokes each of them.
 /// This will evaluate into a function definition in the form of:
 ///
 /// ```ignore
 /// #[test]
 /// /* other attributes */
 /// fn test_name() {
-///   test_name::sub_test_1();
+///   test_name::sub_test_1()?;
 ///   /* any sub tests or input tests */
+///   Ok(())
 /// }
 /// mod test_name {
 ///   use super::*;
-///   fn sub_test_1() {
-///      let __context = ::neotest_common::__internal::__Context::path(&[]);
-///      __neotest_test_name_dispatch(param0, param1, __context);
+///   pub fn sub_test_1() {
+///     ::neotest_common::__internal::__run_with_timeout("test_name::sub_test_1", None, || {
+///       let __context = ::neotest_common::__internal::__Context::path(&[]);
+///       super::__neotest_test_name_case_0(__context)
+///     })
 ///   }
 ///   mod sub_test_1 {}
 /// }
 /// ```
+///
+/// Executors of test-cases whose inputs have attributes are given those
+/// attributes. Parents only invoke these executors under the same `#[cfg]`
+/// attributes, and never invoke executors that are `#[ignore]`d or that
+/// `#[should_panic]` -- where the latter instead return `()`:
+///
+/// ```ignore
+/// #[test]
+/// #[should_panic]
+/// pub fn a_0() {
+///   ::neotest_common::__internal::__expect_panic(
+///     ::neotest_common::__internal::__run_with_timeout("test_name::a_0", None, || {
+///       /* ... */
+///     }),
+///   )
+/// }
+/// ```
+///
+/// Executors of test-cases that are excluded by the `exclude` input of the
+/// test are instead ignored, and never invoked by their parent:
+///
+/// ```ignore
+/// #[test]
+/// #[ignore = "filtered by `exclude`"]
+/// pub fn a_0_b_0() -> ::neotest_common::TestResult {
+///   Ok(())
+/// }
+/// ```
 pub struct TestExecutor {
   attrs: TestAttributes,
   sig: Signature,
-  dispatch_call: DispatcherCall,
+  label: String,
+  timeout: Option<Duration>,
+  depth: usize,
+  dispatch_call: Option<DispatcherCall>,
   subtests: Vec<TestExecutor>,
+  case_attrs: Vec<Attribute>,
+  excluded: bool,
 }
 
 impl TestExecutor {
-  pub fn new(name: syn::Ident, attrs: TestAttributes, params: TestParameters, test: &Test) -> Self {
+  /// Constructs the root [`TestExecutor`] of a test.
+  ///
+  /// The root executor does nothing until it is either given a test-case to
+  /// dispatch to with [`Self::dispatch`], or child executors with
+  /// [`Self::push_input`].
+  ///
+  /// # Arguments
+  ///
+  /// * `name` - the name of the executor
+  /// * `attrs` - the attributes to apply to this and all child executors
+  /// * `test` - the test being executed
+  /// * `timeout` - the timeout of each test-case, if any
+  pub fn new(
+    name: syn::Ident,
+    attrs: TestAttributes,
+    test: &Test,
+    timeout: Option<Duration>,
+  ) -> Self {
     Self {
       attrs,
-      dispatch_call: if params.is_empty() {
-        DispatcherCall::default()
-      } else {
-        DispatcherCall::new(params)
-      },
+      label: name.to_string(),
+      timeout,
       sig: Self::executor_signature(test.signature().clone(), name),
+      depth: 0,
+      dispatch_call: None,
       subtests: Default::default(),
+      case_attrs: Vec::new(),
+      excluded: false,
     }
   }
 
@@ -54,20 +112,92 @@ impl TestExecutor {
     &self.sig.ident
   }
 
+  /// Makes this executor dispatch to the specified test-case.
+  ///
+  /// # Arguments
+  ///
+  /// * `case` - the test-case to dispatch to
+  pub fn dispatch(&mut self, case: &TestCase) {
+    self.dispatch_call = Some(DispatcherCall::new(case.case_name().clone(), self.depth));
+  }
+
+  /// Marks this executor as running a test-case that is excluded by the
+  /// `exclude` input of the test, so that it is ignored rather than run.
+  pub fn exclude(&mut self) {
+    self.excluded = true;
+  }
+
+  /// Applies the attributes of the inputs of the test-case that this executor
+  /// runs, such as `#[ignore]` or `#[cfg(unix)]`, which replace the same
+  /// attributes of the test itself.
+  ///
+  /// # Arguments
+  ///
+  /// * `attrs` - the attributes of the test-case
+  pub fn apply_case_attributes(&mut self, attrs: &[Attribute]) {
+    self.case_attrs = attrs.to_vec();
+  }
+
+  /// Creates a child executor for a set of test inputs, which will be invoked
+  /// along with this executor.
+  ///
+  /// # Arguments
+  ///
+  /// * `name` - the name of the child executor
+  /// * `f` - a function to set up the child executor
+  pub fn push_input<F>(&mut self, name: syn::Ident, f: F)
+  where
+    F: FnOnce(&mut TestExecutor),
+  {
+    let mut test = Self {
+      attrs: self.attrs.clone(),
+      label: format!("{}::{name}", self.label),
+      timeout: self.timeout,
+      sig: Signature {
+        ident: name,
+        ..self.sig.clone()
+      },
+      depth: self.depth + 1,
+      dispatch_call: None,
+      subtests: Default::default(),
+      case_attrs: Vec::new(),
+      excluded: false,
+    };
+
+    f(&mut test);
+
+    self.subtests.push(test);
+  }
+
   /// Creates a derived subtest with a new index, and also provides
   pub fn push_subtest<F>(&mut self, subsection: usize, name: syn::Ident, f: F)
   where
     F: FnOnce(&mut TestExecutor),
   {
-    let dispatcher = self.dispatch_call.subsection(subsection);
-    let mut new_sig = self.sig.clone();
-    new_sig.ident = name;
-
+    let dispatcher = self
+      .dispatch_call
+      .as_ref()
+      .map(|v| v.subsection(subsection));
     let mut test = Self {
       attrs: self.attrs.clone(),
+      label: format!("{}::{name}", self.label),
+      timeout: self.timeout,
+      sig: Signature {
+        ident: name,
+        ..self.sig.clone()
+      },
+      depth: self.depth + 1,
       dispatch_call: dispatcher,
-      sig: new_sig,
       subtests: Default::default(),
+      // Subtests are defined within the module of this executor, which is
+      // already configured by its `#[cfg]` attributes
+      case_attrs: self
+        .case_attrs
+        .iter()
+        .filter(|v| !v.path.is_ident("cfg"))
+        .cloned()
+        .collect(),
+      excluded: false,
     };
 
     f(&mut test);
@@ -75,15 +205,132 @@ impl TestExecutor {
     self.subtests.push(test);
   }
 
-  pub fn to_tokens_with_call(&self, tokens: &mut TokenStream, dispatcher: &TestDispatcher) {
-    self.attrs.to_tokens(tokens);
+  /// Returns the number of times that the test-cases are dispatched to when
+  /// every executor in this tree is run as its own test.
+  ///
+  /// Executors that are `#[ignore]`d are only run when the test harness is
+  /// asked to run ignored tests, and so are counted separately.
+  ///
+  /// # Arguments
+  ///
+  /// * `ignored` - whether to count only the executors that are ignored
+  pub fn dispatch_count(&self, ignored: bool) -> DispatchCount {
+    if self.excluded {
+      return DispatchCount::default();
+    }
+    let children: DispatchCount = self
+      .subtests
+      .iter()
+      .map(|v| v.dispatch_count(ignored))
+      .sum();
+    let count = match self.is_ignored() == ignored {
+      true => self.invocation_count(),
+      false => DispatchCount::default(),
+    };
+    count + children.with_cfgs(self.cfgs())
+  }
 
-    Visibility::Public(VisPublic {
-      pub_token: Default::default(),
-    })
-    .to_tokens(tokens);
-    FunctionDefinition(&self.sig).surround(tokens, |tokens| {
-      self.dispatch_call.to_tokens_with_call(tokens, dispatcher);
+  /// Returns the number of times that the test-cases are dispatched to when
+  /// only this executor is run.
+  fn invocation_count(&self) -> DispatchCount {
+    let count = match &self.dispatch_call {
+      Some(_) => DispatchCount::new(1),
+      None => self
+        .subtests
+        .iter()
+        .filter(|v| v.is_invoked())
+        .map(Self::invocation_count)
+        .sum(),
+    };
+    count.with_cfgs(self.cfgs())
+  }
+
+  /// Tests whether this executor is invoked by its parent, in addition to
+  /// being run as its own test.
+  ///
+  /// Test-cases that are excluded or `#[ignore]`d are not run by default, and
+  /// test-cases that `#[should_panic]` would otherwise fail their parent.
+  fn is_invoked(&self) -> bool {
+    !self.excluded && !self.has_case_attribute("ignore") && !self.expects_panic()
+  }
+
+  /// Tests whether this executor is `#[ignore]`d, either by its test or by
+  /// its test-case.
+  fn is_ignored(&self) -> bool {
+    self.has_case_attribute("ignore") || self.attrs.iter().any(|v| v.path.is_ident("ignore"))
+  }
+
+  /// Tests whether the test-case of this executor is expected to panic.
+  fn expects_panic(&self) -> bool {
+    self.has_case_attribute("should_panic")
+  }
+
+  /// Tests whether the test-case of this executor has the attribute `name`.
+  ///
+  /// # Arguments
+  ///
+  /// * `name` - the name of the attribute
+  fn has_case_attribute(&self, name: &str) -> bool {
+    self.case_attrs.iter().any(|v| v.path.is_ident(name))
+  }
+
+  /// Returns the `#[cfg]` attributes of the test-case of this executor.
+  fn cfgs(&self) -> impl Iterator<Item = &Attribute> {
+    self.case_attrs.iter().filter(|v| v.path.is_ident("cfg"))
+  }
+
+  fn is_root(&self) -> bool {
+    self.depth == 0
+  }
+}
+
+impl ToTokens for TestExecutor {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    if self.excluded {
+      self.to_tokens_excluded(tokens);
+      return;
+    }
+    self.to_tokens_attrs(tokens);
+    attribute::allow_non_snake_case().to_tokens(tokens);
+
+    // Nested executors must be visible to the executor that invokes them.
+    if !self.is_root() {
+      Visibility::Public(VisPublic {
+        pub_token: Default::default(),
+      })
+      .to_tokens(tokens);
+    }
+    // The test harness requires tests that `#[should_panic]` to return `()`
+    let sig = match self.expects_panic() {
+      true => Signature {
+        output: ReturnType::Default,
+        ..self.sig.clone()
+      },
+      false => self.sig.clone(),
+    };
+    FunctionDefinition(&sig).surround(tokens, |tokens| match &self.dispatch_call {
+      Some(dispatch_call) if self.expects_panic() => {
+        let internal = path::crate_internal();
+        let run = self.run_with_timeout(&dispatch_call.expecting_panic());
+        let expect: Expr = parse_quote!(#internal::__expect_panic(#run));
+        expect.to_tokens(tokens);
+      }
+      Some(dispatch_call) => self.run_with_timeout(dispatch_call).to_tokens(tokens),
+      None => {
+        let scope = &self.sig.ident;
+        for subtest in self.subtests.iter().filter(|v| v.is_invoked()) {
+          let test_name = subtest.executor_name();
+          let cfgs = subtest.cfgs();
+
+          let invoke_stmt: Stmt = parse_quote! { #(#cfgs)* #scope::#test_name()?; };
+          invoke_stmt.to_tokens(tokens);
+        }
+        let ok_expr: Expr = parse_quote! {
+          #[allow(unreachable_code)]
+          Ok(())
+        };
+        ok_expr.to_tokens(tokens);
+      }
     });
 
     // Only define submodules when we have subtests to run
@@ -92,19 +339,97 @@ impl TestExecutor {
     }
 
     // Define all subtests inside of the module definition
+    tokens.append_all(self.cfgs());
     ModuleDefinition(&self.sig.ident).surround(tokens, |tokens| {
+      attribute::allow_unused_imports().to_tokens(tokens);
       let use_stmt: Stmt = parse_quote! { use super::*; };
       use_stmt.to_tokens(tokens);
 
       for subtest in self.subtests.iter() {
-        subtest.to_tokens_with_call(tokens, dispatcher);
+        subtest.to_tokens(tokens);
       }
     });
   }
+}
+
+impl TestExecutor {
+  /// Writes the attributes of this executor, where the attributes of its
+  /// test-case replace the same attributes of the test.
+  ///
+  /// # Arguments
+  ///
+  /// * `tokens` - the token-stream to write to
+  fn to_tokens_attrs(&self, tokens: &mut TokenStream) {
+    let attrs = self.attrs.iter().filter(|v| {
+      let is_replaced = self
+        .case_attrs
+        .iter()
+        .any(|case| case.path.get_ident() == v.path.get_ident() && !case.path.is_ident("cfg"));
+      !is_replaced
+    });
+    tokens.append_all(attrs);
+    tokens.append_all(self.case_attrs.iter());
+  }
+
+  /// Writes the executor of an excluded test-case, which is ignored with the
+  /// reason it was filtered.
+  ///
+  /// Attributes that would change the outcome of running the test-case, such
+  /// as `#[should_panic]`, are omitted since the test-case does nothing.
+  ///
+  /// # Arguments
+  ///
+  /// * `tokens` - the token-stream to write to
+  fn to_tokens_excluded(&self, tokens: &mut TokenStream) {
+    let attrs = self
+      .attrs
+      .iter()
+      .filter(|v| !v.path.is_ident("ignore") && !v.path.is_ident("should_panic"));
+    tokens.append_all(attrs);
+    tokens.append_all(self.cfgs());
+    attribute::allow_non_snake_case().to_tokens(tokens);
+    let ignore: Attribute = parse_quote!(#[ignore = "filtered by `exclude`"]);
+    ignore.to_tokens(tokens);
+    Visibility::Public(VisPublic {
+      pub_token: Default::default(),
+    })
+    .to_tokens(tokens);
+    FunctionDefinition(&self.sig).surround(tokens, |tokens| {
+      let ok_expr: Expr = parse_quote!(Ok(()));
+      ok_expr.to_tokens(tokens);
+    });
+  }
+
+  /// Creates the dispatch to the test-case, run under a watchdog that fails
+  /// the test-case if it exceeds its timeout.
+  ///
+  /// Test-cases without a timeout still use the global default timeout, if one
+  /// is set when the tests are run.
+  ///
+  /// # Arguments
+  ///
+  /// * `dispatch_call` - the call that dispatches to the test-case
+  fn run_with_timeout(&self, dispatch_call: &DispatcherCall) -> Expr {
+    let internal = path::crate_internal();
+    let label = &self.label;
+    let timeout: Expr = match self.timeout {
+      Some(timeout) => {
+        let secs = timeout.as_secs();
+        let nanos = timeout.subsec_nanos();
+        parse_quote!(::std::option::Option::Some(::std::time::Duration::new(#secs, #nanos)))
+      }
+      None => parse_quote!(::std::option::Option::None),
+    };
+    parse_quote! {
+      #internal::__run_with_timeout(#label, #timeout, || { #dispatch_call })
+    }
+  }
 
   fn executor_signature(mut sig: Signature, ident: syn::Ident) -> Signature {
     sig.ident = ident;
+    sig.asyncness = None;
     sig.inputs.clear();
+    sig.generics = Default::default();
     sig.output = ty::test_result();
     sig
   }
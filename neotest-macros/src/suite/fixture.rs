@@ -0,0 +1,262 @@
+use quote::{format_ident, ToTokens};
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+use syn::{parse_quote, Expr, FnArg, Signature, Type};
+
+use crate::input::{FixtureScope, TestInputs};
+use crate::syn_utils::TryIdent;
+
+/// A single fixture that is prepared by the [`TestDispatcher`] and injected
+/// into the test as one of its arguments.
+///
+/// Any argument of a test that is not bound to a `parameter` input is
+/// considered to be a fixture, and is prepared from the type that it refers
+/// to -- so both `f: &Fixture` and `f: Fixture` prepare a `Fixture`. For tests
+/// defined in a [`neotest_fixture`], the `self` receiver is the fixture.
+///
+/// Fixtures named with `fixture(scope = suite)` are instead prepared once and
+/// shared by every test in the binary, and fixtures named with
+/// `fixture(scope = module)` are shared by every test in the module; and so
+/// both must be taken as `&Fixture`.
+///
+/// Fixtures named with parameters, such as `fixture = Fixture(backend)`, are
+/// instead prepared as a [`ParameterizedFixture`] from the values of those
+/// parameters for each test-case.
+///
+/// [`TestDispatcher`]: crate::suite::TestDispatcher
+/// [`neotest_fixture`]: crate::neotest_fixture
+/// [`ParameterizedFixture`]: neotest_common::ParameterizedFixture
+#[derive(Clone)]
+pub struct TestFixture {
+  index: usize,
+  ty: Type,
+  arg: FnArg,
+  scope: FixtureScope,
+  params: Vec<(syn::Ident, Type)>,
+}
+
+impl TestFixture {
+  /// Resolves every fixture argument of the test signature, in the order that
+  /// they appear.
+  ///
+  /// Each fixture named by a `fixture` input must match the type of one of the
+  /// injected arguments, and may only be prepared from `parameter` inputs.
+  ///
+  /// # Arguments
+  ///
+  /// * `inputs` - the test inputs passed to the attribute
+  /// * `sig` - the signature of the test
+  /// * `self_ty` - the type of the fixture the test is a method of, if any
+  pub fn resolve(
+    inputs: &TestInputs,
+    sig: &Signature,
+    self_ty: Option<&Type>,
+  ) -> syn::Result<Vec<TestFixture>> {
+    let mut result: Vec<TestFixture> = Vec::new();
+    for (index, arg) in sig.inputs.iter().enumerate() {
+      let ty = match arg {
+        FnArg::Receiver(_) => match self_ty {
+          Some(ty) => ty.clone(),
+          None => continue,
+        },
+        FnArg::Typed(pat_type) => {
+          let is_parameter = arg
+            .try_ident()
+            .map(|ident| inputs.is_parameter(ident))
+            .unwrap_or(false);
+          if is_parameter {
+            continue;
+          }
+          match &*pat_type.ty {
+            Type::Reference(reference) => (*reference.elem).clone(),
+            ty => ty.clone(),
+          }
+        }
+      };
+      result.push(TestFixture {
+        index,
+        ty,
+        arg: arg.clone(),
+        scope: FixtureScope::Test,
+        params: Vec::new(),
+      });
+    }
+
+    for (i, fixture) in inputs.fixtures.iter().enumerate() {
+      let name = fixture.ty.to_token_stream().to_string();
+      if inputs.fixtures[..i]
+        .iter()
+        .any(|v| v.ty.to_token_stream().to_string() == name)
+      {
+        return Err(syn::Error::new_spanned(
+          &fixture.ty,
+          format!("Test fixture '{name}' specified more than once."),
+        ));
+      }
+      let mut matches = result
+        .iter_mut()
+        .filter(|v| Self::is_type(&v.ty, &fixture.ty))
+        .peekable();
+      if matches.peek().is_none() {
+        return Err(syn::Error::new_spanned(
+          &fixture.ty,
+          format!("Test fixture '{name}' does not match the type of any test function parameter."),
+        ));
+      }
+      let params = Self::resolve_params(inputs, sig, &fixture.params)?;
+      for matched in matches {
+        matched.ty = fixture.ty.clone();
+        matched.scope = fixture.scope;
+        matched.params = params.clone();
+        matched.validate_scope(&name)?;
+      }
+    }
+    Ok(result)
+  }
+
+  /// Resolves the type of each parameter that a fixture is prepared from.
+  ///
+  /// # Arguments
+  ///
+  /// * `inputs` - the test inputs passed to the attribute
+  /// * `sig` - the signature of the test
+  /// * `params` - the names of the parameters
+  fn resolve_params(
+    inputs: &TestInputs,
+    sig: &Signature,
+    params: &[syn::Ident],
+  ) -> syn::Result<Vec<(syn::Ident, Type)>> {
+    params
+      .iter()
+      .map(|param| {
+        let is_parameter = inputs.is_parameter(param);
+        let ty = sig.inputs.iter().find_map(|arg| match arg {
+          FnArg::Typed(pat_type) if arg.try_ident() == Some(param) => Some(&*pat_type.ty),
+          _ => None,
+        });
+        match ty.filter(|_| is_parameter) {
+          Some(ty) => Ok((param.clone(), ty.clone())),
+          None => Err(syn::Error::new(
+            param.span(),
+            format!("Fixture parameter '{param}' does not name a test parameter."),
+          )),
+        }
+      })
+      .collect()
+  }
+
+  /// Returns the index of the argument that this fixture is injected into
+  pub fn index(&self) -> usize {
+    self.index
+  }
+
+  /// Returns the type of the fixture being prepared
+  pub fn ty(&self) -> &Type {
+    &self.ty
+  }
+
+  /// Returns the argument that this fixture is injected into
+  pub fn arg(&self) -> &FnArg {
+    &self.arg
+  }
+
+  /// Returns the scope that the fixture is prepared for
+  pub fn scope(&self) -> FixtureScope {
+    self.scope
+  }
+
+  /// Returns whether the fixture is prepared from parameters of the test
+  pub fn is_parameterized(&self) -> bool {
+    !self.params.is_empty()
+  }
+
+  /// Returns the type of the parameters that the fixture is prepared from;
+  /// either the type of the single parameter, or a tuple of every parameter.
+  pub fn params_ty(&self) -> Type {
+    match &self.params[..] {
+      [(_, ty)] => ty.clone(),
+      params => {
+        let types: Punctuated<&Type, Comma> = params.iter().map(|(_, ty)| ty).collect();
+        parse_quote!((#types,))
+      }
+    }
+  }
+
+  /// Returns the expression that clones the parameters that the fixture is
+  /// prepared from, in the same shape as [`TestFixture::params_ty`].
+  pub fn params_expr(&self) -> Expr {
+    match &self.params[..] {
+      [(ident, _)] => parse_quote!(::std::clone::Clone::clone(&#ident)),
+      params => {
+        let values: Punctuated<Expr, Comma> = params
+          .iter()
+          .map(|(ident, _)| -> Expr { parse_quote!(::std::clone::Clone::clone(&#ident)) })
+          .collect();
+        parse_quote!((#values,))
+      }
+    }
+  }
+
+  /// Returns the name of the variable that holds the prepared fixture
+  pub fn ident(&self) -> syn::Ident {
+    format_ident!("__fixture_{}", self.index)
+  }
+
+  /// Validates that the argument is able to receive a fixture of this scope.
+  ///
+  /// Suite and module fixtures are shared between tests, and so can only be
+  /// received by shared reference.
+  ///
+  /// # Arguments
+  ///
+  /// * `name` - the name of the fixture
+  fn validate_scope(&self, name: &str) -> syn::Result<()> {
+    let is_shared_ref = match &self.arg {
+      FnArg::Typed(pat_type) => {
+        matches!(&*pat_type.ty, Type::Reference(v) if v.mutability.is_none())
+      }
+      FnArg::Receiver(_) => false,
+    };
+    if self.scope != FixtureScope::Test && self.is_parameterized() {
+      return Err(syn::Error::new_spanned(
+        &self.arg,
+        format!("Test fixture '{name}' can only be prepared from parameters in the test scope."),
+      ));
+    }
+    let scope = match self.scope {
+      FixtureScope::Test => return Ok(()),
+      FixtureScope::Suite => "Suite",
+      FixtureScope::Module => "Module",
+    };
+    if !is_shared_ref {
+      return Err(syn::Error::new_spanned(
+        &self.arg,
+        format!(
+          "{scope}-scoped test fixture '{name}' must be taken by shared reference, e.g. `&{name}`."
+        ),
+      ));
+    }
+    Ok(())
+  }
+
+  /// Checks whether the specified argument type is the named fixture type.
+  ///
+  /// Paths are compared by their final segment, so that a fixture may be named
+  /// by a different path than the one used in the test signature -- e.g.
+  /// `support::Harness<T>` matches an argument of `&Harness<T>`.
+  ///
+  /// # Arguments
+  ///
+  /// * `ty` - the type of the argument
+  /// * `fixture` - the named fixture type
+  pub fn is_type(ty: &Type, fixture: &Type) -> bool {
+    match (ty, fixture) {
+      (Type::Path(lhs), Type::Path(rhs)) if lhs.qself.is_none() && rhs.qself.is_none() => {
+        let lhs = lhs.path.segments.last().map(ToTokens::to_token_stream);
+        let rhs = rhs.path.segments.last().map(ToTokens::to_token_stream);
+        lhs.map(|v| v.to_string()) == rhs.map(|v| v.to_string())
+      }
+      _ => ty.to_token_stream().to_string() == fixture.to_token_stream().to_string(),
+    }
+  }
+}
@@ -0,0 +1,115 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, TokenStreamExt};
+use syn::{Attribute, ImplItem, ImplItemMethod, ItemFn, ItemImpl};
+
+use crate::input::TestInputs;
+
+use super::TestSuite;
+
+/// The set of test-suites defined by the `#[neotest]` methods of a
+/// [`neotest_fixture`] impl block.
+///
+/// This will tokenize into the impl block, without any of the test methods,
+/// followed by the test-suites of each of the test methods.
+///
+/// [`neotest_fixture`]: crate::neotest_fixture
+pub struct FixtureSuite {
+  item: ItemImpl,
+  suites: Vec<TestSuite>,
+}
+
+impl FixtureSuite {
+  /// Constructs a [`FixtureSuite`] by extracting all `#[neotest]` methods from
+  /// the specified impl block.
+  ///
+  /// # Arguments
+  ///
+  /// * `item` - the impl block of the fixture
+  pub fn from_impl(mut item: ItemImpl) -> syn::Result<Self> {
+    Self::validate(&item)?;
+
+    let mut suites: Vec<TestSuite> = Vec::new();
+    let mut items: Vec<ImplItem> = Vec::with_capacity(item.items.len());
+    for impl_item in std::mem::take(&mut item.items) {
+      match impl_item {
+        ImplItem::Method(method) if Self::is_test(&method) => {
+          let (inputs, test_fn) = Self::extract_test(method)?;
+          suites.push(TestSuite::from_method(inputs, test_fn, &item.self_ty)?);
+        }
+        impl_item => items.push(impl_item),
+      }
+    }
+    item.items = items;
+
+    Ok(Self { item, suites })
+  }
+
+  /// Tests whether the specified method is annotated with `#[neotest]`
+  ///
+  /// # Arguments
+  ///
+  /// * `method` - the method to test
+  fn is_test(method: &ImplItemMethod) -> bool {
+    method.attrs.iter().any(Self::is_test_attribute)
+  }
+
+  /// Tests whether the specified attribute is the `#[neotest]` attribute,
+  /// including by its path -- e.g. `#[neotest::neotest]`.
+  ///
+  /// # Arguments
+  ///
+  /// * `attr` - the attribute to test
+  pub(super) fn is_test_attribute(attr: &Attribute) -> bool {
+    attr.path.segments.last().map(|v| v.ident == "neotest") == Some(true)
+  }
+
+  /// Converts a test method into the [`TestInputs`] of its `#[neotest]`
+  /// attribute, and the function definition without that attribute.
+  ///
+  /// # Arguments
+  ///
+  /// * `method` - the test method
+  fn extract_test(method: ImplItemMethod) -> syn::Result<(TestInputs, ItemFn)> {
+    let (tests, attrs): (Vec<Attribute>, Vec<Attribute>) =
+      method.attrs.into_iter().partition(Self::is_test_attribute);
+
+    if let Some(attr) = tests.get(1) {
+      return Err(syn::Error::new_spanned(
+        attr,
+        "#[neotest] attribute can only be specified once",
+      ));
+    }
+    let inputs = TestInputs::from_attribute(&tests[0])?;
+
+    let test_fn = ItemFn {
+      attrs,
+      vis: method.vis,
+      sig: method.sig,
+      block: Box::new(method.block),
+    };
+    Ok((inputs, test_fn))
+  }
+
+  fn validate(item: &ItemImpl) -> syn::Result<()> {
+    if let Some((_, path, _)) = &item.trait_ {
+      return Err(syn::Error::new_spanned(
+        path,
+        "#[neotest_fixture] cannot be applied to trait implementations",
+      ));
+    }
+    if !item.generics.params.is_empty() {
+      return Err(syn::Error::new_spanned(
+        &item.generics,
+        "#[neotest_fixture] cannot be applied to generic impl blocks",
+      ));
+    }
+    Ok(())
+  }
+}
+
+impl ToTokens for FixtureSuite {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    self.item.to_tokens(tokens);
+    tokens.append_all(self.suites.iter());
+  }
+}
@@ -0,0 +1,224 @@
+use proc_macro2::Span;
+use quote::ToTokens;
+use syn::{Expr, GenericArgument, GenericParam, Generics, Type};
+
+use std::rc::Rc;
+
+use crate::common::ident::{self, sanitize};
+use crate::input::TestInputs;
+use crate::syn_utils::Turbofish;
+
+/// A collection of generic arguments to provide to a given [`TestDispatcher`]
+/// invocation.
+///
+/// This tokenizes as a turbofish of the generic arguments, e.g. `::<u32, 4>`,
+/// or as nothing if there are no generic arguments.
+///
+/// [`TestDispatcher`]: crate::suite::TestDispatcher
+#[derive(Clone, Default)]
+pub struct TestGenerics {
+  args: Rc<Vec<GenericArgument>>,
+}
+
+impl TestGenerics {
+  /// Creates a new [`TestGenerics`] from the set of generic arguments.
+  ///
+  /// # Arguments
+  ///
+  /// * `args` - the generic arguments, in the order they are declared
+  pub fn new(args: Vec<GenericArgument>) -> Self {
+    Self {
+      args: Rc::new(args),
+    }
+  }
+
+  /// Produces every combination of the generic inputs, along with the name of
+  /// the executor that will be generated for it.
+  ///
+  /// Generic inputs are combined in the order that the generics are declared in
+  /// the test signature. If there are no generic inputs, this returns an empty
+  /// vector.
+  ///
+  /// # Arguments
+  ///
+  /// * `inputs` - the test inputs to multiplex
+  /// * `generics` - the generics declared on the test function
+  pub fn multiplex(inputs: &TestInputs, generics: &Generics) -> Vec<(syn::Ident, TestGenerics)> {
+    let test_cases = MultiplexedTestGenerics::multiplex(inputs, generics);
+
+    let mut result: Vec<(syn::Ident, TestGenerics)> = Vec::with_capacity(test_cases.len());
+    for case in test_cases.into_iter() {
+      let ident = ident::new_test_generic_input(&case.names, Span::call_site());
+
+      result.push((ident, TestGenerics::new(case.args)));
+    }
+    result
+  }
+}
+
+impl ToTokens for TestGenerics {
+  fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+    Turbofish(&self.args).to_tokens(tokens)
+  }
+}
+
+/// The set of possible values for a single generic parameter, along with the
+/// names used to identify each value.
+struct GenericInput {
+  ident: syn::Ident,
+  names: Vec<String>,
+  args: Vec<GenericArgument>,
+}
+
+impl GenericInput {
+  /// Finds the input bound to the specified generic parameter, if any.
+  ///
+  /// # Arguments
+  ///
+  /// * `inputs` - the test inputs
+  /// * `param` - the generic parameter to find the input of
+  fn find(inputs: &TestInputs, param: &GenericParam) -> Option<Self> {
+    match param {
+      GenericParam::Type(param) => inputs
+        .type_parameters
+        .iter()
+        .find(|v| v.ident == param.ident)
+        .map(|v| Self::from_types(&v.ident, v.inputs.elems.iter())),
+      GenericParam::Const(param) => inputs
+        .const_parameters
+        .iter()
+        .find(|v| v.ident == param.ident)
+        .map(|v| Self::from_consts(&v.ident, v.inputs.elems.iter())),
+      GenericParam::Lifetime(_) => None,
+    }
+  }
+
+  /// Creates a [`GenericInput`] from a sequence of types.
+  ///
+  /// # Arguments
+  ///
+  /// * `ident` - the ident of the generic parameter
+  /// * `types` - the types to substitute
+  fn from_types<'a>(ident: &syn::Ident, types: impl Iterator<Item = &'a Type>) -> Self {
+    let mut result = Self {
+      ident: ident.clone(),
+      names: Vec::new(),
+      args: Vec::new(),
+    };
+    for (i, ty) in types.enumerate() {
+      result.push(i, type_name(ty), GenericArgument::Type(ty.clone()));
+    }
+    result
+  }
+
+  /// Creates a [`GenericInput`] from a sequence of const expressions.
+  ///
+  /// # Arguments
+  ///
+  /// * `ident` - the ident of the generic parameter
+  /// * `exprs` - the const expressions to substitute
+  fn from_consts<'a>(ident: &syn::Ident, exprs: impl Iterator<Item = &'a Expr>) -> Self {
+    let mut result = Self {
+      ident: ident.clone(),
+      names: Vec::new(),
+      args: Vec::new(),
+    };
+    for (i, expr) in exprs.enumerate() {
+      // Signs are kept, so that `-1` and `1` are named `N_neg_1` and `N_1`
+      let value = expr.to_token_stream().to_string().replace('-', " neg ");
+      let name = format!("{ident}_{}", sanitize(&value));
+      result.push(i, name, GenericArgument::Const(expr.clone()));
+    }
+    result
+  }
+
+  /// Adds a new argument to this input, ensuring that its name is a unique and
+  /// valid identifier.
+  ///
+  /// # Arguments
+  ///
+  /// * `index` - the index of the argument being pushed
+  /// * `name` - the preferred name of the argument
+  /// * `arg` - the generic argument
+  fn push(&mut self, index: usize, name: String, arg: GenericArgument) {
+    let name = if syn::parse_str::<syn::Ident>(&name).is_err() {
+      format!("{}_{index}", self.ident)
+    } else if self.names.contains(&name) {
+      format!("{name}_{index}")
+    } else {
+      name
+    };
+    self.names.push(name);
+    self.args.push(arg);
+  }
+}
+
+#[derive(Default, Clone)]
+struct MultiplexedTestGenerics {
+  names: Vec<String>,
+  args: Vec<GenericArgument>,
+}
+
+impl MultiplexedTestGenerics {
+  /// Creates a vector of [`MultiplexedTestGenerics`] by combinatorially
+  /// producing all combination of generic inputs.
+  ///
+  /// # Arguments
+  ///
+  /// * `inputs` - the test inputs containing generic inputs
+  /// * `generics` - the generics declared on the test function
+  fn multiplex(inputs: &TestInputs, generics: &Generics) -> Vec<Self> {
+    let generic_inputs: Vec<GenericInput> = generics
+      .params
+      .iter()
+      .filter_map(|v| GenericInput::find(inputs, v))
+      .collect();
+
+    let mut result: Vec<Self> = Vec::new();
+    if !generic_inputs.is_empty() {
+      Self::multiplex_into_aux(&mut result, Self::default(), &generic_inputs);
+    }
+    result
+  }
+
+  /// An auxiliary function used for the recursion of generic inputs.
+  ///
+  /// # Arguments
+  ///
+  /// * `result` - the vector to populate
+  /// * `current` - the current state of inputs
+  /// * `generics` - the generic inputs to use for producing combinations
+  fn multiplex_into_aux(result: &mut Vec<Self>, current: Self, generics: &[GenericInput]) {
+    // Recursive base-case: we have finished iterating
+    if generics.is_empty() {
+      result.push(current)
+    } else {
+      let generic = generics.first().unwrap();
+      for (name, arg) in generic.names.iter().zip(generic.args.iter()) {
+        let mut current = current.clone();
+        current.names.push(name.clone());
+        current.args.push(arg.clone());
+        Self::multiplex_into_aux(result, current, &generics[1..])
+      }
+    }
+  }
+}
+
+/// Computes a readable name for the specified type.
+///
+/// Paths are named after their final segment, so that `std::string::String`
+/// becomes `String` and `Vec<u8>` becomes `Vec_u8`. All other types are named
+/// by replacing any non-identifier characters with underscores.
+///
+/// # Arguments
+///
+/// * `ty` - the type to name
+fn type_name(ty: &Type) -> String {
+  match ty {
+    Type::Path(path) if path.qself.is_none() => match path.path.segments.last() {
+      Some(segment) => sanitize(&segment.to_token_stream().to_string()),
+      None => sanitize(&ty.to_token_stream().to_string()),
+    },
+    _ => sanitize(&ty.to_token_stream().to_string()),
+  }
+}
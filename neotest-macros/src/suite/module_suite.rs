@@ -0,0 +1,255 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{parse_quote, Item, ItemFn, ItemMod, Type};
+
+use crate::common::{ident, path};
+use crate::input::{SuiteInputs, TestInputs};
+use crate::syn_utils::ContainsIdent;
+
+use super::{DispatchCount, FixtureSuite, TestFixture, TestSuite};
+
+/// The set of test-suites defined by the `#[neotest]` functions of a
+/// [`neotest_suite`] module.
+///
+/// The inputs of the module are applied as defaults to each test function,
+/// and each module-scoped fixture is defined as a static within the module.
+/// This will tokenize into the module, with each test function replaced by its
+/// test-suite.
+///
+/// [`neotest_suite`]: crate::neotest_suite
+pub struct ModuleSuite {
+  item: ItemMod,
+  suites: Vec<TestSuite>,
+}
+
+impl ModuleSuite {
+  /// Constructs a [`ModuleSuite`] by applying the suite `inputs` to all
+  /// `#[neotest]` functions in the specified module.
+  ///
+  /// # Arguments
+  ///
+  /// * `inputs` - the inputs of the suite
+  /// * `item` - the module containing the tests
+  pub fn from_module(inputs: SuiteInputs, mut item: ItemMod) -> syn::Result<Self> {
+    let content = match &mut item.content {
+      Some((_, content)) => content,
+      None => {
+        return Err(syn::Error::new_spanned(
+          &item,
+          "#[neotest_suite] can only be applied to inline modules",
+        ))
+      }
+    };
+
+    let mut suites: Vec<TestSuite> = Vec::new();
+    let mut items: Vec<Item> = Vec::with_capacity(content.len());
+    for module_item in std::mem::take(content) {
+      match module_item {
+        Item::Fn(test_fn) if test_fn.attrs.iter().any(FixtureSuite::is_test_attribute) => {
+          let (test_inputs, test_fn) = Self::extract_test(&inputs, test_fn)?;
+          suites.push(TestSuite::from_module_inputs(test_inputs, test_fn)?);
+        }
+        module_item => items.push(module_item),
+      }
+    }
+    items.extend(Self::module_fixtures(&suites));
+    *content = items;
+
+    Ok(Self { item, suites })
+  }
+
+  /// Converts a test function into the [`TestInputs`] of its `#[neotest]`
+  /// attribute combined with the suite inputs, and the function definition
+  /// without that attribute.
+  ///
+  /// # Arguments
+  ///
+  /// * `suite` - the inputs of the suite
+  /// * `test_fn` - the test function
+  fn extract_test(suite: &SuiteInputs, mut test_fn: ItemFn) -> syn::Result<(TestInputs, ItemFn)> {
+    let (tests, mut attrs): (Vec<_>, Vec<_>) = std::mem::take(&mut test_fn.attrs)
+      .into_iter()
+      .partition(FixtureSuite::is_test_attribute);
+
+    if let Some(attr) = tests.get(1) {
+      return Err(syn::Error::new_spanned(
+        attr,
+        "#[neotest] attribute can only be specified once",
+      ));
+    }
+    let inputs = TestInputs::from_attribute(&tests[0])?;
+    let inputs = Self::apply_defaults(&suite.inputs, inputs, &test_fn);
+
+    for meta in suite.attributes.iter().rev() {
+      attrs.insert(0, parse_quote!(#[#meta]));
+    }
+    test_fn.attrs = attrs;
+    Ok((inputs, test_fn))
+  }
+
+  /// Applies each of the `defaults` to the test inputs, if the test is able to
+  /// accept it and has not already specified it.
+  ///
+  /// # Arguments
+  ///
+  /// * `defaults` - the default inputs from the suite
+  /// * `inputs` - the inputs of the test
+  /// * `test_fn` - the test function
+  fn apply_defaults(defaults: &TestInputs, mut inputs: TestInputs, test_fn: &ItemFn) -> TestInputs {
+    let sig = &test_fn.sig;
+    for parameter in defaults.parameters.iter() {
+      let is_bound = inputs.is_parameter(&parameter.ident);
+      if !is_bound && sig.inputs.contains_ident(&parameter.ident) {
+        inputs.parameters.push(parameter.clone());
+      }
+    }
+    for parameter in defaults.generated_parameters.iter() {
+      let is_bound = inputs.is_parameter(&parameter.ident);
+      if !is_bound && sig.inputs.contains_ident(&parameter.ident) {
+        inputs.generated_parameters.push(parameter.clone());
+      }
+    }
+    for parameter in defaults.property_parameters.iter() {
+      let is_bound = inputs.is_parameter(&parameter.ident);
+      if !is_bound && sig.inputs.contains_ident(&parameter.ident) {
+        inputs.property_parameters.push(parameter.clone());
+      }
+    }
+    for zip in defaults.zips.iter() {
+      let is_bound = zip
+        .idents
+        .iter()
+        .all(|ident| inputs.parameters.iter().any(|v| v.ident == *ident));
+      let is_zipped = zip
+        .idents
+        .iter()
+        .any(|ident| inputs.zips.iter().any(|v| v.contains(ident)));
+      if is_bound && !is_zipped {
+        inputs.zips.push(zip.clone());
+      }
+    }
+    for parameter in defaults.type_parameters.iter() {
+      let is_bound = inputs
+        .type_parameters
+        .iter()
+        .any(|v| v.ident == parameter.ident);
+      if !is_bound && sig.generics.params.contains_ident(&parameter.ident) {
+        inputs.type_parameters.push(parameter.clone());
+      }
+    }
+    for parameter in defaults.const_parameters.iter() {
+      let is_bound = inputs
+        .const_parameters
+        .iter()
+        .any(|v| v.ident == parameter.ident);
+      if !is_bound && sig.generics.params.contains_ident(&parameter.ident) {
+        inputs.const_parameters.push(parameter.clone());
+      }
+    }
+
+    if inputs.combinations.is_none() {
+      inputs.combinations = defaults.combinations;
+    }
+    // Exclusions are only applied to tests that take every excluded parameter
+    if let (None, Some(exclude)) = (&inputs.exclude, &defaults.exclude) {
+      if exclude.args().all(|(_, ident)| inputs.is_parameter(ident)) {
+        inputs.exclude = Some(exclude.clone());
+      }
+    }
+    if inputs.timeout.is_none() {
+      inputs.timeout = defaults.timeout;
+    }
+    // Cases are only applied to tests with parameters generated by strategies
+    if inputs.cases.is_none() && !inputs.property_parameters.is_empty() {
+      inputs.cases = defaults.cases.clone();
+    }
+
+    // Executors are only applied to async tests
+    if inputs.executor.is_none() && sig.asyncness.is_some() {
+      inputs.executor = defaults.executor.clone();
+    }
+
+    // Fixtures are only applied to tests that take them
+    let injected = TestFixture::resolve(
+      &TestInputs {
+        fixtures: Vec::new(),
+        ..inputs.clone()
+      },
+      sig,
+      None,
+    )
+    .unwrap_or_default();
+    for fixture in defaults.fixtures.iter() {
+      let is_named = inputs
+        .fixtures
+        .iter()
+        .any(|v| TestFixture::is_type(&v.ty, &fixture.ty));
+      let is_taken = injected
+        .iter()
+        .any(|v| TestFixture::is_type(v.ty(), &fixture.ty));
+      if !is_named && is_taken {
+        inputs.fixtures.push(fixture.clone());
+      }
+    }
+    inputs
+  }
+
+  /// Defines the statics holding each module-scoped fixture used by the
+  /// `suites`, which expect to be used once for each time a test uses them.
+  ///
+  /// # Arguments
+  ///
+  /// * `suites` - the test suites of the module
+  fn module_fixtures(suites: &[TestSuite]) -> Vec<Item> {
+    let mut fixtures: Vec<(syn::Ident, &Type, DispatchCount, DispatchCount)> = Vec::new();
+    for suite in suites.iter() {
+      let (uses, ignored_uses) = suite.fixture_uses();
+      for ty in suite.module_fixtures() {
+        let ident = ident::new_module_fixture(ty);
+        match fixtures.iter_mut().find(|(v, _, _, _)| *v == ident) {
+          Some((_, _, existing, existing_ignored)) => {
+            *existing += uses.clone();
+            *existing_ignored += ignored_uses.clone();
+          }
+          None => fixtures.push((ident, ty, uses.clone(), ignored_uses.clone())),
+        }
+      }
+    }
+
+    let internal = path::crate_internal();
+    fixtures
+      .into_iter()
+      .map(|(ident, ty, uses, ignored_uses)| {
+        parse_quote! {
+          #[allow(non_upper_case_globals)]
+          static #ident: #internal::__ModuleFixture<#ty> =
+            #internal::__ModuleFixture::new(#uses, #ignored_uses);
+        }
+      })
+      .collect()
+  }
+}
+
+impl ToTokens for ModuleSuite {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    let ItemMod {
+      attrs,
+      vis,
+      mod_token,
+      ident,
+      content,
+      ..
+    } = &self.item;
+    let items = content.iter().flat_map(|(_, items)| items.iter());
+    let suites = self.suites.iter();
+
+    quote! {
+      #(#attrs)*
+      #vis #mod_token #ident {
+        #(#items)*
+        #(#suites)*
+      }
+    }
+    .to_tokens(tokens);
+  }
+}
@@ -1,18 +1,38 @@
 use proc_macro2::{Span, TokenStream};
 use quote::ToTokens;
+use syn::ext::IdentExt;
 use syn::punctuated::Punctuated;
-use syn::{token::Comma, Expr};
+use syn::{parse_quote, token::Comma, Attribute, Expr, Lit, Signature};
 
+use std::collections::BTreeSet;
 use std::rc::Rc;
 
 use crate::common::ident;
-use crate::input::{ParameterInput, TestInputs};
+use crate::input::{
+  Combinations, GeneratedParameterInput, ParameterInput, PropertyParameterInput, TestInputs,
+};
+use crate::syn_utils::TryIdent;
+
+use super::TestExclusion;
 
 /// A collection of test parameters to provide to a given [`TestDispatcher`]
 /// invocation.
+///
+/// Parameters that are generated at runtime are provided by the variable that
+/// holds each of their generated inputs, while the test-case runs each of its
+/// generated cases. Parameters that are generated by a strategy are provided in
+/// the same way, while the test-case runs each of its property cases.
+///
+/// The attributes of each input, such as `#[ignore]`, are collected into the
+/// attributes of the test-case.
 #[derive(Clone)]
 pub struct TestParameters {
   params: Rc<Punctuated<Expr, Comma>>,
+  generated: Rc<Vec<GeneratedParameterInput>>,
+  properties: Rc<Vec<PropertyParameterInput>>,
+  cases: Option<syn::LitInt>,
+  attrs: Rc<Vec<Attribute>>,
+  excluded: bool,
 }
 
 impl Default for TestParameters {
@@ -30,23 +50,166 @@ impl TestParameters {
   pub fn new(params: Punctuated<Expr, Comma>) -> Self {
     Self {
       params: Rc::new(params),
+      generated: Default::default(),
+      properties: Default::default(),
+      cases: None,
+      attrs: Default::default(),
+      excluded: false,
     }
   }
 
-  pub fn multiplex(inputs: &TestInputs) -> Vec<(syn::Ident, TestParameters)> {
-    let test_cases = MultiplexedTestParameters::multiplex(inputs);
+  /// Tests whether the test-case of these parameters is excluded by the
+  /// `exclude` input of the test, which is known when the test is expanded.
+  pub fn is_excluded(&self) -> bool {
+    self.excluded
+  }
 
-    let mut result: Vec<(syn::Ident, TestParameters)> = Vec::with_capacity(test_cases.len());
-    for param in test_cases.into_iter() {
-      let ident = ident::new_test_input(&param.input_indices, Span::call_site());
+  /// Returns the attributes of the test-case, which are given by the
+  /// attributes of each of its inputs
+  pub fn attrs(&self) -> &[Attribute] {
+    &self.attrs
+  }
+
+  /// Returns the parameters whose inputs are generated at runtime
+  pub fn generated(&self) -> &[GeneratedParameterInput] {
+    &self.generated
+  }
 
-      result.push((ident, TestParameters::new(param.parameters)));
+  /// Returns the parameters whose inputs are generated by a strategy
+  pub fn properties(&self) -> &[PropertyParameterInput] {
+    &self.properties
+  }
+
+  /// Returns the number of cases to run the properties of the test with, if
+  /// specified
+  pub fn cases(&self) -> Option<&syn::LitInt> {
+    self.cases.as_ref()
+  }
+
+  /// Produces the parameters of a test that has no parameter inputs, other
+  /// than any that are generated at runtime or by a strategy.
+  ///
+  /// # Arguments
+  ///
+  /// * `inputs` - the test inputs
+  /// * `sig` - the signature of the test
+  pub fn unparameterized(inputs: &TestInputs, sig: &Signature) -> Self {
+    let inputs = TestInputs {
+      parameters: Vec::new(),
+      ..inputs.clone()
+    };
+    Self::with_generated(&inputs, sig, &Default::default())
+  }
+
+  /// Merges the generated and property parameters of the inputs into the
+  /// `params` of a single test-case, in the order of the arguments of the test.
+  ///
+  /// A lone generated parameter moves each of its inputs into its test-case,
+  /// whereas the inputs of several generated parameters are combined, and so
+  /// are cloned into each test-case. Values generated by strategies are always
+  /// moved.
+  ///
+  /// # Arguments
+  ///
+  /// * `inputs` - the test inputs
+  /// * `sig` - the signature of the test
+  /// * `params` - the expressions of the parameter inputs of the test-case
+  fn with_generated(
+    inputs: &TestInputs,
+    sig: &Signature,
+    params: &Punctuated<Expr, Comma>,
+  ) -> Self {
+    let generated = &inputs.generated_parameters;
+    let properties = &inputs.property_parameters;
+    if generated.is_empty() && properties.is_empty() {
+      return Self::new(params.clone());
+    }
+
+    let mut result: Punctuated<Expr, Comma> = Punctuated::new();
+    let mut params = params.iter();
+    for arg in sig.inputs.iter() {
+      let ident = match arg.try_ident() {
+        Some(ident) => ident,
+        None => continue,
+      };
+      if inputs.parameters.iter().any(|v| v.ident == *ident) {
+        result.push(params.next().unwrap().clone());
+      } else if generated.iter().any(|v| v.ident == *ident) {
+        let param = ident::new_generated_parameter(ident);
+        if generated.len() == 1 {
+          result.push(parse_quote!(#param));
+        } else {
+          result.push(parse_quote!(::std::clone::Clone::clone(#param)));
+        }
+      } else if properties.iter().any(|v| v.ident == *ident) {
+        let param = ident::new_generated_parameter(ident);
+        result.push(parse_quote!(#param));
+      }
+    }
+    Self {
+      params: Rc::new(result),
+      generated: Rc::new(generated.clone()),
+      properties: Rc::new(properties.clone()),
+      cases: inputs.cases.clone(),
+      attrs: Default::default(),
+      excluded: false,
     }
-    result
   }
 
-  pub fn is_empty(&self) -> bool {
-    self.params.is_empty()
+  /// Produces the parameters of every test-case, along with the name of the
+  /// executor of each test-case.
+  ///
+  /// Test-cases are named after the name of each of their inputs, such as
+  /// `a_0_b_hello`. Test-cases whose names would collide are made unique with
+  /// the index of the test-case, such as `a_1_3`. Test-cases whose literal
+  /// inputs are excluded by the `exclude` input of the test are still produced,
+  /// so that they can be reported as filtered.
+  ///
+  /// # Arguments
+  ///
+  /// * `inputs` - the test inputs
+  /// * `sig` - the signature of the test
+  pub fn multiplex(inputs: &TestInputs, sig: &Signature) -> Vec<(syn::Ident, TestParameters)> {
+    let test_cases = MultiplexedTestParameters::multiplex(inputs);
+    let names: Vec<Vec<String>> = inputs.parameters.iter().map(input_names).collect();
+    let exclusion = inputs.exclude.clone().map(TestExclusion::new);
+
+    let case_names: Vec<String> = test_cases
+      .iter()
+      .map(|param| {
+        let names: Vec<String> = param
+          .param_indices
+          .iter()
+          .zip(names.iter())
+          .map(|(i, names)| names[*i].clone())
+          .collect();
+        names.join("_")
+      })
+      .collect();
+
+    let mut result: Vec<(syn::Ident, TestParameters)> = Vec::with_capacity(test_cases.len());
+    for (i, param) in test_cases.iter().enumerate() {
+      let name = &case_names[i];
+      let is_unique = case_names.iter().filter(|v| *v == name).count() == 1;
+      let ident = if is_unique {
+        syn::Ident::new(name, Span::call_site())
+      } else {
+        syn::Ident::new(&format!("{name}_{i}"), Span::call_site())
+      };
+      let mut params = TestParameters::with_generated(inputs, sig, &param.parameters);
+      params.attrs = Rc::new(case_attrs(&inputs.parameters, &param.param_indices));
+      if let Some(exclusion) = &exclusion {
+        let values: Vec<(&syn::Ident, &Expr)> = inputs
+          .parameters
+          .iter()
+          .map(|v| &v.ident)
+          .zip(param.parameters.iter())
+          .collect();
+        params.excluded = exclusion.excludes(&values);
+      }
+      result.push((ident, params));
+    }
+    result
   }
 
   /// Serializes the test-parameters to a comma-separated sequence, while also
@@ -58,7 +221,7 @@ impl TestParameters {
   ///
   /// * `tokens` - the [`TokenStream`] to serialize to
   /// * `context` - the context identifier
-  pub fn to_tokens_with_context(&self, tokens: &mut TokenStream, context: &syn::Ident) {
+  pub fn to_tokens_with_context<T: ToTokens>(&self, tokens: &mut TokenStream, context: &T) {
     if self.params.is_empty() {
       context.to_tokens(tokens);
     } else {
@@ -69,9 +232,86 @@ impl TestParameters {
   }
 }
 
+/// Collects the attributes of the inputs of a single test-case.
+///
+/// Only the first `#[ignore]` and `#[should_panic]` are kept when several
+/// inputs have them, since each may only be applied to a test once.
+///
+/// # Arguments
+///
+/// * `parameters` - the parameter inputs of the test
+/// * `indices` - the index of the input of each parameter
+fn case_attrs(parameters: &[ParameterInput], indices: &[usize]) -> Vec<Attribute> {
+  let mut result: Vec<Attribute> = Vec::new();
+  let attrs = parameters
+    .iter()
+    .zip(indices.iter())
+    .flat_map(|(param, i)| param.attrs[*i].iter());
+  for attr in attrs {
+    let is_repeated = !attr.path.is_ident("cfg")
+      && result
+        .iter()
+        .any(|v| v.path.get_ident() == attr.path.get_ident());
+    if !is_repeated {
+      result.push(attr.clone());
+    }
+  }
+  result
+}
+
+/// Returns the name of each input of the parameter.
+///
+/// Inputs are named by their label if they have one, by their value if it is
+/// a literal that is also valid within an identifier, or otherwise by their
+/// index -- e.g. `a as [0, max: u32::MAX, -1]` names its inputs `a_0`, `a_max`
+/// and `a_idx2`. Indices are prefixed so that they do not collide with the
+/// names of integer literals.
+///
+/// # Arguments
+///
+/// * `param` - the parameter input
+fn input_names(param: &ParameterInput) -> Vec<String> {
+  let ident = param.ident.unraw();
+  param
+    .labels
+    .iter()
+    .zip(param.inputs.elems.iter())
+    .enumerate()
+    .map(|(i, (label, expr))| {
+      let name = match label {
+        Some(label) => label.clone(),
+        None => literal_name(expr).unwrap_or_else(|| format!("idx{i}")),
+      };
+      format!("{ident}_{name}")
+    })
+    .collect()
+}
+
+/// Returns the name of a literal expression, if it is valid within an
+/// identifier.
+///
+/// # Arguments
+///
+/// * `expr` - the expression to name
+fn literal_name(expr: &Expr) -> Option<String> {
+  let lit = match expr {
+    Expr::Lit(expr) => &expr.lit,
+    _ => return None,
+  };
+  let name = match lit {
+    Lit::Int(v) => v.base10_digits().to_string(),
+    Lit::Bool(v) => v.value.to_string(),
+    Lit::Str(v) => v.value(),
+    Lit::Char(v) => v.value().to_string(),
+    _ => return None,
+  };
+  let is_valid = !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+  is_valid.then_some(name)
+}
+
 #[derive(Default, Clone)]
 struct MultiplexedTestParameters {
-  input_indices: Vec<usize>,
+  param_indices: Vec<usize>,
   parameters: Punctuated<Expr, Comma>,
 }
 
@@ -79,13 +319,20 @@ impl MultiplexedTestParameters {
   /// Creates a vector of [`MultiplexedTestParameters`] by combinatorially
   /// producing all combination of parameter inputs.
   ///
+  /// Zipped parameters form a single axis of the combinations, so that each
+  /// combination receives the inputs of those parameters at the same index.
+  /// When only `n`-wise combinations are requested, this instead produces the
+  /// combinations of a [`CoveringArray`].
+  ///
   /// # Arguments
   ///
   /// * `inputs` - the parameter inputs to test with
   pub fn multiplex(inputs: &TestInputs) -> Vec<Self> {
-    let mut result: Vec<Self> = Vec::with_capacity(Self::input_size(inputs));
+    let axes = ParameterAxis::from_inputs(inputs);
+    let combinations = inputs.combinations.unwrap_or_default();
+    let mut result: Vec<Self> = Vec::with_capacity(Self::input_size(&axes));
 
-    Self::multiplex_into(&mut result, &inputs.parameters);
+    Self::multiplex_into(&mut result, &inputs.parameters, &axes, combinations);
 
     result
   }
@@ -97,44 +344,84 @@ impl MultiplexedTestParameters {
   ///
   /// * `result` - the vector to populate
   /// * `parameters` - the input parameters to use for producing combinations
-  fn multiplex_into(result: &mut Vec<Self>, parameters: &[ParameterInput]) {
+  /// * `axes` - the axes of the parameters to combine
+  /// * `combinations` - the combinations of the axes to produce
+  fn multiplex_into(
+    result: &mut Vec<Self>,
+    parameters: &[ParameterInput],
+    axes: &[ParameterAxis],
+    combinations: Combinations,
+  ) {
     if parameters.is_empty() {
       return;
     }
-    let current = Self {
-      input_indices: Vec::with_capacity(parameters.len()),
-      parameters: Punctuated::default(),
+    let lens: Vec<usize> = axes.iter().map(|v| v.len).collect();
+    let rows = match combinations {
+      Combinations::NWise(n) if n < axes.len() => CoveringArray::new(&lens, n).into_rows(),
+      _ => {
+        let mut rows: Vec<Vec<usize>> = Vec::new();
+        Self::multiplex_into_aux(&mut rows, Vec::with_capacity(axes.len()), axes);
+        rows
+      }
     };
-    Self::multiplex_into_aux(result, current, parameters)
+
+    result.extend(
+      rows
+        .into_iter()
+        .map(|row| Self::from_row(parameters, axes, &row)),
+    );
   }
 
   /// An auxiliary function used for the recursion of parameter inputs.
   ///
-  /// This passes the current state of the test input down into the next
-  /// set of parameter values.
+  /// This passes the current index of each axis down into the next axis.
   ///
   /// # Arguments
   ///
-  /// * `result` - the vector to populate
-  /// * `current` - the current state of inputs
-  /// * `parameters` - the input parameters to use for producing combinations
-  fn multiplex_into_aux(result: &mut Vec<Self>, current: Self, parameters: &[ParameterInput]) {
+  /// * `result` - the vector of input indices to populate
+  /// * `current` - the current indices of the axes
+  /// * `axes` - the remaining axes to use for producing combinations
+  fn multiplex_into_aux(result: &mut Vec<Vec<usize>>, current: Vec<usize>, axes: &[ParameterAxis]) {
     // Recursive base-case: we have finished iterating
-    if parameters.is_empty() {
+    if axes.is_empty() {
       result.push(current)
     } else {
-      let param = parameters.first().unwrap();
-      for (i, v) in param.inputs.elems.iter().enumerate() {
+      for i in 0..axes[0].len {
         let mut current = current.clone();
-        current.input_indices.push(i);
-        current.parameters.push(v.clone());
-        Self::multiplex_into_aux(result, current, &parameters[1..])
+        current.push(i);
+        Self::multiplex_into_aux(result, current, &axes[1..])
       }
     }
   }
 
+  /// Creates the test parameters for a single combination of input indices.
+  ///
+  /// # Arguments
+  ///
+  /// * `parameters` - the input parameters
+  /// * `axes` - the axes of the parameters
+  /// * `row` - the input index of each axis
+  fn from_row(parameters: &[ParameterInput], axes: &[ParameterAxis], row: &[usize]) -> Self {
+    let param_indices: Vec<usize> = (0..parameters.len())
+      .map(|i| {
+        let axis = axes.iter().position(|v| v.params.contains(&i)).unwrap();
+        row[axis]
+      })
+      .collect();
+    let parameters = parameters
+      .iter()
+      .zip(param_indices.iter())
+      .map(|(param, i)| param.inputs.elems[*i].clone())
+      .collect();
+
+    Self {
+      param_indices,
+      parameters,
+    }
+  }
+
   /// Computes the number of input combinations that are possible given
-  /// the specified test inputs.
+  /// the specified parameter axes.
   ///
   /// Since parameterized tests produce the complete-graph of combinations,
   /// the number of possible inputs is multiplicative. Thus, if a test takes
@@ -149,14 +436,183 @@ impl MultiplexedTestParameters {
   /// * `(a_2, b_0)
   /// * `(a_2, b_1)
   ///
+  /// Zipped parameters only count once, since they are a single axis.
+  ///
+  /// # Arguments
+  ///
+  /// * `axes` - the axes of the parameters
+  fn input_size(axes: &[ParameterAxis]) -> usize {
+    axes.iter().map(|v| v.len).product()
+  }
+}
+
+/// A single axis of the combinations of parameter inputs, which is formed of
+/// either a single parameter, or of a set of zipped parameters.
+struct ParameterAxis {
+  params: Vec<usize>,
+  len: usize,
+}
+
+impl ParameterAxis {
+  /// Groups the parameters of the inputs into axes, in the order of the first
+  /// parameter of each axis.
+  ///
+  /// # Arguments
+  ///
+  /// * `inputs` - the test inputs
+  fn from_inputs(inputs: &TestInputs) -> Vec<Self> {
+    let mut axes: Vec<Self> = Vec::new();
+    for (i, param) in inputs.parameters.iter().enumerate() {
+      let zip = inputs.zips.iter().find(|v| v.contains(&param.ident));
+      let axis = zip.and_then(|zip| {
+        axes.iter_mut().find(|axis| {
+          axis
+            .params
+            .iter()
+            .any(|v| zip.contains(&inputs.parameters[*v].ident))
+        })
+      });
+      match axis {
+        Some(axis) => axis.params.push(i),
+        None => axes.push(Self {
+          params: vec![i],
+          len: param.inputs.elems.len(),
+        }),
+      }
+    }
+    axes
+  }
+}
+
+/// A set of combinations of input indices in which every combination of the
+/// inputs of each `n` axes appears at least once.
+///
+/// This is generated greedily, but deterministically: each row starts from the
+/// first combination that is not yet covered, and each remaining axis is given
+/// the input that covers the most new combinations -- preferring the lowest
+/// index on ties. Rows are sorted, so the generated test-cases are only
+/// affected by changes to the inputs themselves.
+struct CoveringArray {
+  lens: Vec<usize>,
+  subsets: Vec<Vec<usize>>,
+  uncovered: BTreeSet<(usize, Vec<usize>)>,
+}
+
+impl CoveringArray {
+  /// Creates the [`CoveringArray`] for axes with the specified number of
+  /// inputs, such that every combination of `n` axes is covered.
+  ///
   /// # Arguments
   ///
-  /// * `inputs` - the test inputs to provide
-  fn input_size(inputs: &TestInputs) -> usize {
-    inputs
-      .parameters
+  /// * `lens` - the number of inputs of each axis
+  /// * `n` - the number of axes to cover the combinations of
+  fn new(lens: &[usize], n: usize) -> Self {
+    let mut subsets: Vec<Vec<usize>> = Vec::new();
+    Self::subsets_into(&mut subsets, Vec::with_capacity(n), 0, lens.len(), n);
+
+    let mut uncovered: BTreeSet<(usize, Vec<usize>)> = BTreeSet::new();
+    for (i, subset) in subsets.iter().enumerate() {
+      let subset_lens: Vec<usize> = subset.iter().map(|v| lens[*v]).collect();
+      let mut values: Vec<Vec<usize>> = Vec::new();
+      Self::values_into(&mut values, Vec::with_capacity(n), &subset_lens);
+      uncovered.extend(values.into_iter().map(|v| (i, v)));
+    }
+
+    Self {
+      lens: lens.to_vec(),
+      subsets,
+      uncovered,
+    }
+  }
+
+  /// Consumes the covering array, producing the input index of each axis for
+  /// every row.
+  fn into_rows(mut self) -> Vec<Vec<usize>> {
+    // Axes without inputs can never be combined
+    if self.lens.contains(&0) {
+      return Vec::new();
+    }
+    let mut rows: Vec<Vec<usize>> = Vec::new();
+    while let Some((subset, values)) = self.uncovered.iter().next().cloned() {
+      let mut row: Vec<Option<usize>> = vec![None; self.lens.len()];
+      for (axis, value) in self.subsets[subset].iter().zip(values) {
+        row[*axis] = Some(value);
+      }
+      for axis in 0..row.len() {
+        if row[axis].is_some() {
+          continue;
+        }
+        let best = (0..self.lens[axis])
+          .rev()
+          .max_by_key(|v| {
+            row[axis] = Some(*v);
+            self.newly_covered(&row)
+          })
+          .unwrap();
+        row[axis] = Some(best);
+      }
+
+      let row: Vec<usize> = row.into_iter().map(Option::unwrap).collect();
+      for (i, subset) in self.subsets.iter().enumerate() {
+        let values: Vec<usize> = subset.iter().map(|v| row[*v]).collect();
+        self.uncovered.remove(&(i, values));
+      }
+      rows.push(row);
+    }
+    rows.sort();
+    rows
+  }
+
+  /// Counts the uncovered combinations of the axes that are assigned in the
+  /// partial `row`.
+  ///
+  /// # Arguments
+  ///
+  /// * `row` - the partially assigned row
+  fn newly_covered(&self, row: &[Option<usize>]) -> usize {
+    self
+      .subsets
       .iter()
-      .map(|v| v.inputs.elems.len())
-      .product()
+      .enumerate()
+      .filter_map(|(i, subset)| {
+        let values: Option<Vec<usize>> = subset.iter().map(|v| row[*v]).collect();
+        values.map(|v| (i, v))
+      })
+      .filter(|v| self.uncovered.contains(v))
+      .count()
+  }
+
+  /// Populates `result` with every subset of `n` axes, in lexicographic order.
+  fn subsets_into(
+    result: &mut Vec<Vec<usize>>,
+    current: Vec<usize>,
+    start: usize,
+    len: usize,
+    n: usize,
+  ) {
+    if current.len() == n {
+      result.push(current);
+      return;
+    }
+    for i in start..len {
+      let mut current = current.clone();
+      current.push(i);
+      Self::subsets_into(result, current, i + 1, len, n);
+    }
+  }
+
+  /// Populates `result` with every combination of inputs of the axes with the
+  /// specified number of inputs.
+  fn values_into(result: &mut Vec<Vec<usize>>, current: Vec<usize>, lens: &[usize]) {
+    match lens.split_first() {
+      None => result.push(current),
+      Some((len, rest)) => {
+        for i in 0..*len {
+          let mut current = current.clone();
+          current.push(i);
+          Self::values_into(result, current, rest);
+        }
+      }
+    }
   }
 }
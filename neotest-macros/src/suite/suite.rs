@@ -1,168 +1,181 @@
 #![allow(unused)]
 use proc_macro2::TokenStream;
 use quote::ToTokens;
-use syn::{parse_quote, Block, Expr, ItemFn, ReturnType, Signature, Stmt};
+use syn::punctuated::Punctuated;
+use syn::token::{Bracket, Comma};
+use syn::{parse_quote, Block, GenericParam, ItemFn, Type};
 
-use crate::common::ty;
-use crate::input::{FixtureInput, TestInputs};
+use crate::common::ident;
+use crate::input::{FixtureScope, TestInputs};
 use crate::suite::{Section, SectionGraph, Test, TestAttributes};
-use crate::syn_utils::{ContainsIdent, FunctionDefinition, ModuleDefinition, TryIdent};
-
-use super::{TestDispatcher, TestExecutor, TestParameters};
-
-pub struct ParameterizedTestSuite {
-  test: Test,
-  main: TestDispatcher,
-
-  attrs: TestAttributes,
-  sig: Signature,
-  subtests: Vec<TestExecutor>,
-}
-
-impl ParameterizedTestSuite {
-  fn push_subtest<F>(&mut self, name: syn::Ident, params: TestParameters, test: &Test, f: F)
-  where
-    F: FnOnce(&mut TestExecutor),
-  {
-    let mut executor = TestExecutor::new(name, self.attrs.clone(), params, test);
-    f(&mut executor);
-    self.subtests.push(executor);
-  }
-}
-
-impl ToTokens for ParameterizedTestSuite {
-  fn to_tokens(&self, tokens: &mut TokenStream) {
-    self.test.to_tokens(tokens);
-    self.main.to_tokens_with_call(tokens, &self.test);
-
-    self.attrs.to_tokens(tokens);
-    FunctionDefinition(&self.sig).surround(tokens, |tokens| {
-      for test in self.subtests.iter() {
-        let scope = &self.sig.ident;
-        let test_name = test.executor_name();
-
-        let invoke_stmt: Stmt = parse_quote! { #scope::#test_name()?; };
-        invoke_stmt.to_tokens(tokens);
-      }
-      let ok_expr: Expr = parse_quote! {
-        #[allow(unreachable)]
-        Ok(())
-      };
-      ok_expr.to_tokens(tokens);
-    });
-
-    ModuleDefinition(&self.sig.ident).surround(tokens, |tokens| {
-      let use_stmt: Stmt = parse_quote! { use super::*; };
-      use_stmt.to_tokens(tokens);
-      for subtest in self.subtests.iter() {
-        subtest.to_tokens_with_call(tokens, &self.main);
-      }
-    })
-  }
-}
-
-pub struct StandardTestSuite {
+use crate::syn_utils::{ContainsIdent, TryIdent};
+
+use super::{
+  DataCases, DispatchCount, TestCase, TestDispatcher, TestExclusion, TestExecutor, TestFixture,
+  TestGenerics, TestParameters,
+};
+
+/// The complete set of generated definitions for a single test.
+///
+/// A test-suite is formed of the test itself, the dispatcher that prepares
+/// any fixtures for the test, one test-case for every combination of generic
+/// and parameter inputs, and the tree of executors that invoke those cases.
+pub struct TestSuite {
   test: Test,
   main: TestDispatcher,
+  cases: Vec<TestCase>,
   root: TestExecutor,
+  data_cases: Option<DataCases>,
 }
 
-impl ToTokens for StandardTestSuite {
+impl ToTokens for TestSuite {
   fn to_tokens(&self, tokens: &mut TokenStream) {
     self.test.to_tokens(tokens);
     self.main.to_tokens_with_call(tokens, &self.test);
-    self.root.to_tokens_with_call(tokens, &self.main);
-  }
-}
-
-pub enum TestSuite {
-  /// A test executor for dispatching to parameterized-tests
-  Parameterized(ParameterizedTestSuite),
-
-  /// A test executor for dispatching basic non-parameterized tests that may
-  /// contain subtests.
-  Standard(StandardTestSuite),
-}
-
-impl ToTokens for TestSuite {
-  fn to_tokens(&self, tokens: &mut TokenStream) {
-    match &self {
-      TestSuite::Parameterized(executor) => executor.to_tokens(tokens),
-      TestSuite::Standard(executor) => executor.to_tokens(tokens),
+    for case in self.cases.iter() {
+      case.to_tokens_with_call(tokens, &self.main);
     }
+    self.root.to_tokens(tokens);
+    self.data_cases.to_tokens(tokens);
   }
 }
 
 impl TestSuite {
   /// Constructs a [`TestSuite`] from the specified test inputs.
   ///
-  /// If the provided [`TestInputs`] contains any parameterized tests, this will
-  /// produce a [`TestSuite::Parameterized`], otherwise this will produce a
-  /// [`TestSuite::Standard`].
+  /// The executors of the suite form a tree where each generic input produces
+  /// a child of the root executor, each parameter input produces a child of
+  /// that, and each subtest produces a child of the executor that dispatches
+  /// to the test-case.
   ///
   /// # Arguments
   ///
   /// * `inputs` - the test input arguments supplied in the test attribute
   /// * `test` - the definition of the test function
-  pub fn from_inputs(mut inputs: TestInputs, test: ItemFn) -> syn::Result<Self> {
-    Self::validate(&inputs, &test)?;
-    inputs.reorder(&test.sig);
-
-    if inputs.parameters.is_empty() {
-      Self::standard_from_inputs(inputs, test)
-    } else {
-      Self::parameterized_from_inputs(inputs, test)
+  pub fn from_inputs(inputs: TestInputs, test_fn: ItemFn) -> syn::Result<Self> {
+    let module_fixture = inputs
+      .fixtures
+      .iter()
+      .find(|v| v.scope == FixtureScope::Module);
+    if let Some(fixture) = module_fixture {
+      return Err(syn::Error::new_spanned(
+        &fixture.ty,
+        "module-scoped fixtures can only be used by tests within a #[neotest_suite] module",
+      ));
     }
+    Self::from_module_inputs(inputs, test_fn)
   }
 
-  fn parameterized_from_inputs(inputs: TestInputs, mut test_fn: ItemFn) -> syn::Result<Self> {
-    let sig = test_fn.sig.clone();
-    let graph = Self::translate_sections(&mut test_fn.block)?;
-
-    let test = Test::new(test_fn);
-    let attrs = TestAttributes::new(test.attrs().into());
-    let main = TestDispatcher::new(&test, inputs.fixture.as_ref().cloned().map(|v| v.ident));
-    let mut subtests = Self::multiplex_subtests(attrs.clone(), &inputs, &test);
-    for subtest in &mut subtests {
-      Self::apply_subsections(subtest, graph.subsections());
-    }
-
-    Ok(Self::Parameterized(ParameterizedTestSuite {
-      attrs,
-      main,
-      sig: Self::suite_signature(sig),
-      test,
-      subtests,
-    }))
-  }
+  /// Constructs a [`TestSuite`] from the specified test inputs of a test
+  /// within a [`neotest_suite`] module, where module-scoped fixtures may be
+  /// used.
+  ///
+  /// # Arguments
+  ///
+  /// * `inputs` - the test input arguments, including those of the module
+  /// * `test` - the definition of the test function
+  ///
+  /// [`neotest_suite`]: crate::neotest_suite
+  pub fn from_module_inputs(mut inputs: TestInputs, test_fn: ItemFn) -> syn::Result<Self> {
+    let data_cases = DataCases::apply(&mut inputs, &test_fn.sig)?;
+    Self::validate(&inputs, &test_fn)?;
 
-  fn suite_signature(mut sig: Signature) -> Signature {
-    sig.inputs.clear();
-    sig.output = ty::test_result();
-    sig
+    let fixtures = TestFixture::resolve(&inputs, &test_fn.sig, None)?;
+    Self::from_test(inputs, test_fn, fixtures, data_cases, Test::new)
   }
 
-  /// Constructs a [`TestSuite::Standard`] object from the specified inputs
+  /// Constructs a [`TestSuite`] from a test method defined within a
+  /// [`neotest_fixture`] impl block.
   ///
-  /// A standard test-suite is a non-parameterized test-suite that may, or may not,
-  /// contain subtests.
+  /// The `self` receiver of the method is prepared as the fixture of the test,
+  /// along with any other fixtures the method takes.
   ///
   /// # Arguments
   ///
   /// * `inputs` - the test input arguments supplied in the test attribute
-  /// * `test` - the definition of the test function
-  fn standard_from_inputs(inputs: TestInputs, mut test_fn: ItemFn) -> syn::Result<Self> {
+  /// * `test` - the definition of the test method
+  /// * `self_ty` - the fixture type that the method is defined on
+  ///
+  /// [`neotest_fixture`]: crate::neotest_fixture
+  pub fn from_method(mut inputs: TestInputs, test_fn: ItemFn, self_ty: &Type) -> syn::Result<Self> {
+    Self::validate_method(&inputs, &test_fn)?;
+    let data_cases = DataCases::apply(&mut inputs, &test_fn.sig)?;
+    Self::validate(&inputs, &test_fn)?;
+
+    let fixtures = TestFixture::resolve(&inputs, &test_fn.sig, Some(self_ty))?;
+    Self::from_test(inputs, test_fn, fixtures, data_cases, |v| {
+      Test::new_method(v, self_ty.clone())
+    })
+  }
+
+  fn from_test<F>(
+    mut inputs: TestInputs,
+    mut test_fn: ItemFn,
+    fixtures: Vec<TestFixture>,
+    data_cases: Option<DataCases>,
+    make_test: F,
+  ) -> syn::Result<Self>
+  where
+    F: FnOnce(ItemFn) -> Test,
+  {
+    inputs.reorder(&test_fn.sig);
+
     let name = test_fn.sig.ident.clone();
     let graph = Self::translate_sections(&mut test_fn.block)?;
 
-    let test = Test::new(test_fn);
+    let test = make_test(test_fn);
     let attrs = TestAttributes::new(test.attrs().into());
-    let main = TestDispatcher::new(&test, inputs.fixture.map(|v| v.ident));
-    let mut root = TestExecutor::new(name, attrs, Default::default(), &test);
+    let exclusion = inputs.exclude.clone().map(TestExclusion::new);
+    let main = TestDispatcher::new(&test, fixtures, inputs.executor.clone(), exclusion);
+    let mut root = TestExecutor::new(name, attrs, &test, inputs.timeout);
+    let mut cases: Vec<TestCase> = Vec::new();
+
+    let generics = TestGenerics::multiplex(&inputs, &test.signature().generics);
+    let params = TestParameters::multiplex(&inputs, test.signature());
+    let builder = CaseBuilder {
+      test: &test,
+      params: &params,
+      unparameterized: TestParameters::unparameterized(&inputs, test.signature()),
+      graph: &graph,
+    };
+
+    if generics.is_empty() {
+      builder.apply_parameters(&mut root, &mut cases, &Default::default());
+    } else {
+      for (ident, generic) in generics.iter() {
+        root.push_input(ident.clone(), |executor| {
+          builder.apply_parameters(executor, &mut cases, generic);
+        });
+      }
+    }
+
+    Ok(Self {
+      test,
+      main,
+      cases,
+      root,
+      data_cases,
+    })
+  }
 
-    Self::apply_subsections(&mut root, graph.subsections());
+  /// Returns the types of all module-scoped fixtures used by this test
+  pub fn module_fixtures(&self) -> impl Iterator<Item = &Type> {
+    self
+      .main
+      .fixtures()
+      .iter()
+      .filter(|v| v.scope() == FixtureScope::Module)
+      .map(TestFixture::ty)
+  }
 
-    Ok(Self::Standard(StandardTestSuite { test, main, root }))
+  /// Returns the number of times that this test uses its fixtures when all of
+  /// its executors are run, as the uses by executors that are run by default
+  /// and the uses by those that are `#[ignore]`d.
+  pub fn fixture_uses(&self) -> (DispatchCount, DispatchCount) {
+    (
+      self.root.dispatch_count(false),
+      self.root.dispatch_count(true),
+    )
   }
 
   /// Translates `#[section]` attributes within the test function into
@@ -171,6 +184,71 @@ impl TestSuite {
   fn translate_sections(block: &mut Box<Block>) -> syn::Result<SectionGraph> {
     SectionGraph::discover_subtests(block)
   }
+}
+
+/// A small helper for populating the tree of [`TestExecutor`]s with their
+/// respective [`TestCase`]s.
+struct CaseBuilder<'a> {
+  test: &'a Test,
+  params: &'a [(syn::Ident, TestParameters)],
+  unparameterized: TestParameters,
+  graph: &'a SectionGraph,
+}
+
+impl<'a> CaseBuilder<'a> {
+  /// Applies all parameter inputs to the executor, producing a test-case for
+  /// each combination that is not excluded.
+  ///
+  /// # Arguments
+  ///
+  /// * `executor` - the executor for the test
+  /// * `cases` - the test-cases to populate
+  /// * `generics` - the generic arguments of the test-cases
+  fn apply_parameters(
+    &self,
+    executor: &mut TestExecutor,
+    cases: &mut Vec<TestCase>,
+    generics: &TestGenerics,
+  ) {
+    if self.params.is_empty() {
+      self.apply_case(executor, cases, generics, &self.unparameterized);
+      return;
+    }
+    for (ident, params) in self.params.iter() {
+      executor.push_input(ident.clone(), |executor| {
+        executor.apply_case_attributes(params.attrs());
+        if params.is_excluded() {
+          executor.exclude();
+        } else {
+          self.apply_case(executor, cases, generics, params);
+        }
+      });
+    }
+  }
+
+  /// Creates a new test-case for the executor to dispatch to, along with any
+  /// subtests.
+  ///
+  /// # Arguments
+  ///
+  /// * `executor` - the executor for the test
+  /// * `cases` - the test-cases to populate
+  /// * `generics` - the generic arguments of the test-case
+  /// * `params` - the parameters of the test-case
+  fn apply_case(
+    &self,
+    executor: &mut TestExecutor,
+    cases: &mut Vec<TestCase>,
+    generics: &TestGenerics,
+    params: &TestParameters,
+  ) {
+    let name = ident::new_test_case(self.test.test_name(), cases.len());
+    let case = TestCase::new(name, generics.clone(), params.clone());
+
+    executor.dispatch(&case);
+    Self::apply_subsections(executor, self.graph.subsections());
+    cases.push(case);
+  }
 
   /// Applies subsections to a given test-executor so that it can invoke
   /// subtests.
@@ -189,19 +267,6 @@ impl TestSuite {
       });
     }
   }
-
-  fn multiplex_subtests(
-    attrs: TestAttributes,
-    inputs: &TestInputs,
-    test: &Test,
-  ) -> Vec<TestExecutor> {
-    let test_cases = TestParameters::multiplex(inputs);
-    let mut result: Vec<TestExecutor> = Vec::with_capacity(test_cases.len());
-    for (ident, params) in test_cases.into_iter() {
-      result.push(TestExecutor::new(ident, attrs.clone(), params, test));
-    }
-    result
-  }
 }
 
 impl TestSuite {
@@ -214,19 +279,34 @@ impl TestSuite {
   fn validate(inputs: &TestInputs, test: &syn::ItemFn) -> syn::Result<()> {
     // Perform basic validation
     Self::validate_attributes(test)?;
+    Self::validate_executor(inputs, test)?;
     Self::validate_parameters(inputs, test)?;
+    Self::validate_zips(inputs)?;
+    Self::validate_exclude(inputs)?;
+    Self::validate_properties(inputs)?;
     Self::validate_generic_parameters(inputs, test)?;
     Ok(())
   }
 
-  fn validate_fixture_input(input: &FixtureInput, args: &[syn::FnArg]) -> syn::Result<()> {
-    let first = args.first();
-
-    if first.is_none() {
-      let type_str = input.ident.to_string();
+  /// Validates that a test method within a fixture takes the fixture as its
+  /// receiver, rather than through a `fixture` input.
+  ///
+  /// # Arguments
+  ///
+  /// * `inputs` - the test inputs passed to the attribute
+  /// * `test` - the method performing the testing
+  fn validate_method(inputs: &TestInputs, test: &syn::ItemFn) -> syn::Result<()> {
+    if let Some(fixture) = inputs.fixtures.first() {
+      return Err(syn::Error::new_spanned(
+        &fixture.ty,
+        "fixture argument cannot be specified for tests within a #[neotest_fixture]; the fixture is the `self` type",
+      ));
+    }
+    if test.sig.receiver().is_none() {
+      let name = test.sig.ident.to_string();
       return Err(syn::Error::new(
-        input.ident.span(),
-        format!("test fixture function missing {type_str} fixture as first argument"),
+        test.sig.ident.span(),
+        format!("test '{name}' within a #[neotest_fixture] must take `self`, `&self`, or `&mut self` as its first argument"),
       ));
     }
     Ok(())
@@ -246,64 +326,319 @@ impl TestSuite {
     Ok(())
   }
 
-  fn validate_parameters(inputs: &TestInputs, test: &syn::ItemFn) -> syn::Result<()> {
-    // If we have a fixture, verify that it's the first argument
-    let mut args: Vec<syn::FnArg> = Vec::from_iter(test.sig.inputs.iter().cloned());
+  /// Validates that an executor is only specified for `async fn` tests.
+  ///
+  /// # Arguments
+  ///
+  /// * `inputs` - the test inputs passed to the attribute
+  /// * `test` - the function performing the testing
+  fn validate_executor(inputs: &TestInputs, test: &syn::ItemFn) -> syn::Result<()> {
+    match &inputs.executor {
+      Some(executor) if test.sig.asyncness.is_none() => Err(syn::Error::new_spanned(
+        executor,
+        "executor can only be specified for `async fn` tests",
+      )),
+      _ => Ok(()),
+    }
+  }
 
-    if let Some(fixture) = &inputs.fixture {
-      Self::validate_fixture_input(fixture, &args)?;
-      if !args.is_empty() {
-        args.remove(0);
-      }
+  /// Validates that each parameter input names a parameter of the test, and
+  /// that no parameter is bound more than once.
+  ///
+  /// Parameters of the test that are not bound to any input are injected as
+  /// fixtures.
+  ///
+  /// # Arguments
+  ///
+  /// * `inputs` - the test inputs passed to the attribute
+  /// * `test` - the function performing the testing
+  fn validate_parameters(inputs: &TestInputs, test: &syn::ItemFn) -> syn::Result<()> {
+    let idents: Vec<&syn::Ident> = inputs
+      .parameters
+      .iter()
+      .map(|v| &v.ident)
+      .chain(inputs.generated_parameters.iter().map(|v| &v.ident))
+      .chain(inputs.property_parameters.iter().map(|v| &v.ident))
+      .collect();
+
+    for parameter in inputs.parameters.iter() {
+      Self::validate_nonempty_input(
+        &parameter.ident,
+        &parameter.inputs.bracket_token,
+        parameter.inputs.elems.is_empty(),
+      )?;
     }
 
-    for ident in inputs.parameters.iter().map(|v| &v.ident) {
+    for (i, ident) in idents.iter().enumerate() {
+      let name = ident.to_string();
       if !test.sig.inputs.contains_ident(ident) {
-        let name = ident.to_string();
         return Err(syn::Error::new(
           ident.span(),
           format!("Test input '{name}' is not a valid function parameter."),
         ));
       }
+      if idents[..i].contains(ident) {
+        return Err(syn::Error::new(
+          ident.span(),
+          format!("Test input '{name}' specified more than once."),
+        ));
+      }
     }
+    Ok(())
+  }
 
-    // TODO(mrodusek): Refactor this into smaller functions
-    for arg in args.iter() {
-      if let Some(ident) = arg.try_ident() {
-        let arg_inputs: Vec<&syn::Ident> = inputs
-          .parameters
-          .iter()
-          .map(|v| &v.ident)
-          .filter(|v| *ident == v.to_string())
-          .collect();
-        let count = arg_inputs.len();
-
-        if count != 1 {
-          let param_name = ident.to_string();
-          if count == 0 {
-            return Err(syn::Error::new(
-              ident.span(),
-              format!(
-                "Test function parameter '{param_name}' is not bound to test input. Use `parameter = {param_name} as ...` to set a parameter.",
-              ),
-            ));
-          } else if count > 1 {
-            let ident = arg_inputs[1];
-
-            return Err(syn::Error::new(
-              ident.span(),
-              format!("Test input '{param_name}' specified more than once."),
-            ));
-          }
-        }
+  /// Validates that each zipped parameter is a parameter input that is only
+  /// zipped once, and that all zipped parameters have the same number of
+  /// inputs.
+  ///
+  /// # Arguments
+  ///
+  /// * `inputs` - the test inputs passed to the attribute
+  fn validate_zips(inputs: &TestInputs) -> syn::Result<()> {
+    let idents: Vec<&syn::Ident> = inputs.zips.iter().flat_map(|v| v.idents.iter()).collect();
+    for (i, ident) in idents.iter().enumerate() {
+      let name = ident.to_string();
+      if idents[..i].contains(ident) {
+        return Err(syn::Error::new(
+          ident.span(),
+          format!("Test input '{name}' is zipped more than once."),
+        ));
+      }
+      if inputs
+        .generated_parameters
+        .iter()
+        .any(|v| v.ident == **ident)
+      {
+        return Err(syn::Error::new(
+          ident.span(),
+          format!("Test input '{name}' is generated when the test is run, and cannot be zipped."),
+        ));
+      }
+      if inputs
+        .property_parameters
+        .iter()
+        .any(|v| v.ident == **ident)
+      {
+        return Err(syn::Error::new(
+          ident.span(),
+          format!("Test input '{name}' is generated by a strategy, and cannot be zipped."),
+        ));
+      }
+      if !inputs.parameters.iter().any(|v| v.ident == **ident) {
+        return Err(syn::Error::new(
+          ident.span(),
+          format!("Zipped test input '{name}' is not a parameter. Use `parameter = {name} as ...` to provide its inputs."),
+        ));
+      }
+    }
+
+    // Errors point at the shorter list, since that is most likely incomplete
+    for zip in inputs.zips.iter() {
+      let params: Vec<_> = inputs
+        .parameters
+        .iter()
+        .filter(|v| zip.contains(&v.ident))
+        .collect();
+      let longest = params.iter().max_by_key(|v| v.inputs.elems.len()).unwrap();
+      let len = longest.inputs.elems.len();
+      if let Some(shorter) = params.iter().find(|v| v.inputs.elems.len() < len) {
+        return Err(syn::Error::new_spanned(
+          &shorter.inputs,
+          format!(
+            "Zipped test input '{}' has {} inputs, but '{}' has {}.",
+            shorter.ident,
+            shorter.inputs.elems.len(),
+            longest.ident,
+            len
+          ),
+        ));
+      }
+    }
+    Ok(())
+  }
+
+  /// Validates that each argument of the `exclude` predicate names a
+  /// parameter input of the test.
+  ///
+  /// # Arguments
+  ///
+  /// * `inputs` - the test inputs passed to the attribute
+  fn validate_exclude(inputs: &TestInputs) -> syn::Result<()> {
+    let exclude = match &inputs.exclude {
+      Some(exclude) => exclude,
+      None => return Ok(()),
+    };
+    for (_, ident) in exclude.args() {
+      if !inputs.is_parameter(ident) {
+        let name = ident.to_string();
+        return Err(syn::Error::new(
+          ident.span(),
+          format!("Excluded test input '{name}' is not a parameter. Use `parameter = {name} as ...` to provide its inputs."),
+        ));
       }
     }
     Ok(())
   }
 
+  /// Validates that parameters generated by strategies are not combined with
+  /// generated parameters, that there are no more of them than a strategy
+  /// tuple can hold, and that `cases` is only specified for tests that have
+  /// them.
+  ///
+  /// # Arguments
+  ///
+  /// * `inputs` - the test inputs passed to the attribute
+  fn validate_properties(inputs: &TestInputs) -> syn::Result<()> {
+    const MAX_PROPERTIES: usize = 8;
+
+    let properties = &inputs.property_parameters;
+    if let (Some(property), Some(generated)) =
+      (properties.first(), inputs.generated_parameters.first())
+    {
+      return Err(syn::Error::new(
+        property.ident.span(),
+        format!(
+          "Test input '{}' is generated by a strategy, and cannot be combined with '{}', which is generated from an iterator.",
+          property.ident, generated.ident
+        ),
+      ));
+    }
+    if let Some(property) = properties.get(MAX_PROPERTIES) {
+      return Err(syn::Error::new(
+        property.ident.span(),
+        format!("At most {MAX_PROPERTIES} test inputs can be generated by strategies."),
+      ));
+    }
+    match &inputs.cases {
+      Some(cases) if properties.is_empty() => Err(syn::Error::new(
+        cases.span(),
+        "argument 'cases' requires a parameter generated by a strategy, such as `parameter = v as any::<u32>()`",
+      )),
+      _ => Ok(()),
+    }
+  }
+
   fn validate_generic_parameters(inputs: &TestInputs, test: &syn::ItemFn) -> syn::Result<()> {
-    let (_, _) = (inputs, test);
-    // TODO(mrodusek): Check that all generic arguments correspond to name parameters
+    let generics = &test.sig.generics.params;
+    let type_idents = inputs.type_parameters.iter().map(|v| &v.ident);
+    let const_idents = inputs.const_parameters.iter().map(|v| &v.ident);
+
+    // Verify that each input is given at least one argument
+    for input in inputs.type_parameters.iter() {
+      Self::validate_nonempty_input(
+        &input.ident,
+        &input.inputs.bracket_token,
+        input.inputs.elems.is_empty(),
+      )?;
+    }
+    for input in inputs.const_parameters.iter() {
+      Self::validate_nonempty_input(
+        &input.ident,
+        &input.inputs.bracket_token,
+        input.inputs.elems.is_empty(),
+      )?;
+    }
+
+    // Verify that each input names a generic of the correct kind
+    for ident in type_idents.clone() {
+      Self::validate_generic_input(ident, GenericKind::Type, generics)?;
+    }
+    for ident in const_idents.clone() {
+      Self::validate_generic_input(ident, GenericKind::Const, generics)?;
+    }
+
+    // Verify that no generic is bound more than once
+    let idents: Vec<&syn::Ident> = type_idents.chain(const_idents).collect();
+    for (i, ident) in idents.iter().enumerate() {
+      if idents[..i].contains(ident) {
+        let name = ident.to_string();
+        return Err(syn::Error::new(
+          ident.span(),
+          format!("Generic test input '{name}' specified more than once."),
+        ));
+      }
+    }
+
+    // Verify that every generic is bound to an input
+    for param in generics.iter() {
+      let (ident, option) = match param {
+        GenericParam::Type(v) => (&v.ident, "type_parameter"),
+        GenericParam::Const(v) => (&v.ident, "const_parameter"),
+        GenericParam::Lifetime(_) => continue,
+      };
+      if !idents.contains(&ident) {
+        let name = ident.to_string();
+        return Err(syn::Error::new(
+          ident.span(),
+          format!(
+            "Test generic parameter '{name}' is not bound to test input. Use `{option} = {name} as ...` to set a generic parameter.",
+          ),
+        ));
+      }
+    }
     Ok(())
   }
+
+  /// Validates that an input is given a non-empty list of arguments, since
+  /// an empty list would silently generate no test-cases at all.
+  ///
+  /// # Arguments
+  ///
+  /// * `ident` - the name of the input
+  /// * `bracket` - the brackets of the list of arguments
+  /// * `is_empty` - whether the list of arguments is empty
+  fn validate_nonempty_input(
+    ident: &syn::Ident,
+    bracket: &Bracket,
+    is_empty: bool,
+  ) -> syn::Result<()> {
+    if !is_empty {
+      return Ok(());
+    }
+    let name = ident.to_string();
+    Err(syn::Error::new(
+      bracket.span,
+      format!("Test input '{name}' must be given at least one argument."),
+    ))
+  }
+
+  /// Validates that a single generic input names a generic parameter of the
+  /// test with the expected kind.
+  ///
+  /// # Arguments
+  ///
+  /// * `ident` - the name of the generic input
+  /// * `kind` - the kind of generic the input provides
+  /// * `generics` - the generic parameters of the test function
+  fn validate_generic_input(
+    ident: &syn::Ident,
+    kind: GenericKind,
+    generics: &Punctuated<GenericParam, Comma>,
+  ) -> syn::Result<()> {
+    let name = ident.to_string();
+    let param = generics.iter().find(|v| v.try_ident() == Some(ident));
+
+    let message = match (param, kind) {
+      (None, _) => format!("Test input '{name}' is not a valid generic parameter."),
+      (Some(GenericParam::Type(_)), GenericKind::Type) => return Ok(()),
+      (Some(GenericParam::Const(_)), GenericKind::Const) => return Ok(()),
+      (Some(GenericParam::Type(_)), GenericKind::Const) => format!(
+        "Test input '{name}' is a generic type parameter, and cannot be given const values. Use `type_parameter = {name} as ...` instead."
+      ),
+      (Some(GenericParam::Const(_)), GenericKind::Type) => format!(
+        "Test input '{name}' is a generic const parameter, and cannot be given types. Use `const_parameter = {name} as ...` instead."
+      ),
+      (Some(GenericParam::Lifetime(_)), _) => {
+        format!("Test input '{name}' names a lifetime, which cannot be used as a test input.")
+      }
+    };
+    Err(syn::Error::new(ident.span(), message))
+  }
+}
+
+/// The kind of generic parameter that a generic test input provides.
+#[derive(Clone, Copy)]
+enum GenericKind {
+  Type,
+  Const,
 }
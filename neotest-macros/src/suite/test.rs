@@ -1,18 +1,47 @@
 use proc_macro2::TokenStream;
-use quote::{ToTokens, TokenStreamExt};
-use syn::{parse_quote, Attribute, Block, ItemFn, ReturnType, Signature};
+use quote::{quote, ToTokens, TokenStreamExt};
+use syn::token::{Brace, Impl};
+use syn::{parse_quote, Attribute, Block, ItemFn, ReturnType, Signature, Type};
 
 use crate::common::{ident, ty};
 
+/// The definition of the test function itself, which is renamed and given an
+/// additional [`__Context`] argument so that subtests can be selected.
 ///
+/// Tests defined within a [`neotest_fixture`] are kept as methods of the
+/// fixture, so that `self` and `Self` remain usable within the test body.
+///
+/// [`__Context`]: neotest_common::__internal::__Context
+/// [`neotest_fixture`]: crate::neotest_fixture
 pub struct Test {
+  name: syn::Ident,
   attrs: Vec<Attribute>,
   sig: Signature,
   block: Box<Block>,
+  self_ty: Option<Type>,
 }
 
 impl Test {
+  /// Constructs a [`Test`] from a free function
+  ///
+  /// # Arguments
+  ///
+  /// * `item` - the test function
   pub fn new(item: ItemFn) -> Self {
+    Self::new_impl(item, None)
+  }
+
+  /// Constructs a [`Test`] from a method of the fixture type `self_ty`.
+  ///
+  /// # Arguments
+  ///
+  /// * `item` - the test method
+  /// * `self_ty` - the type of the fixture that the method is defined in
+  pub fn new_method(item: ItemFn, self_ty: Type) -> Self {
+    Self::new_impl(item, Some(self_ty))
+  }
+
+  fn new_impl(item: ItemFn, self_ty: Option<Type>) -> Self {
     let block = match &item.sig.output {
       ReturnType::Default => {
         let block = item.block;
@@ -26,17 +55,37 @@ impl Test {
       ReturnType::Type(_, _) => item.block,
     };
     Self {
+      name: item.sig.ident.clone(),
       attrs: item.attrs,
       sig: Self::test_signature(item.sig),
       block,
+      self_ty,
     }
   }
 
+  /// Returns a reference to the name of this test, as written by the user
+  pub fn name(&self) -> &syn::Ident {
+    &self.name
+  }
+
   /// Returns a reference to the identifier of this test
   pub fn test_name(&self) -> &syn::Ident {
     &self.sig.ident
   }
 
+  /// Returns the path used to call this test, given the generic arguments.
+  ///
+  /// # Arguments
+  ///
+  /// * `generics` - the turbofish of generic arguments for the call
+  pub fn callee<T: ToTokens>(&self, generics: T) -> TokenStream {
+    let name = &self.sig.ident;
+    match &self.self_ty {
+      Some(self_ty) => quote! { <#self_ty>::#name #generics },
+      None => quote! { #name #generics },
+    }
+  }
+
   pub fn signature(&self) -> &Signature {
     &self.sig
   }
@@ -59,8 +108,20 @@ impl Test {
 
 impl ToTokens for Test {
   fn to_tokens(&self, tokens: &mut TokenStream) {
-    tokens.append_all(self.attrs.iter());
-    self.sig.to_tokens(tokens);
-    self.block.to_tokens(tokens);
+    let define = |tokens: &mut TokenStream| {
+      tokens.append_all(self.attrs.iter());
+      self.sig.to_tokens(tokens);
+      self.block.to_tokens(tokens);
+    };
+
+    // Tests defined in fixtures are defined in their own impl block
+    match &self.self_ty {
+      Some(self_ty) => {
+        Impl::default().to_tokens(tokens);
+        self_ty.to_tokens(tokens);
+        Brace::default().surround(tokens, define);
+      }
+      None => define(tokens),
+    }
   }
 }
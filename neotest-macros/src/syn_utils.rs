@@ -2,18 +2,21 @@
 //!
 //! This utilities enable better access to certain symbol types, as well as
 //! easier mechanisms for quoting.
+mod const_value;
 mod contains_ident;
 mod fn_arg;
 mod function_call;
 mod function_definition;
 mod ident;
-mod inner;
 mod module_definition;
 mod try_ident;
+mod turbofish;
 mod type_sequence;
 
 // Re-export all submodule contents.
 
+#[doc(inline)]
+pub use const_value::*;
 #[doc(inline)]
 pub use contains_ident::*;
 #[doc(inline)]
@@ -25,10 +28,10 @@ pub use function_definition::*;
 #[doc(inline)]
 pub use ident::*;
 #[doc(inline)]
-pub use inner::*;
-#[doc(inline)]
 pub use module_definition::*;
 #[doc(inline)]
 pub use try_ident::*;
 #[doc(inline)]
+pub use turbofish::*;
+#[doc(inline)]
 pub use type_sequence::*;
@@ -0,0 +1,179 @@
+//! This internal module defines a small evaluator of literal expressions, so
+//! that simple expressions over literal inputs can be decided while a test is
+//! being expanded.
+use syn::{BinOp, Expr, Lit, UnOp};
+
+/// The value of an expression that was evaluated from literals.
+///
+/// Integers are evaluated without their type, and so are only evaluated as
+/// far as they fit within an [`i128`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConstValue {
+  Bool(bool),
+  Int(i128),
+  Float(f64),
+  Char(char),
+  Str(String),
+}
+
+impl ConstValue {
+  /// Evaluates the value of a literal expression.
+  ///
+  /// Returns [`None`] if the expression is not a literal.
+  ///
+  /// # Arguments
+  ///
+  /// * `expr` - the expression to evaluate
+  pub fn from_literal(expr: &Expr) -> Option<Self> {
+    Self::eval(expr, &|_| None)
+  }
+
+  /// Evaluates an expression of literals, comparisons, arithmetic, and
+  /// boolean logic, where identifiers are resolved with `lookup`.
+  ///
+  /// Returns [`None`] if the expression cannot be evaluated -- such as when it
+  /// calls a function, or uses an identifier that `lookup` does not resolve.
+  /// Since `&&` and `||` short-circuit, their result may still be evaluated
+  /// when only one of their operands can be.
+  ///
+  /// # Arguments
+  ///
+  /// * `expr` - the expression to evaluate
+  /// * `lookup` - resolves the value of an identifier, if it is known
+  pub fn eval(expr: &Expr, lookup: &dyn Fn(&syn::Ident) -> Option<Self>) -> Option<Self> {
+    match expr {
+      Expr::Lit(expr) => Self::from_lit(&expr.lit),
+      Expr::Path(expr) if expr.qself.is_none() => lookup(expr.path.get_ident()?),
+      Expr::Paren(expr) => Self::eval(&expr.expr, lookup),
+      Expr::Group(expr) => Self::eval(&expr.expr, lookup),
+      Expr::Reference(expr) => Self::eval(&expr.expr, lookup),
+      Expr::Block(expr) if expr.block.stmts.len() == 1 => match &expr.block.stmts[0] {
+        syn::Stmt::Expr(expr) => Self::eval(expr, lookup),
+        _ => None,
+      },
+      Expr::Unary(expr) => {
+        let value = Self::eval(&expr.expr, lookup)?;
+        match (expr.op, value) {
+          (UnOp::Deref(_), value) => Some(value),
+          (UnOp::Not(_), Self::Bool(v)) => Some(Self::Bool(!v)),
+          (UnOp::Neg(_), Self::Int(v)) => v.checked_neg().map(Self::Int),
+          (UnOp::Neg(_), Self::Float(v)) => Some(Self::Float(-v)),
+          _ => None,
+        }
+      }
+      Expr::Binary(expr) => match expr.op {
+        BinOp::And(_) => Self::eval_logic(&expr.left, &expr.right, false, lookup),
+        BinOp::Or(_) => Self::eval_logic(&expr.left, &expr.right, true, lookup),
+        op => {
+          let left = Self::eval(&expr.left, lookup)?;
+          let right = Self::eval(&expr.right, lookup)?;
+          Self::eval_binary(op, left, right)
+        }
+      },
+      _ => None,
+    }
+  }
+
+  /// Evaluates the value of a literal
+  fn from_lit(lit: &Lit) -> Option<Self> {
+    match lit {
+      Lit::Bool(v) => Some(Self::Bool(v.value)),
+      Lit::Int(v) => v.base10_parse().ok().map(Self::Int),
+      Lit::Float(v) => v.base10_parse().ok().map(Self::Float),
+      Lit::Char(v) => Some(Self::Char(v.value())),
+      Lit::Str(v) => Some(Self::Str(v.value())),
+      _ => None,
+    }
+  }
+
+  /// Evaluates a short-circuiting `&&` or `||` operator, whose result is
+  /// `short` if either operand is `short`.
+  ///
+  /// # Arguments
+  ///
+  /// * `left` - the left operand
+  /// * `right` - the right operand
+  /// * `short` - the value that decides the result on its own
+  /// * `lookup` - resolves the value of an identifier, if it is known
+  fn eval_logic(
+    left: &Expr,
+    right: &Expr,
+    short: bool,
+    lookup: &dyn Fn(&syn::Ident) -> Option<Self>,
+  ) -> Option<Self> {
+    let left = match Self::eval(left, lookup) {
+      Some(Self::Bool(v)) => Some(v),
+      Some(_) => return None,
+      None => None,
+    };
+    if left == Some(short) {
+      return Some(Self::Bool(short));
+    }
+    let right = match Self::eval(right, lookup)? {
+      Self::Bool(v) => v,
+      _ => return None,
+    };
+    match left {
+      Some(_) => Some(Self::Bool(right)),
+      None if right == short => Some(Self::Bool(short)),
+      None => None,
+    }
+  }
+
+  /// Evaluates a binary operator other than `&&` and `||`
+  fn eval_binary(op: BinOp, left: Self, right: Self) -> Option<Self> {
+    use std::cmp::Ordering;
+
+    let ordering = match (&left, &right) {
+      (Self::Bool(a), Self::Bool(b)) => a.partial_cmp(b),
+      (Self::Int(a), Self::Int(b)) => a.partial_cmp(b),
+      (Self::Float(a), Self::Float(b)) => a.partial_cmp(b),
+      (Self::Char(a), Self::Char(b)) => a.partial_cmp(b),
+      (Self::Str(a), Self::Str(b)) => a.partial_cmp(b),
+      _ => return None,
+    };
+    let compare = |f: fn(Ordering) -> bool| Some(Self::Bool(ordering.is_some_and(f)));
+    match op {
+      BinOp::Eq(_) => return compare(Ordering::is_eq),
+      // `NaN != NaN`, even though the two are unordered
+      BinOp::Ne(_) => return Some(Self::Bool(!ordering.is_some_and(Ordering::is_eq))),
+      BinOp::Lt(_) => return compare(Ordering::is_lt),
+      BinOp::Le(_) => return compare(Ordering::is_le),
+      BinOp::Gt(_) => return compare(Ordering::is_gt),
+      BinOp::Ge(_) => return compare(Ordering::is_ge),
+      _ => {}
+    }
+
+    match (left, right) {
+      (Self::Int(a), Self::Int(b)) => match op {
+        BinOp::Add(_) => a.checked_add(b),
+        BinOp::Sub(_) => a.checked_sub(b),
+        BinOp::Mul(_) => a.checked_mul(b),
+        BinOp::Div(_) => a.checked_div(b),
+        BinOp::Rem(_) => a.checked_rem(b),
+        BinOp::BitAnd(_) => Some(a & b),
+        BinOp::BitOr(_) => Some(a | b),
+        BinOp::BitXor(_) => Some(a ^ b),
+        _ => None,
+      }
+      .map(Self::Int),
+      (Self::Float(a), Self::Float(b)) => match op {
+        BinOp::Add(_) => Some(a + b),
+        BinOp::Sub(_) => Some(a - b),
+        BinOp::Mul(_) => Some(a * b),
+        BinOp::Div(_) => Some(a / b),
+        BinOp::Rem(_) => Some(a % b),
+        _ => None,
+      }
+      .map(Self::Float),
+      (Self::Bool(a), Self::Bool(b)) => match op {
+        BinOp::BitAnd(_) => Some(a & b),
+        BinOp::BitOr(_) => Some(a | b),
+        BinOp::BitXor(_) => Some(a ^ b),
+        _ => None,
+      }
+      .map(Self::Bool),
+      _ => None,
+    }
+  }
+}
@@ -85,6 +85,14 @@ impl<'a> ResolveFnArg<'a> {
   }
 }
 
+impl<'a> ResolveFnArg<'a> {
+  /// Returns `true` if the argument is resolved by reference, rather than by
+  /// transferring ownership.
+  pub fn is_reference(&self) -> bool {
+    self.and.is_some()
+  }
+}
+
 impl<'a> ToTokens for ResolveFnArg<'a> {
   fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
     self.and.to_tokens(tokens);
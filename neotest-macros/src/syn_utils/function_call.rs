@@ -9,9 +9,9 @@ use syn::token::Paren;
 /// definitions.
 ///
 /// [`Bracket`]: syn::token::Bracket
-pub struct FunctionCall<'a>(pub &'a syn::Ident);
+pub struct FunctionCall<'a, T: ?Sized + ToTokens = syn::Ident>(pub &'a T);
 
-impl<'a> FunctionCall<'a> {
+impl<'a, T: ?Sized + ToTokens> FunctionCall<'a, T> {
   /// Outputs anything from `f` into the [`TokenStream`] surrounding the contents
   /// with a module definition of the specified ident.
   ///
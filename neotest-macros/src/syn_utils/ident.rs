@@ -14,7 +14,7 @@ impl Ident for syn::Ident {
   }
 }
 
-impl<'a> Ident for &'a syn::Ident {
+impl Ident for &syn::Ident {
   fn ident(&self) -> &syn::Ident {
     self
   }
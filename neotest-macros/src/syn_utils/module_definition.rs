@@ -26,6 +26,7 @@ impl<'a> ModuleDefinition<'a> {
     F: FnOnce(&mut TokenStream),
   {
     attribute::allow_dead_code().to_tokens(tokens);
+    attribute::allow_non_snake_case().to_tokens(tokens);
     Mod::default().to_tokens(tokens);
     self.0.to_tokens(tokens);
     Brace::default().surround(tokens, |v| f(v));
@@ -0,0 +1,42 @@
+//! This internal module defines a simple utility for tokenizing generic
+//! arguments as a turbofish.
+use quote::ToTokens;
+use syn::token::{Colon2, Comma, Gt, Lt};
+
+/// A wrapper utility around a slice of generic arguments, which tokenizes them
+/// in the form of a turbofish (e.g. `::<T, U>`).
+///
+/// If the slice is empty, nothing is tokenized -- which allows this to be used
+/// unconditionally when calling functions that may or may not be generic.
+///
+/// # Example
+///
+/// Basic use:
+///
+/// ```ignore
+/// use crate::syn_utils::Turbofish;
+/// use quote::quote;
+///
+/// let args: Vec<syn::Type> = get_types();
+/// let turbofish = Turbofish(&args);
+///
+/// quote!{
+///     some_function #turbofish ()
+/// }
+/// ```
+pub struct Turbofish<'a, T: 'a>(pub &'a [T]);
+
+impl<'a, T: 'a + ToTokens> ToTokens for Turbofish<'a, T> {
+  fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+    if self.0.is_empty() {
+      return;
+    }
+    Colon2::default().to_tokens(tokens);
+    Lt::default().to_tokens(tokens);
+    for arg in self.0.iter() {
+      arg.to_tokens(tokens);
+      Comma::default().to_tokens(tokens);
+    }
+    Gt::default().to_tokens(tokens);
+  }
+}
@@ -1,4 +1,5 @@
 /// This
+use quote::ToTokens;
 use syn::punctuated::Punctuated;
 use syn::token::Comma;
 use syn::Type;
@@ -39,3 +40,11 @@ impl syn::parse::Parse for TypeSequence {
     })
   }
 }
+
+impl ToTokens for TypeSequence {
+  fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+    self.bracket_token.surround(tokens, |tokens| {
+      self.elems.to_tokens(tokens);
+    });
+  }
+}
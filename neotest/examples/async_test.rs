@@ -0,0 +1,164 @@
+#[cfg(test)]
+mod test {
+  use neotest::{neotest, neotest_fixture, subtest, AsyncFixture, Fixture, Result, TestOutcome};
+  use std::future::Future;
+  use std::pin::Pin;
+  use std::sync::atomic::{AtomicBool, Ordering};
+  use std::task::{Context, Poll};
+
+  /// A future that is pending the first time it is polled, so that tests
+  /// actually need to be woken by the executor.
+  struct YieldNow(bool);
+
+  impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+      if self.0 {
+        return Poll::Ready(());
+      }
+      self.0 = true;
+      cx.waker().wake_by_ref();
+      Poll::Pending
+    }
+  }
+
+  async fn yield_now() {
+    YieldNow(false).await
+  }
+
+  /// A connection which is opened and closed asynchronously.
+  struct Connection {
+    open: bool,
+    requests: Vec<String>,
+  }
+
+  impl Connection {
+    async fn send(&mut self, request: &str) -> usize {
+      yield_now().await;
+      self.requests.push(request.to_string());
+      self.requests.len()
+    }
+  }
+
+  impl AsyncFixture for Connection {
+    async fn prepare() -> Result<Self> {
+      yield_now().await;
+      Ok(Self {
+        open: true,
+        requests: Vec::new(),
+      })
+    }
+
+    async fn tear_down(mut self, _outcome: &TestOutcome) -> Result<()> {
+      yield_now().await;
+      self.open = false;
+      Ok(())
+    }
+  }
+
+  /// A synchronous fixture, which can be used by async tests as well.
+  #[derive(Default, Fixture)]
+  struct Counter {
+    count: usize,
+  }
+
+  #[neotest]
+  async fn test_awaits_future() {
+    yield_now().await;
+  }
+
+  #[neotest(fixture = Connection)]
+  async fn test_uses_async_fixture(conn: &mut Connection) {
+    assert!(conn.open);
+    assert_eq!(conn.send("ping").await, 1);
+  }
+
+  #[neotest(fixture = [Connection, Counter], parameter = n as [1, 3])]
+  async fn test_uses_fixtures_with_parameters(conn: &mut Connection, n: usize, c: &mut Counter) {
+    for _ in 0..n {
+      c.count = conn.send("ping").await;
+    }
+    assert_eq!(c.count, n);
+  }
+
+  #[neotest]
+  async fn test_returns_result() -> Result<()> {
+    yield_now().await;
+    Ok(())
+  }
+
+  #[neotest(fixture = Connection)]
+  async fn test_async_subtests(conn: &mut Connection) {
+    conn.send("setup").await;
+
+    subtest!(first, {
+      conn.send("first").await;
+      assert_eq!(conn.requests.last().unwrap(), "first");
+    });
+
+    subtest!(second, {
+      conn.send("second").await;
+      assert_eq!(conn.requests.last().unwrap(), "second");
+    });
+  }
+
+  #[derive(Default, Fixture)]
+  struct Service {
+    calls: usize,
+  }
+
+  #[neotest_fixture]
+  impl Service {
+    async fn call(&mut self) -> usize {
+      yield_now().await;
+      self.calls += 1;
+      self.calls
+    }
+
+    #[neotest]
+    async fn test_async_method(&mut self) {
+      assert_eq!(self.call().await, 1);
+    }
+  }
+
+  static SESSION_CLOSED: AtomicBool = AtomicBool::new(false);
+
+  struct Session;
+  struct Offline;
+
+  impl AsyncFixture for Session {
+    async fn prepare() -> Result<Self> {
+      Ok(Session)
+    }
+
+    async fn tear_down(self, outcome: &TestOutcome) -> Result<()> {
+      yield_now().await;
+      assert!(outcome.is_failure());
+      SESSION_CLOSED.store(true, Ordering::SeqCst);
+      Ok(())
+    }
+  }
+
+  impl AsyncFixture for Offline {
+    async fn prepare() -> Result<Self> {
+      yield_now().await;
+      Err("offline".into())
+    }
+  }
+
+  // This is only run by `test_setup_error_tears_down_async_fixtures`
+  #[neotest(fixture = [Session, Offline])]
+  #[ignore]
+  async fn abandoned(_: &Session, _: &Offline) {}
+
+  #[neotest]
+  fn test_setup_error_tears_down_async_fixtures() {
+    let error = abandoned().unwrap_err();
+
+    assert!(error.to_string().contains("Offline"));
+    assert!(SESSION_CLOSED.load(Ordering::SeqCst));
+  }
+}
+
+fn main() {}
@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod test {
+  use neotest::{neotest, neotest_suite, subtest};
+
+  #[cfg(unix)]
+  const UNIX_DIVISOR: u32 = 4;
+  #[cfg(not(unix))]
+  const OTHER_DIVISOR: u32 = 6;
+
+  #[neotest(
+    parameter = divisor as [
+      1,
+      2,
+      #[ignore = "bug 42"] 5,
+      // Inputs that are configured out may name items that do not exist
+      #[cfg(unix)] UNIX_DIVISOR,
+      #[cfg(not(unix))] OTHER_DIVISOR,
+      #[should_panic(expected = "divisor of zero")] zero: 0,
+    ],
+  )]
+  fn test_case_attributes(divisor: u32) {
+    assert_eq!(12 % divisor, 0);
+  }
+
+  #[neotest(
+    parameter = a as [1, #[should_panic] 0],
+    parameter = b as [#[ignore] 0, 1],
+  )]
+  fn test_combined_case_attributes(a: u32, b: u32) {
+    assert_eq!(a / a + b, 1 + b);
+  }
+
+  #[neotest(parameter = value as [1, #[should_panic] 0])]
+  fn test_case_attributes_with_subtests(value: u32) {
+    let inverse = 1 / value;
+
+    subtest!(is_one, {
+      assert_eq!(inverse, 1);
+    });
+  }
+
+  #[neotest_suite(
+    fixture(scope = module) = Counter,
+    parameter = value as [1, #[ignore] 2, #[cfg(unix)] 3, #[should_panic] 0],
+  )]
+  mod suite {
+    use neotest::{Fixture, Result, TestOutcome};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static PREPARE_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    pub struct Counter;
+
+    impl Fixture for Counter {
+      fn prepare() -> Result<Self> {
+        PREPARE_COUNT.fetch_add(1, Ordering::SeqCst);
+        Ok(Counter)
+      }
+
+      // Test-cases that panic as expected pass, and so do not fail the fixture
+      fn tear_down(self, outcome: &TestOutcome) -> Result<()> {
+        match outcome.is_failure() {
+          true => Err(format!("torn down after a test {outcome:?}").into()),
+          false => Ok(()),
+        }
+      }
+    }
+
+    // Configured-out test-cases never use module fixtures, and ignored ones
+    // only do so when run with `--ignored` or `--include-ignored`
+    #[neotest]
+    fn test_suite_case_attributes(_counter: &Counter, value: u32) {
+      assert_eq!(PREPARE_COUNT.load(Ordering::SeqCst), 1);
+      assert_ne!(value, 0);
+    }
+  }
+}
+
+fn main() {}
@@ -0,0 +1,47 @@
+#[cfg(test)]
+mod test {
+  use neotest::neotest;
+  use std::str::FromStr;
+
+  #[neotest(
+    // Each row becomes its own test-case, with each column providing the
+    // parameter of the same name
+    cases_from = "tests/data/parse.csv",
+  )]
+  fn test_cases_from_csv(input: &str, radix: u32, expected: u32) {
+    let input = input.replace('_', "");
+    assert_eq!(u32::from_str_radix(&input, radix).unwrap(), expected);
+  }
+
+  #[derive(Debug, PartialEq)]
+  struct Name(String);
+
+  impl FromStr for Name {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+      Ok(Name(s.to_string()))
+    }
+  }
+
+  #[neotest(
+    // Values are parsed with `FromStr` as the type of their parameter
+    cases_from = "tests/data/parse.jsonl",
+  )]
+  fn test_cases_from_json_lines(name: Name, value: i64, negative: bool) {
+    assert!(!name.0.is_empty());
+    assert_eq!(value < 0, negative);
+  }
+
+  #[neotest(
+    // Cases from files are combined with other parameter inputs
+    cases_from = "tests/data/parse.csv",
+    parameter = prefix as ["", "+"],
+  )]
+  fn test_cases_from_with_parameters(prefix: &str, input: &str, radix: u32, expected: u32) {
+    let input = format!("{prefix}{}", input.replace('_', ""));
+    assert_eq!(u32::from_str_radix(&input, radix).unwrap(), expected);
+  }
+}
+
+fn main() {}
@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod test {
+  use neotest::{neotest, subtest, Result};
+
+  #[neotest(const_parameter = N as [0, 1, 16, 256])]
+  fn test_array_len<const N: usize>() {
+    let sut = [0u8; N];
+
+    assert_eq!(sut.len(), N);
+  }
+
+  #[neotest(
+    const_parameter = N as [1, 4],
+    parameter = value as [0, 42],
+  )]
+  fn test_filled_array<const N: usize>(value: u32) {
+    let sut = [value; N];
+
+    subtest!(all_elements_are_value, {
+      assert!(sut.iter().all(|v| *v == value));
+    });
+    subtest!(sum_is_multiple_of_value, {
+      assert_eq!(sut.iter().sum::<u32>(), value * N as u32);
+    });
+  }
+
+  #[neotest(const_parameter = OFFSET as [-1, 1, -1i32 as i32])]
+  fn test_signed_offset<const OFFSET: i32>() {
+    assert_eq!(OFFSET.abs(), 1);
+  }
+
+  #[neotest]
+  fn test_signed_offset_names() -> Result<()> {
+    // Negative inputs keep their sign in their names
+    test_signed_offset::OFFSET_neg_1()?;
+    test_signed_offset::OFFSET_1()?;
+    test_signed_offset::OFFSET_neg_1i32_as_i32()
+  }
+
+  #[neotest(
+    type_parameter = T as [u8, u64],
+    const_parameter = ROWS as [1, 2],
+    const_parameter = COLS as [3, { 2 + 2 }],
+  )]
+  fn test_matrix<T, const ROWS: usize, const COLS: usize>()
+  where
+    T: Default + Copy,
+  {
+    let sut = [[T::default(); COLS]; ROWS];
+
+    assert_eq!(sut.len() * sut[0].len(), ROWS * COLS);
+  }
+}
+
+fn main() {}
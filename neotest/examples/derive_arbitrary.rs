@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod test {
+  use neotest::neotest;
+  use neotest::prop::{any, vec, Arbitrary, TestRng};
+
+  #[derive(Arbitrary, Clone, Debug, PartialEq)]
+  struct Point {
+    x: i32,
+    y: i32,
+  }
+
+  #[derive(Arbitrary, Clone, Debug, PartialEq)]
+  struct Rgb(
+    // Fields may be generated by a strategy, rather than by their type
+    #[arbitrary(strategy = 0..=255u16)] u16,
+    #[arbitrary(strategy = 0..=255u16)] u16,
+    #[arbitrary(strategy = 0..=255u16)] u16,
+  );
+
+  #[derive(Arbitrary, Clone, Debug, PartialEq)]
+  enum Shape {
+    // Variants are generated in proportion to their weight
+    #[arbitrary(weight = 4)]
+    Empty,
+    Circle {
+      center: Point,
+      radius: u8,
+    },
+    Polygon(#[arbitrary(strategy = vec(any::<Point>(), 3..8))] Vec<Point>),
+    #[arbitrary(weight = 0)]
+    #[allow(dead_code)]
+    Unused,
+  }
+
+  #[derive(Arbitrary, Clone, Debug, PartialEq)]
+  #[arbitrary(max_depth = 3)]
+  enum Expr {
+    Literal(#[arbitrary(strategy = -10..10i64)] i64),
+    Negate(Box<Expr>),
+    Add(Box<Expr>, Box<Expr>),
+  }
+
+  impl Expr {
+    fn depth(&self) -> usize {
+      match self {
+        Expr::Literal(_) => 1,
+        Expr::Negate(v) => 1 + v.depth(),
+        Expr::Add(a, b) => 1 + a.depth().max(b.depth()),
+      }
+    }
+
+    fn eval(&self) -> i64 {
+      match self {
+        Expr::Literal(v) => *v,
+        Expr::Negate(v) => -v.eval(),
+        Expr::Add(a, b) => a.eval() + b.eval(),
+      }
+    }
+  }
+
+  #[derive(Arbitrary, Clone, Debug, PartialEq)]
+  struct Pair<T> {
+    first: T,
+    second: T,
+  }
+
+  #[neotest(parameter = point as any::<Point>())]
+  fn test_derived_struct(point: Point) {
+    let mirrored = Point {
+      x: point.y,
+      y: point.x,
+    };
+    assert_eq!(mirrored.x, point.y);
+  }
+
+  #[neotest(parameter = color as any::<Rgb>())]
+  fn test_field_strategy(color: Rgb) {
+    assert!(color.0 <= 255 && color.1 <= 255 && color.2 <= 255);
+  }
+
+  #[neotest(parameter = shape as any::<Shape>())]
+  fn test_derived_enum(shape: Shape) {
+    assert_ne!(shape, Shape::Unused);
+    if let Shape::Polygon(points) = shape {
+      assert!((3..8).contains(&points.len()));
+    }
+  }
+
+  #[neotest(parameter = expr as any::<Expr>(), cases = 64)]
+  fn test_recursive_depth(expr: Expr) {
+    // Only literals are generated once nested more than `max_depth` times
+    assert!(expr.depth() <= 4);
+    assert!(expr.eval().abs() < 10 << 4);
+  }
+
+  #[neotest(parameter = pair as any::<Pair<u8>>())]
+  fn test_generic_struct(pair: Pair<u8>) {
+    assert!(u16::from(pair.first) + u16::from(pair.second) <= 510);
+  }
+
+  #[neotest]
+  fn test_shrinks_field_wise() {
+    let point = Point { x: 4, y: 0 };
+
+    assert_eq!(point.shrink()[0], Point { x: 0, y: 0 });
+    assert!(point.shrink().iter().all(|v| v.y == 0));
+  }
+
+  #[neotest]
+  fn test_shrinks_to_simpler_variants() {
+    let shape = Shape::Circle {
+      center: Point { x: 0, y: 0 },
+      radius: 3,
+    };
+    let expr = Expr::Negate(Box::new(Expr::Literal(5)));
+
+    assert_eq!(shape.shrink()[0], Shape::Empty);
+    assert_eq!(expr.shrink()[0], Expr::Literal(5));
+  }
+
+  #[neotest]
+  fn test_generation_is_seeded() {
+    let mut a = TestRng::from_seed(7);
+    let mut b = TestRng::from_seed(7);
+
+    assert_eq!(Expr::arbitrary(&mut a), Expr::arbitrary(&mut b));
+  }
+}
+
+fn main() {}
@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod test {
+  use neotest::{neotest, Fixture, Result, TestOutcome};
+  use std::cell::RefCell;
+
+  #[derive(Default, Fixture)]
+  struct Database {
+    rows: Vec<u32>,
+  }
+
+  fn open_log() -> Result<Vec<String>> {
+    Ok(vec!["opened".to_string()])
+  }
+
+  #[derive(Fixture)]
+  struct Composite {
+    #[fixture(default = 42)]
+    answer: u32,
+    #[fixture(with = open_log)]
+    log: Vec<String>,
+    #[fixture(fixture)]
+    db: Database,
+    name: String,
+  }
+
+  #[derive(Fixture)]
+  struct Harness<T: Clone + Default> {
+    #[fixture(default = vec![T::default(); 2])]
+    values: Vec<T>,
+    #[fixture(fixture)]
+    inner: Wrapper<T>,
+  }
+
+  #[derive(Default, Fixture)]
+  struct Wrapper<T>(T);
+
+  #[derive(Fixture)]
+  struct Tuple(#[fixture(default = "hello")] &'static str, u32);
+
+  thread_local! {
+    static EVENTS: RefCell<Vec<String>> = RefCell::new(Vec::new());
+  }
+
+  /// A fixture that records when it is prepared and torn down.
+  struct Recorder<const ID: u32>;
+
+  impl<const ID: u32> Fixture for Recorder<ID> {
+    fn prepare() -> Result<Self> {
+      EVENTS.with(|v| v.borrow_mut().push(format!("prepare {ID}")));
+      Ok(Recorder)
+    }
+
+    fn tear_down(self, _outcome: &TestOutcome) -> Result<()> {
+      EVENTS.with(|v| v.borrow_mut().push(format!("tear down {ID}")));
+      Ok(())
+    }
+  }
+
+  fn fail_to_open() -> Result<u32> {
+    Err("failed to open".into())
+  }
+
+  #[derive(Fixture)]
+  struct Nested {
+    #[fixture(fixture)]
+    _first: Recorder<1>,
+    #[fixture(default = 2)]
+    _value: u32,
+    #[fixture(fixture)]
+    _second: Recorder<2>,
+  }
+
+  #[derive(Fixture)]
+  struct PartiallyPrepared {
+    #[fixture(fixture)]
+    _first: Recorder<1>,
+    #[fixture(fixture)]
+    _second: Recorder<2>,
+    #[fixture(with = fail_to_open)]
+    _value: u32,
+    #[fixture(fixture)]
+    _third: Recorder<3>,
+  }
+
+  #[neotest(fixture = Composite)]
+  fn test_composite_fixture(f: &Composite) {
+    assert_eq!(f.answer, 42);
+    assert_eq!(f.log, vec!["opened".to_string()]);
+    assert!(f.db.rows.is_empty());
+    assert!(f.name.is_empty());
+  }
+
+  #[neotest(fixture = Harness<T>, type_parameter = T as [u32, String])]
+  fn test_generic_fixture<T: Clone + Default + PartialEq + std::fmt::Debug>(f: &Harness<T>) {
+    assert_eq!(f.values, vec![T::default(), T::default()]);
+    assert_eq!(f.inner.0, T::default());
+  }
+
+  #[neotest]
+  fn test_nested_fixtures_torn_down_in_reverse() -> Result<()> {
+    let nested = Nested::prepare()?;
+    nested.tear_down(&TestOutcome::Passed)?;
+
+    let events = EVENTS.with(|v| v.take());
+    assert_eq!(
+      events,
+      ["prepare 1", "prepare 2", "tear down 2", "tear down 1"]
+    );
+    Ok(())
+  }
+
+  #[neotest]
+  fn test_nested_fixtures_torn_down_on_failure() {
+    let error = PartiallyPrepared::prepare().err().unwrap();
+
+    assert_eq!(error.to_string(), "failed to open");
+    let events = EVENTS.with(|v| v.take());
+    assert_eq!(
+      events,
+      ["prepare 1", "prepare 2", "tear down 2", "tear down 1"]
+    );
+  }
+
+  #[neotest(fixture = Tuple)]
+  fn test_tuple_fixture(f: Tuple) {
+    assert_eq!(f.0, "hello");
+    assert_eq!(f.1, 0);
+  }
+}
+
+fn main() {}
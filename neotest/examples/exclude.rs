@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod test {
+  use neotest::{neotest, neotest_suite};
+
+  #[neotest(
+    parameter = a as [1, 2, 3],
+    parameter = b as [1, 2, 3],
+    // Literal combinations are filtered when the test is expanded, and are
+    // reported as ignored
+    exclude = |a, b| a == b,
+  )]
+  fn test_exclude_literal(a: u32, b: u32) {
+    assert_ne!(a, b);
+  }
+
+  #[neotest(
+    parameter = divisor as [0, 1, u32::MAX],
+    parameter = value as [1, u32::MAX],
+    // Other combinations are filtered when they are run, and so are reported
+    // as passing; each argument is a reference to the input of its parameter
+    exclude = |divisor, value| *divisor == 0 || divisor == value,
+  )]
+  fn test_exclude_at_runtime(divisor: u32, value: u32) {
+    assert_ne!(divisor, value);
+    assert!(value / divisor <= value);
+  }
+
+  #[neotest(
+    parameter = value from 0..10,
+    parameter = step as [1, 2],
+    // Generated cases are filtered individually
+    exclude = |&value, &step| value % step != 0,
+  )]
+  fn test_exclude_generated(value: u32, step: u32) {
+    assert_eq!(value % step, 0);
+  }
+
+  #[neotest_suite(
+    fixture(scope = module) = Counter,
+    parameter = value as [1, 2, 3],
+    exclude = |value| *value == 2,
+  )]
+  mod suite {
+    use neotest::{Fixture, Result};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static PREPARE_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    pub struct Counter;
+
+    impl Fixture for Counter {
+      fn prepare() -> Result<Self> {
+        PREPARE_COUNT.fetch_add(1, Ordering::SeqCst);
+        Ok(Counter)
+      }
+    }
+
+    #[neotest]
+    fn test_suite_exclude(_counter: &Counter, value: u32) {
+      assert_ne!(value, 2);
+      assert_eq!(PREPARE_COUNT.load(Ordering::SeqCst), 1);
+    }
+
+    // Filtered cases still release their use of module fixtures
+    #[neotest(parameter = step from [1, 2, 3])]
+    fn test_suite_exclude_generated(_counter: &Counter, value: u32, step: u32) {
+      assert_ne!(value * step, 0);
+      assert_eq!(PREPARE_COUNT.load(Ordering::SeqCst), 1);
+    }
+  }
+}
+
+fn main() {}
@@ -0,0 +1,43 @@
+#[cfg(test)]
+mod test {
+  use neotest::neotest;
+  use std::path::Path;
+
+  fn evaluate(expression: &str) -> i64 {
+    let tokens: Vec<&str> = expression.split_whitespace().collect();
+    let (lhs, rhs) = (
+      tokens[0].parse::<i64>().unwrap(),
+      tokens[2].parse::<i64>().unwrap(),
+    );
+    match tokens[1] {
+      "+" => lhs + rhs,
+      "-" => lhs - rhs,
+      "*" => lhs * rhs,
+      _ => unreachable!(),
+    }
+  }
+
+  #[neotest(
+    // Each file matching the pattern becomes its own test-case, named after
+    // its path, e.g. `path_nested_subtract`
+    parameter = path as files("tests/data/corpus/**/*.input"),
+  )]
+  fn test_files(path: &Path) {
+    let input = std::fs::read_to_string(path).unwrap();
+    let expected = std::fs::read_to_string(path.with_extension("expected")).unwrap();
+
+    assert_eq!(evaluate(&input).to_string(), expected.trim());
+  }
+
+  #[neotest(
+    // The contents of each file may be used instead of its path
+    parameter = input as file_contents("tests/data/corpus/*.input"),
+    parameter = expected as file_contents("tests/data/corpus/*.expected"),
+    zip(input, expected),
+  )]
+  fn test_file_contents(input: &str, expected: &str) {
+    assert_eq!(evaluate(input).to_string(), expected.trim());
+  }
+}
+
+fn main() {}
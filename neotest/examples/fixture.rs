@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod test {
-  use neotest::{neotest, Fixture};
+  use neotest::{neotest, Fixture, Result, SetupError};
+  use std::any::type_name;
   #[derive(Default, Fixture)]
   struct SomeFixture {}
 
@@ -42,6 +43,36 @@ mod test {
     f.do_something_mut();
     assert!(f.do_something_truthy());
   }
+
+  struct BrokenFixture;
+
+  impl Fixture for BrokenFixture {
+    fn prepare() -> Result<Self> {
+      Err("no database".into())
+    }
+  }
+
+  // This is only run by `test_setup_error`
+  #[neotest(fixture = BrokenFixture)]
+  #[ignore]
+  fn unprepared(_: &BrokenFixture) {}
+
+  #[neotest]
+  fn test_setup_error() {
+    let error = unprepared().unwrap_err();
+
+    let setup = error.downcast_ref::<SetupError>().unwrap();
+    assert_eq!(setup.fixture(), type_name::<BrokenFixture>());
+    assert!(setup.test().ends_with("unprepared"));
+    assert_eq!(
+      format!("{error:?}"),
+      format!(
+        "failed to set up fixture `{}` for test `{}`: no database",
+        setup.fixture(),
+        setup.test()
+      )
+    );
+  }
 }
 
 fn main() {}
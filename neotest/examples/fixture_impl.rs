@@ -0,0 +1,56 @@
+#[cfg(test)]
+mod test {
+  use neotest::{neotest_fixture, subtest, Fixture};
+
+  #[derive(Default, Fixture)]
+  struct SomeFixture {
+    values: Vec<u32>,
+  }
+
+  #[neotest_fixture]
+  impl SomeFixture {
+    // Methods without `#[neotest]` are left as-is
+    fn push(&mut self, value: u32) {
+      self.values.push(value);
+    }
+
+    #[neotest]
+    fn test_fixture(self) {
+      assert!(self.values.is_empty());
+    }
+
+    #[neotest]
+    fn test_ref_fixture(&self) {
+      assert_eq!(self.values.len(), 0);
+    }
+
+    #[neotest::neotest]
+    fn test_path_attribute(&self) {
+      assert!(self.values.is_empty());
+    }
+
+    #[neotest(parameter = value as [1, 2, 42])]
+    fn test_mut_ref_fixture(&mut self, value: u32) {
+      self.push(value);
+
+      subtest!(contains_value, {
+        assert!(self.values.contains(&value));
+      });
+      subtest!(has_one_value, {
+        assert_eq!(self.values.len(), 1);
+      });
+    }
+
+    #[neotest(type_parameter = T as [u8, u64])]
+    fn test_generic_fixture<T>(mut self)
+    where
+      T: Into<u64> + Default,
+    {
+      self.values.push(T::default().into() as u32);
+
+      assert_eq!(self.values, vec![0]);
+    }
+  }
+}
+
+fn main() {}
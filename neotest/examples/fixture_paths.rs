@@ -0,0 +1,47 @@
+#[cfg(test)]
+mod support {
+  use neotest::{Fixture, Result};
+
+  #[derive(Default, Fixture)]
+  pub struct DbFixture {
+    pub rows: Vec<u32>,
+  }
+
+  pub struct Harness<T> {
+    pub values: Vec<T>,
+  }
+
+  impl<T: Default> Fixture for Harness<T> {
+    fn prepare() -> Result<Self> {
+      Ok(Self {
+        values: vec![T::default()],
+      })
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::support::Harness;
+  use neotest::neotest;
+
+  #[neotest(fixture = crate::support::DbFixture)]
+  fn test_fixture_path(db: &crate::support::DbFixture) {
+    assert!(db.rows.is_empty());
+  }
+
+  #[neotest(fixture = super::support::Harness<String>)]
+  fn test_generic_fixture(harness: &Harness<String>) {
+    assert_eq!(harness.values, vec![String::new()]);
+  }
+
+  #[neotest(fixture = Harness<T>, type_parameter = T as [u32, String])]
+  fn test_fixture_of_type_parameter<T: Default + PartialEq + std::fmt::Debug>(
+    harness: &mut Harness<T>,
+  ) {
+    harness.values.push(T::default());
+    assert_eq!(harness.values, vec![T::default(), T::default()]);
+  }
+}
+
+fn main() {}
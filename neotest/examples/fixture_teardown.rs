@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod test {
+  use neotest::{neotest, Fixture, Result, TeardownError, TestOutcome};
+  use std::cell::Cell;
+  use std::panic;
+
+  /// A fixture that tracks temporary files, and reports any that were leaked
+  /// by the test when it is torn down.
+  struct TempFiles {
+    files: Vec<String>,
+  }
+
+  impl TempFiles {
+    fn create(&mut self, name: &str) {
+      self.files.push(name.to_string());
+    }
+
+    fn remove(&mut self, name: &str) {
+      self.files.retain(|v| v != name);
+    }
+  }
+
+  impl Fixture for TempFiles {
+    fn prepare() -> Result<Self> {
+      Ok(Self { files: Vec::new() })
+    }
+
+    fn tear_down(self, outcome: &TestOutcome) -> Result<()> {
+      if outcome.is_failure() {
+        eprintln!("files at time of failure: {:?}", self.files);
+      }
+      if !self.files.is_empty() {
+        return Err(format!("leaked temp files: {:?}", self.files).into());
+      }
+      Ok(())
+    }
+  }
+
+  #[neotest(fixture = TempFiles)]
+  fn test_cleans_up_files(f: &mut TempFiles) {
+    f.create("a.txt");
+    f.remove("a.txt");
+  }
+
+  #[neotest(fixture = TempFiles, parameter = name as ["a.txt", "b.txt"])]
+  fn test_cleans_up_named_file(f: &mut TempFiles, name: &str) {
+    f.create(name);
+    assert_eq!(f.files.len(), 1);
+    f.remove(name);
+  }
+
+  // This is only run by `test_tear_down_error`
+  #[neotest(fixture = TempFiles)]
+  #[ignore]
+  fn leaks_file(f: &mut TempFiles) {
+    f.create("leaked.txt");
+  }
+
+  #[neotest]
+  fn test_tear_down_error() {
+    let error = leaks_file().unwrap_err();
+
+    let teardown = error.downcast_ref::<TeardownError>().unwrap();
+    assert!(teardown.fixture().ends_with("TempFiles"));
+    assert!(error
+      .to_string()
+      .ends_with("leaked temp files: [\"leaked.txt\"]"));
+  }
+
+  thread_local! {
+    static TORN_DOWN: Cell<Option<TestOutcome>> = const { Cell::new(None) };
+  }
+
+  /// A fixture that records the outcome it was torn down with.
+  struct Recorder;
+
+  impl Fixture for Recorder {
+    fn prepare() -> Result<Self> {
+      Ok(Self)
+    }
+
+    fn tear_down(self, outcome: &TestOutcome) -> Result<()> {
+      TORN_DOWN.with(|v| v.set(Some(*outcome)));
+      Ok(())
+    }
+  }
+
+  // This is only run by `test_tears_down_after_error`
+  #[neotest(fixture = Recorder)]
+  #[ignore]
+  fn fails_with_error(_: &Recorder) -> Result<()> {
+    Err("expected".into())
+  }
+
+  // This is only run by `test_tears_down_after_panic`
+  #[neotest(fixture = Recorder)]
+  #[ignore]
+  fn fails_with_panic(_: &Recorder) {
+    panic!("expected");
+  }
+
+  #[neotest]
+  fn test_tears_down_after_error() {
+    let error = fails_with_error().unwrap_err();
+
+    assert_eq!(error.to_string(), "expected");
+    assert_eq!(TORN_DOWN.with(Cell::get), Some(TestOutcome::Failed));
+  }
+
+  #[neotest]
+  fn test_tears_down_after_panic() {
+    let result = panic::catch_unwind(fails_with_panic);
+
+    assert!(result.is_err());
+    assert_eq!(TORN_DOWN.with(Cell::get), Some(TestOutcome::Panicked));
+  }
+}
+
+fn main() {}
@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod test {
+  use neotest::{neotest, neotest_suite, subtest, ParameterizedFixture};
+
+  #[derive(Debug, Clone, Copy, PartialEq)]
+  enum Preset {
+    Small,
+    Medium,
+    Large,
+  }
+
+  impl Preset {
+    fn all() -> [Preset; 3] {
+      [Preset::Small, Preset::Medium, Preset::Large]
+    }
+
+    fn capacity(self) -> usize {
+      match self {
+        Preset::Small => 16,
+        Preset::Medium => 256,
+        Preset::Large => 4096,
+      }
+    }
+  }
+
+  fn powers_of_two(limit: u32) -> impl Iterator<Item = u32> {
+    (0..limit).map(|v| 1 << v)
+  }
+
+  #[neotest(
+    // Inputs are generated from any `IntoIterator` when the test is run
+    parameter = preset from Preset::all(),
+  )]
+  fn test_generated_parameter(preset: Preset) {
+    assert!(preset.capacity().is_power_of_two());
+  }
+
+  #[neotest(
+    // Generated parameters are run within each test-case of other parameters
+    parameter = value from powers_of_two(8),
+    parameter = shift as [0, 1],
+  )]
+  fn test_generated_with_other_parameters(value: u32, shift: u32) {
+    assert!((value << shift).is_power_of_two());
+  }
+
+  #[neotest(
+    // Every combination of several generated parameters is run
+    parameter = preset from Preset::all(),
+    parameter = name from vec!["a".to_string(), "b".to_string()],
+  )]
+  fn test_multiple_generated_parameters(preset: Preset, name: String) {
+    assert_ne!(preset.capacity(), name.len());
+  }
+
+  #[neotest(parameter = preset from Preset::all())]
+  fn test_generated_parameter_with_subtests(preset: Preset) {
+    let capacity = preset.capacity();
+
+    subtest!(nonzero, {
+      assert_ne!(capacity, 0);
+    });
+
+    subtest!(bounded, {
+      assert!(capacity <= 4096);
+    });
+  }
+
+  struct Buffer(Vec<u8>);
+
+  impl ParameterizedFixture<Preset> for Buffer {
+    fn prepare_with(preset: Preset) -> neotest::Result<Self> {
+      Ok(Buffer(Vec::with_capacity(preset.capacity())))
+    }
+  }
+
+  #[neotest(
+    fixture = Buffer(preset),
+    parameter = preset from Preset::all(),
+  )]
+  fn test_generated_parameter_fixture(buffer: &Buffer, preset: Preset) {
+    assert!(buffer.0.capacity() >= preset.capacity());
+  }
+
+  #[neotest_suite(parameter = preset from Preset::all())]
+  mod suite {
+    use super::*;
+
+    #[neotest]
+    fn test_suite_generated_parameter(preset: Preset) {
+      assert!(Preset::all().contains(&preset));
+    }
+  }
+}
+
+fn main() {}
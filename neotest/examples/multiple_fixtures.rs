@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod test {
+  use neotest::{neotest, Fixture, Result, TestOutcome};
+  use std::sync::Mutex;
+
+  #[derive(Default, Fixture)]
+  struct Database {
+    rows: Vec<u32>,
+  }
+
+  struct TempDir {
+    path: String,
+    files: Vec<String>,
+  }
+
+  impl Fixture for TempDir {
+    fn prepare() -> Result<Self> {
+      Ok(Self {
+        path: "/tmp/neotest".to_string(),
+        files: Vec::new(),
+      })
+    }
+
+    fn tear_down(self, _: &TestOutcome) -> Result<()> {
+      if !self.files.is_empty() {
+        return Err(format!("leaked temp files: {:?}", self.files).into());
+      }
+      Ok(())
+    }
+  }
+
+  #[neotest(fixture = [Database, TempDir])]
+  fn test_named_fixtures(db: &mut Database, dir: &TempDir) {
+    db.rows.push(1);
+    assert_eq!(db.rows.len(), 1);
+    assert_eq!(dir.path, "/tmp/neotest");
+  }
+
+  // Fixtures do not need to be named, and may appear in any position
+  #[neotest(parameter = value as [1, 2, 3])]
+  fn test_injected_fixtures(value: u32, dir: &mut TempDir, db: Database) {
+    dir.files.push(format!("{value}.txt"));
+    assert!(db.rows.is_empty());
+    dir.files.clear();
+  }
+
+  static TORN_DOWN: Mutex<Vec<&str>> = Mutex::new(Vec::new());
+
+  struct First;
+  struct Second;
+  struct Unavailable;
+
+  impl Fixture for First {
+    fn prepare() -> Result<Self> {
+      Ok(First)
+    }
+
+    fn tear_down(self, outcome: &TestOutcome) -> Result<()> {
+      assert!(outcome.is_failure());
+      TORN_DOWN.lock().unwrap().push("first");
+      Ok(())
+    }
+  }
+
+  impl Fixture for Second {
+    fn prepare() -> Result<Self> {
+      Ok(Second)
+    }
+
+    fn tear_down(self, outcome: &TestOutcome) -> Result<()> {
+      assert!(outcome.is_failure());
+      TORN_DOWN.lock().unwrap().push("second");
+      Ok(())
+    }
+  }
+
+  impl Fixture for Unavailable {
+    fn prepare() -> Result<Self> {
+      Err("unavailable".into())
+    }
+  }
+
+  // This is only run by `test_setup_error_tears_down_prepared_fixtures`
+  #[neotest(fixture = [First, Second, Unavailable])]
+  #[ignore]
+  fn abandoned(_: &First, _: &Second, _: &Unavailable) {}
+
+  #[neotest]
+  fn test_setup_error_tears_down_prepared_fixtures() {
+    let error = abandoned().unwrap_err();
+
+    assert!(error.to_string().contains("Unavailable"));
+    // Fixtures are torn down in the reverse order that they were prepared
+    assert_eq!(*TORN_DOWN.lock().unwrap(), vec!["second", "first"]);
+  }
+}
+
+fn main() {}
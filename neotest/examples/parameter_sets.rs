@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod sets {
+  use neotest::parameter_set;
+
+  // Sets are visible wherever their visibility allows, like any other macro
+  parameter_set!(pub(crate) EDGE_U32 = [zero: 0, one: 1, max: u32::MAX]);
+  parameter_set!(pub(crate) SHIFTS = [0, 1, 31]);
+  parameter_set!(pub(crate) CORPUS = file_contents("tests/data/corpus/*.input"));
+}
+
+#[cfg(test)]
+mod test {
+  use crate::sets::{EDGE_U32, SHIFTS};
+  use neotest::{neotest, neotest_fixture, neotest_suite, parameter_set, Fixture};
+
+  parameter_set!(SMALL = [1, 2, 3]);
+
+  #[neotest(parameter = value as EDGE_U32)]
+  fn test_parameter_set(value: u32) {
+    assert!(value == 0 || value == 1 || value == u32::MAX);
+  }
+
+  #[neotest(
+    // Sets may be named by their path, and combined with other inputs
+    parameter = value as crate::sets::EDGE_U32,
+    parameter = shift as SHIFTS,
+    parameter = scale as [1, 2],
+  )]
+  fn test_multiple_parameter_sets(value: u32, shift: u32, scale: u32) {
+    let _ = value.wrapping_shl(shift).wrapping_mul(scale);
+  }
+
+  #[neotest(parameter = input as crate::sets::CORPUS)]
+  fn test_file_parameter_set(input: &str) {
+    assert!(!input.trim().is_empty());
+  }
+
+  #[neotest_suite(parameter = value as SMALL)]
+  mod suite {
+    #[neotest]
+    fn test_suite_parameter_set(value: u32) {
+      assert!((1..=3).contains(&value));
+    }
+
+    // Sets of the tests within a suite are named from the scope of the suite
+    #[neotest(parameter = shift as SHIFTS)]
+    fn test_suite_nested_parameter_set(value: u32, shift: u32) {
+      assert!(value.checked_shl(shift).is_some());
+    }
+  }
+
+  #[derive(Default, Fixture)]
+  struct Counter {
+    count: u32,
+  }
+
+  #[neotest_fixture]
+  impl Counter {
+    #[neotest(parameter = value as SMALL)]
+    fn test_fixture_parameter_set(&mut self, value: u32) {
+      self.count += value;
+      assert_eq!(self.count, value);
+    }
+  }
+}
+
+fn main() {}
@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod test {
+  use neotest::{neotest, ParameterizedFixture, Result, TestOutcome};
+  use std::collections::HashMap;
+
+  /// The storage backends that a database can be configured with.
+  #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+  enum Backend {
+    Memory,
+    Journal,
+  }
+
+  /// An in-memory database, which is prepared separately for each backend that
+  /// a test is parameterized over.
+  struct Database {
+    backend: Backend,
+    entries: HashMap<String, String>,
+    journal: Vec<String>,
+  }
+
+  impl Database {
+    fn insert(&mut self, key: &str, value: &str) {
+      if self.backend == Backend::Journal {
+        self.journal.push(format!("insert {key}"));
+      }
+      self.entries.insert(key.to_string(), value.to_string());
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+      self.entries.get(key).map(String::as_str)
+    }
+  }
+
+  impl ParameterizedFixture<Backend> for Database {
+    fn prepare_with(backend: Backend) -> Result<Self> {
+      Ok(Self {
+        backend,
+        entries: HashMap::new(),
+        journal: Vec::new(),
+      })
+    }
+
+    fn tear_down(self, _outcome: &TestOutcome) -> Result<()> {
+      if self.backend == Backend::Journal && self.journal.len() != self.entries.len() {
+        return Err("journal does not match the stored entries".into());
+      }
+      Ok(())
+    }
+  }
+
+  /// A key-space of the database, which is prepared from multiple parameters.
+  struct Namespace {
+    prefix: String,
+  }
+
+  impl ParameterizedFixture<(Backend, &str)> for Namespace {
+    fn prepare_with((backend, name): (Backend, &str)) -> Result<Self> {
+      Ok(Self {
+        prefix: format!("{backend:?}/{name}"),
+      })
+    }
+  }
+
+  #[neotest(
+    fixture = Database(backend),
+    parameter = backend as [Backend::Memory, Backend::Journal],
+  )]
+  fn test_database_matches_backend(db: &mut Database, backend: Backend) {
+    assert_eq!(db.backend, backend);
+    db.insert("key", "value");
+    assert_eq!(db.get("key"), Some("value"));
+  }
+
+  #[neotest(
+    fixture = [Database(backend), Namespace(backend, name)],
+    parameter = backend as [Backend::Memory, Backend::Journal],
+    parameter = name as ["users", "groups"],
+  )]
+  fn test_namespace_matches_inputs(
+    db: &mut Database,
+    ns: &Namespace,
+    backend: Backend,
+    name: &str,
+  ) {
+    assert_eq!(ns.prefix, format!("{backend:?}/{name}"));
+    db.insert(&ns.prefix, name);
+    assert_eq!(db.get(&ns.prefix), Some(name));
+  }
+}
+
+fn main() {}
@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod test {
-  use neotest::neotest;
+  use neotest::{neotest, Result};
 
   #[neotest(
     // Order does not need to match input order
@@ -12,6 +12,39 @@ mod test {
     assert_ne!(a, b_int);
   }
 
+  #[neotest(
+    // Test-cases are named after their inputs, such as `value_max_radix_16`;
+    // labels name inputs that are not simple literals
+    parameter = value as [zero: 0, max: u32::MAX],
+    parameter = radix as [2, 16],
+  )]
+  fn test_labelled_parameter(value: u32, radix: u32) {
+    let s = match radix {
+      2 => format!("{value:b}"),
+      _ => format!("{value:x}"),
+    };
+    assert_eq!(u32::from_str_radix(&s, radix).unwrap(), value);
+  }
+
+  #[neotest(
+    // Inputs that cannot be named are named by their index, such as
+    // `value_idx1`; test-cases whose names collide are made unique by the
+    // index of the test-case, such as `value_1_0` and `value_1_3`
+    parameter = value as [1, -1, i32::MIN, 1],
+  )]
+  fn test_unnamed_parameter(value: i32) {
+    assert_ne!(value, 0);
+  }
+
+  #[neotest]
+  fn test_unnamed_parameter_names() -> Result<()> {
+    // Inputs named by their index do not collide with integer literals
+    test_unnamed_parameter::value_1_0()?;
+    test_unnamed_parameter::value_idx1()?;
+    test_unnamed_parameter::value_idx2()?;
+    test_unnamed_parameter::value_1_3()
+  }
+
   #[neotest(
     // To avoid combinatorics, tuples can be used for inputs
     parameter = a as [(1,1), (2,2), (3,3)]
@@ -22,6 +55,40 @@ mod test {
 
     assert_eq!(value, expect);
   }
+
+  #[neotest(
+    // Zipped parameters are paired by position, rather than being combined
+    parameter = input as ["1", "22", "333"],
+    parameter = expected as [1, 22, 333],
+    zip(input, expected),
+  )]
+  fn test_zipped_parameters(input: &str, expected: u32) {
+    assert_eq!(input.parse::<u32>().unwrap(), expected);
+  }
+
+  #[neotest(
+    parameter = radix as [10, 16],
+    parameter = input as ["10", "11"],
+    parameter = expected as [10, 11],
+    zip(input, expected),
+  )]
+  fn test_zipped_with_other_parameters(radix: u32, input: &str, expected: u32) {
+    let value = u32::from_str_radix(input, radix).unwrap();
+    assert!(value >= expected);
+  }
+
+  #[neotest(
+    // Only tests enough cases to cover every pair of inputs, rather than all
+    // 81 combinations of inputs
+    parameter = a as [0, 1, 2],
+    parameter = b as [0, 1, 2],
+    parameter = c as [0, 1, 2],
+    parameter = d as [0, 1, 2],
+    combinations = pairwise,
+  )]
+  fn test_pairwise_parameters(a: u32, b: u32, c: u32, d: u32) {
+    assert!(a + b + c + d <= 8);
+  }
 }
 
 fn main() {}
@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod test {
+  use neotest::prop::{any, just, vec};
+  use neotest::{neotest, neotest_suite, subtest, Fixture};
+
+  #[neotest(
+    // Values are generated by the strategy of each parameter
+    parameter = value as any::<u32>(),
+  )]
+  fn test_property(value: u32) {
+    assert_eq!(value.reverse_bits().reverse_bits(), value);
+  }
+
+  #[neotest(
+    // Each case generates a value for every parameter
+    parameter = bytes as vec(any::<u8>(), 0..64),
+    parameter = index as 0..64usize,
+    cases = 64,
+  )]
+  fn test_multiple_properties(bytes: Vec<u8>, index: usize) {
+    assert!(bytes.len() < 64);
+    assert_eq!(bytes.get(index).is_some(), index < bytes.len());
+  }
+
+  #[neotest(
+    // Strategies compose into tuples, and can be mixed with listed inputs
+    parameter = pair as (1..=6u8, just('x')),
+    parameter = scale as [1, 2],
+  )]
+  fn test_property_with_parameters(pair: (u8, char), scale: u8) {
+    let (die, c) = pair;
+    assert!((1..=12).contains(&(die * scale)));
+    assert_eq!(c, 'x');
+  }
+
+  const PERCENT: std::ops::RangeInclusive<u8> = 0..=100;
+
+  #[neotest(
+    // A lone path names a parameter set, so a constant strategy is written in
+    // parentheses
+    parameter = percent as (PERCENT),
+  )]
+  fn test_constant_strategy(percent: u8) {
+    assert!(percent <= 100);
+  }
+
+  #[neotest(parameter = text as any::<String>())]
+  fn test_property_with_subtests(text: String) {
+    let upper = text.to_uppercase();
+
+    subtest!(idempotent, {
+      assert_eq!(upper.to_uppercase(), upper);
+    });
+
+    subtest!(same_chars, {
+      assert_eq!(upper.chars().count() >= text.chars().count(), true);
+    });
+  }
+
+  #[derive(Default)]
+  struct Stack(Vec<u32>);
+
+  impl Fixture for Stack {
+    fn prepare() -> neotest::Result<Self> {
+      Ok(Stack::default())
+    }
+  }
+
+  #[neotest(
+    fixture = Stack,
+    parameter = values as vec(any::<u32>(), 0..16),
+  )]
+  fn test_property_with_fixture(stack: &mut Stack, values: Vec<u32>) {
+    stack.0.clear();
+    stack.0.extend(values.iter().copied());
+    let popped: Vec<u32> = std::iter::from_fn(|| stack.0.pop()).collect();
+    assert!(popped.iter().rev().eq(values.iter()));
+  }
+
+  #[neotest(
+    parameter = value as any::<i64>(),
+    // Excluded values are skipped, but still count as a case
+    exclude = |value| *value == i64::MIN,
+  )]
+  fn test_property_with_exclude(value: i64) {
+    assert!(value.abs() >= 0);
+  }
+
+  #[neotest_suite(parameter = value as any::<u16>(), cases = 32)]
+  mod suite {
+    use super::*;
+
+    #[neotest]
+    fn test_suite_property(value: u16) {
+      assert_eq!(u32::from(value) * 2 / 2, u32::from(value));
+    }
+
+    #[neotest]
+    fn test_suite_without_property() {}
+  }
+}
+
+fn main() {}
@@ -0,0 +1,41 @@
+#[cfg(test)]
+mod test {
+  use neotest::{neotest, subtest, Fixture, Result};
+  use std::sync::atomic::{AtomicUsize, Ordering};
+
+  static PREPARE_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+  /// An expensive fixture, which is only prepared once for all tests.
+  struct Index {
+    words: Vec<&'static str>,
+  }
+
+  impl Fixture for Index {
+    fn prepare() -> Result<Self> {
+      PREPARE_COUNT.fetch_add(1, Ordering::SeqCst);
+      Ok(Self {
+        words: vec!["hello", "world"],
+      })
+    }
+  }
+
+  #[neotest(fixture(scope = suite) = Index, parameter = word as ["hello", "world"])]
+  fn test_index_contains(index: &Index, word: &str) {
+    assert!(index.words.contains(&word));
+    assert_eq!(PREPARE_COUNT.load(Ordering::SeqCst), 1);
+  }
+
+  #[neotest(fixture(scope = suite) = Index)]
+  fn test_index_len(index: &Index) {
+    assert_eq!(PREPARE_COUNT.load(Ordering::SeqCst), 1);
+
+    subtest!(index_has_words, {
+      assert_eq!(index.words.len(), 2);
+    });
+    subtest!(index_is_not_empty, {
+      assert!(!index.words.is_empty());
+    })
+  }
+}
+
+fn main() {}
@@ -0,0 +1,68 @@
+#[cfg(test)]
+#[neotest::neotest_suite(
+  fixture(scope = module) = Database,
+  parameter = value as [1, 2, 3],
+  type_parameter = T as [u32, u64]
+)]
+mod test {
+  use neotest::{subtest, Fixture, Result, TestOutcome};
+  use std::sync::atomic::{AtomicUsize, Ordering};
+
+  static PREPARE_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+  /// A fixture that is shared by every test in this module.
+  pub struct Database {
+    rows: Vec<u32>,
+  }
+
+  impl Fixture for Database {
+    fn prepare() -> Result<Self> {
+      PREPARE_COUNT.fetch_add(1, Ordering::SeqCst);
+      Ok(Self {
+        rows: vec![1, 2, 3],
+      })
+    }
+
+    // Torn down once, after the last test of the module has run
+    fn tear_down(self, outcome: &TestOutcome) -> Result<()> {
+      if outcome.is_failure() {
+        eprintln!("rows at time of failure: {:?}", self.rows);
+      }
+      Ok(())
+    }
+  }
+
+  #[neotest]
+  fn test_contains_value(db: &Database, value: u32) {
+    assert!(db.rows.contains(&value));
+    assert_eq!(PREPARE_COUNT.load(Ordering::SeqCst), 1);
+  }
+
+  #[neotest(parameter = value as [3])]
+  fn test_last_value(db: &Database, value: u32) {
+    assert_eq!(db.rows.last(), Some(&value));
+  }
+
+  #[neotest]
+  fn test_generic_value<T: From<u32> + PartialOrd>(value: u32) {
+    assert!(T::from(value) > T::from(0));
+  }
+
+  // Tests may also be marked by the path of the attribute
+  #[neotest::neotest]
+  fn test_path_attribute(db: &Database) {
+    assert_eq!(db.rows.first(), Some(&1));
+  }
+
+  #[neotest]
+  fn test_rows(db: &Database) {
+    subtest!(rows_is_not_empty, {
+      assert!(!db.rows.is_empty());
+    });
+    subtest!(rows_has_three_values, {
+      assert_eq!(db.rows.len(), 3);
+    })
+  }
+}
+
+fn main() {}
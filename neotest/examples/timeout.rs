@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod test {
+  use neotest::{neotest, neotest_suite, subtest};
+  use std::thread;
+  use std::time::Duration;
+
+  #[neotest(timeout = "5s")]
+  fn test_completes_in_time() {
+    thread::sleep(Duration::from_millis(10));
+  }
+
+  #[neotest(timeout = "500ms", parameter = delay as [1, 5])]
+  fn test_each_case_completes_in_time(delay: u64) {
+    thread::sleep(Duration::from_millis(delay));
+  }
+
+  #[neotest(timeout = "1m")]
+  fn test_subtests_complete_in_time() {
+    let value = 1;
+
+    subtest!(first, {
+      assert_eq!(value, 1);
+    });
+
+    subtest!(second, {
+      thread::sleep(Duration::from_millis(1));
+    });
+  }
+
+  #[neotest(timeout = "2.5s")]
+  async fn test_async_completes_in_time() {}
+
+  #[neotest(timeout = "1s")]
+  fn test_reports_panics() -> neotest::Result<()> {
+    let result = std::panic::catch_unwind(|| panic!("expected"));
+    assert!(result.is_err());
+    Ok(())
+  }
+
+  // This is only run by `test_reports_timeout`
+  #[neotest(timeout = "10ms")]
+  #[ignore]
+  fn sleeps_past_timeout() {
+    thread::sleep(Duration::from_secs(1));
+  }
+
+  #[neotest]
+  fn test_reports_timeout() {
+    let error = sleeps_past_timeout().unwrap_err();
+
+    let timeout = error.downcast_ref::<neotest::TimeoutError>().unwrap();
+    assert_eq!(timeout.timeout(), Duration::from_millis(10));
+    assert!(timeout.test().ends_with("sleeps_past_timeout"));
+  }
+
+  /// Every test in the suite shares the same timeout, unless it sets its own.
+  #[neotest_suite(timeout = "10s")]
+  mod suite {
+    #[neotest]
+    fn test_uses_suite_timeout() {}
+
+    #[neotest(timeout = "1h")]
+    fn test_overrides_suite_timeout() {}
+  }
+}
+
+fn main() {}
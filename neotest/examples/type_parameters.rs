@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod test {
+  use neotest::{neotest, subtest};
+
+  #[neotest(type_parameter = T as [u8, u16, u32, u64, usize])]
+  fn test_default_is_zero<T>()
+  where
+    T: Default + From<u8> + PartialEq + std::fmt::Debug,
+  {
+    assert_eq!(T::default(), T::from(0u8));
+  }
+
+  #[neotest(
+    type_parameter = T as [Vec<u32>, std::collections::VecDeque<u32>],
+    parameter = len as [0, 1, 8],
+  )]
+  fn test_collection_from_iter<T>(len: u32)
+  where
+    T: FromIterator<u32> + IntoIterator<Item = u32>,
+  {
+    let sut: T = (0..len).collect();
+    let count = sut.into_iter().count() as u32;
+
+    assert_eq!(count, len);
+  }
+
+  #[neotest(
+    // Order does not need to match the generic order
+    type_parameter = U as [String, Vec<u8>],
+    type_parameter = T as [u32, u64],
+  )]
+  fn test_multiple_type_parameters<T, U>()
+  where
+    T: Default + PartialEq + std::fmt::Debug,
+    U: Default,
+  {
+    let value = T::default();
+    let _other = U::default();
+
+    subtest!(value_is_default, {
+      assert_eq!(value, T::default());
+    });
+    subtest!(value_is_not_moved, {
+      assert_eq!(value, value);
+    });
+  }
+}
+
+fn main() {}
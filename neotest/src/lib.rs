@@ -9,6 +9,9 @@
 //! * Parameterized test-cases created with combinatorial inputs, which creates
 //!   an easy mechanisms to set up multiple inputs
 //! * Parameterized generic test-cases from either types or const inputs
+//! * Property-based test-cases, whose inputs are generated by the strategies
+//!   of the [`prop`] module and shrunk when they fail -- including values of
+//!   user types that `#[derive(Arbitrary)]`
 //! * Sub-tests that each execute independently, allowing for multiple individual
 //!   test-cases that can be run per individual test.
 //!
@@ -16,12 +19,34 @@
 //!
 //! The current state of testing frameworks is... interesting.
 #[doc(inline)]
-pub use neotest_macros::{neotest, subtest, Fixture};
+pub use neotest_macros::{
+  neotest, neotest_fixture, neotest_suite, parameter_set, subtest, Fixture,
+};
 
 #[doc(inline)]
-pub use neotest_common::{Fixture, Result};
+pub use neotest_common::{
+  AsyncFixture, Fixture, GeneratedCasesError, ParameterizedFixture, PropertyError, Result,
+  SetupError, TeardownError, TestOutcome, TimeoutError,
+};
+
+/// Property-based testing, where a test is run against many values that are
+/// generated by a [`Strategy`].
+///
+/// See the [`neotest_common::prop`] module for details.
+///
+/// [`Strategy`]: neotest_common::prop::Strategy
+pub mod prop {
+  #[doc(inline)]
+  pub use neotest_common::prop::*;
+
+  #[doc(inline)]
+  pub use neotest_macros::Arbitrary;
+}
 
 #[doc(hidden, inline)]
 pub use neotest_common::__internal;
 
+#[doc(hidden)]
+pub use neotest_macros::__with_parameter_set;
+
 pub mod matcher;